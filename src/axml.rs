@@ -0,0 +1,633 @@
+//! # Android XML
+//!
+//! The Android build tools compile XML files (e.g., `AndroidManifest.xml`)
+//! before packaging. APKs contain them as binary XML chunks, while app
+//! bundles contain them as protocol buffers of `aapt2`. This module decodes
+//! both into a simple element tree, which can be turned back into text.
+
+/// ## Android XML namespace
+///
+/// Namespace URI of all attributes defined by the Android framework.
+pub const ANDROID_NS: &str = "http://schemas.android.com/apk/res/android";
+
+// Framework attributes by resource ID
+//
+// Compiled XML can omit the names of framework attributes, since the tools
+// identify them by their resource ID. This maps the IDs of the attributes
+// relevant to the manifest back to their names.
+const ATTRIBUTES: &[(u32, &str)] = &[
+    (0x01010003, "name"),
+    (0x0101020c, "minSdkVersion"),
+    (0x0101021b, "versionCode"),
+    (0x0101021c, "versionName"),
+    (0x01010270, "targetSdkVersion"),
+    (0x01010572, "compileSdkVersion"),
+];
+
+/// ## XML attribute
+///
+/// A single attribute of an XML element with its value converted to text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attribute {
+    /// Namespace URI of the attribute, if any.
+    pub namespace: Option<String>,
+    /// Local name of the attribute.
+    pub name: String,
+    /// Value of the attribute in its textual representation.
+    pub value: String,
+}
+
+/// ## XML element
+///
+/// A single XML element with all its attributes and child elements. Text
+/// content is not retained.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Element {
+    /// Namespace declarations of this element as `(prefix, uri)` pairs.
+    pub namespaces: Vec<(String, String)>,
+    /// Namespace URI of the element, if any.
+    pub namespace: Option<String>,
+    /// Local name of the element.
+    pub name: String,
+    /// All attributes of the element.
+    pub attributes: Vec<Attribute>,
+    /// All child elements of the element.
+    pub children: Vec<Element>,
+}
+
+impl Element {
+    /// ## Find attribute
+    ///
+    /// Return the value of the attribute with the given namespace URI and
+    /// local name, if present.
+    pub fn attribute(&self, namespace: Option<&str>, name: &str) -> Option<&str> {
+        self.attributes.iter()
+            .find(|v| v.namespace.as_deref() == namespace && v.name == name)
+            .map(|v| v.value.as_str())
+    }
+
+    /// ## Iterate children by name
+    ///
+    /// Return an iterator over all child elements with the given local name.
+    pub fn children_by_name<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |v| v.name == name)
+    }
+
+    // Qualify a name with the prefix of its namespace
+    fn qualify(name: &str, namespace: Option<&str>, scope: &[(String, String)]) -> String {
+        match namespace.and_then(|ns| scope.iter().rev().find(|(_, uri)| uri == ns)) {
+            Some((prefix, _)) if !prefix.is_empty() => format!("{}:{}", prefix, name),
+            _ => name.to_string(),
+        }
+    }
+
+    // Escape text for use in attribute values
+    fn escape(value: &str) -> String {
+        let mut v = String::with_capacity(value.len());
+
+        for c in value.chars() {
+            match c {
+                '&' => v.push_str("&amp;"),
+                '<' => v.push_str("&lt;"),
+                '>' => v.push_str("&gt;"),
+                '"' => v.push_str("&quot;"),
+                _ => v.push(c),
+            }
+        }
+
+        v
+    }
+
+    fn write_text(&self, out: &mut String, depth: usize, scope: &mut Vec<(String, String)>) {
+        let scope_len = scope.len();
+        scope.extend(self.namespaces.iter().cloned());
+
+        let indent = "    ".repeat(depth);
+        let name = Self::qualify(&self.name, self.namespace.as_deref(), scope);
+
+        out.push_str(&indent);
+        out.push('<');
+        out.push_str(&name);
+        for (prefix, uri) in self.namespaces.iter() {
+            if prefix.is_empty() {
+                out.push_str(&format!("\n{}    xmlns=\"{}\"", indent, Self::escape(uri)));
+            } else {
+                out.push_str(&format!("\n{}    xmlns:{}=\"{}\"", indent, prefix, Self::escape(uri)));
+            }
+        }
+        for v in self.attributes.iter() {
+            out.push_str(&format!(
+                "\n{}    {}=\"{}\"",
+                indent,
+                Self::qualify(&v.name, v.namespace.as_deref(), scope),
+                Self::escape(&v.value),
+            ));
+        }
+
+        if self.children.is_empty() {
+            out.push_str(" />\n");
+        } else {
+            out.push_str(">\n");
+            for v in self.children.iter() {
+                v.write_text(out, depth + 1, scope);
+            }
+            out.push_str(&format!("{}</{}>\n", indent, name));
+        }
+
+        scope.truncate(scope_len);
+    }
+
+    /// ## Convert to text
+    ///
+    /// Format the element and all its children as indented XML text, with
+    /// each attribute on a separate line.
+    pub fn to_text(&self) -> String {
+        let mut v = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+        self.write_text(&mut v, 0, &mut Vec::new());
+        v
+    }
+}
+
+// Name of a framework attribute by resource ID
+fn attribute_name(id: Option<u32>) -> Option<&'static str> {
+    let id = id?;
+    ATTRIBUTES.iter().find(|(k, _)| *k == id).map(|(_, v)| *v)
+}
+
+fn u16_at(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset.checked_add(2)?).map(|v| u16::from_le_bytes([v[0], v[1]]))
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset.checked_add(4)?).map(|v| u32::from_le_bytes([v[0], v[1], v[2], v[3]]))
+}
+
+// Parse a binary string pool chunk
+//
+// String pools store either UTF-8 or UTF-16 strings, each prefixed with its
+// length. UTF-8 strings carry both their UTF-16 and UTF-8 lengths.
+fn binary_string_pool(chunk: &[u8]) -> Option<Vec<String>> {
+    let header_size = u16_at(chunk, 2)? as usize;
+    let count = u32_at(chunk, 8)? as usize;
+    let utf8 = u32_at(chunk, 16)? & 0x100 != 0;
+    let start = u32_at(chunk, 20)? as usize;
+
+    let len8 = |pos: usize| -> Option<(usize, usize)> {
+        let v = *chunk.get(pos)? as usize;
+        if v & 0x80 != 0 {
+            Some((((v & 0x7f) << 8) | *chunk.get(pos + 1)? as usize, pos + 2))
+        } else {
+            Some((v, pos + 1))
+        }
+    };
+    let len16 = |pos: usize| -> Option<(usize, usize)> {
+        let v = u16_at(chunk, pos)? as usize;
+        if v & 0x8000 != 0 {
+            Some((((v & 0x7fff) << 16) | u16_at(chunk, pos + 2)? as usize, pos + 4))
+        } else {
+            Some((v, pos + 2))
+        }
+    };
+
+    let mut strings = Vec::with_capacity(count.min(chunk.len() / 4));
+    for i in 0..count {
+        let pos = start.checked_add(u32_at(chunk, header_size + i * 4)? as usize)?;
+        if utf8 {
+            let (_, pos) = len8(pos)?;
+            let (n, pos) = len8(pos)?;
+            strings.push(String::from_utf8_lossy(chunk.get(pos..pos + n)?).into_owned());
+        } else {
+            let (n, pos) = len16(pos)?;
+            let units = (0..n)
+                .map(|i| u16_at(chunk, pos + i * 2))
+                .collect::<Option<Vec<u16>>>()?;
+            strings.push(String::from_utf16_lossy(&units));
+        }
+    }
+
+    Some(strings)
+}
+
+// Format a binary typed value
+//
+// Convert the typed value of a binary attribute to text. References are
+// printed as resource IDs, since the resource table is not consulted.
+fn binary_value(data_type: u8, data: u32, strings: &[String]) -> String {
+    match data_type {
+        0x01 if data == 0 => "@null".to_string(),
+        0x01 => format!("@0x{:08x}", data),
+        0x02 => format!("?0x{:08x}", data),
+        0x03 => strings.get(data as usize).cloned().unwrap_or_default(),
+        0x04 => f32::from_bits(data).to_string(),
+        0x10 => (data as i32).to_string(),
+        0x11 => format!("0x{:x}", data),
+        0x12 => (data != 0).to_string(),
+        0x1c..=0x1f => format!("#{:08x}", data),
+        _ => format!("0x{:08x}", data),
+    }
+}
+
+// Decode binary XML
+//
+// Binary XML is a sequence of chunks: a string pool, a resource map with
+// the resource IDs of the attribute names, and a flat sequence of namespace
+// and element start/end nodes.
+fn decode_binary(data: &[u8]) -> Option<Element> {
+    let size = (u32_at(data, 4)? as usize).min(data.len());
+    let mut pos = u16_at(data, 2)? as usize;
+
+    let mut strings = Vec::new();
+    let mut resources = Vec::new();
+    let mut namespaces = Vec::new();
+    let mut stack: Vec<Element> = Vec::new();
+    let mut root = None;
+
+    while pos + 8 <= size {
+        let chunk_type = u16_at(data, pos)?;
+        let header_size = u16_at(data, pos + 2)? as usize;
+        let chunk_size = u32_at(data, pos + 4)? as usize;
+        if chunk_size < 8 {
+            return None;
+        }
+        let chunk = data.get(pos..pos.checked_add(chunk_size)?)?;
+
+        let string = |index: u32| strings.get(index as usize).cloned();
+
+        match chunk_type {
+            // String pool
+            0x0001 => {
+                strings = binary_string_pool(chunk)?;
+            },
+            // Resource map
+            0x0180 => {
+                resources = (header_size..chunk_size)
+                    .step_by(4)
+                    .filter_map(|v| u32_at(chunk, v))
+                    .collect();
+            },
+            // Namespace start
+            0x0100 => {
+                namespaces.push((
+                    string(u32_at(chunk, header_size)?).unwrap_or_default(),
+                    string(u32_at(chunk, header_size + 4)?)?,
+                ));
+            },
+            // Element start
+            0x0102 => {
+                let mut element = Element {
+                    namespaces: std::mem::take(&mut namespaces),
+                    namespace: string(u32_at(chunk, header_size)?),
+                    name: string(u32_at(chunk, header_size + 4)?)?,
+                    ..Default::default()
+                };
+
+                let start = header_size + u16_at(chunk, header_size + 8)? as usize;
+                let stride = u16_at(chunk, header_size + 10)? as usize;
+                let count = u16_at(chunk, header_size + 12)? as usize;
+                for i in 0..count {
+                    let v = start + i * stride;
+                    let index = u32_at(chunk, v + 4)?;
+                    let name = string(index)
+                        .filter(|v| !v.is_empty())
+                        .or_else(|| attribute_name(resources.get(index as usize).copied()).map(|v| v.to_string()))
+                        .unwrap_or_else(|| format!("attr{}", index));
+                    let value = match u32_at(chunk, v + 8)? {
+                        u32::MAX => binary_value(*chunk.get(v + 15)?, u32_at(chunk, v + 16)?, &strings),
+                        raw => string(raw).unwrap_or_default(),
+                    };
+
+                    element.attributes.push(Attribute {
+                        namespace: string(u32_at(chunk, v)?),
+                        name,
+                        value,
+                    });
+                }
+
+                stack.push(element);
+            },
+            // Element end
+            0x0103 => {
+                let element = stack.pop()?;
+                match stack.last_mut() {
+                    Some(v) => v.children.push(element),
+                    None => root = root.or(Some(element)),
+                }
+            },
+            _ => {},
+        }
+
+        pos += chunk_size;
+    }
+
+    root
+}
+
+// Protocol buffer field value
+enum Proto<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+}
+
+// Parse protocol buffer fields
+//
+// Split an encoded protocol buffer message into its fields. Fixed-size
+// fields are skipped, since `aapt2` XML does not use them for anything
+// relevant here.
+fn proto_fields(mut data: &[u8]) -> Option<Vec<(u64, Proto<'_>)>> {
+    fn varint(data: &mut &[u8]) -> Option<u64> {
+        let mut v = 0u64;
+        for shift in (0..64).step_by(7) {
+            let (b, rest) = data.split_first()?;
+            *data = rest;
+            v |= ((b & 0x7f) as u64) << shift;
+            if b & 0x80 == 0 {
+                return Some(v);
+            }
+        }
+        None
+    }
+
+    let mut fields = Vec::new();
+    while !data.is_empty() {
+        let key = varint(&mut data)?;
+        match key & 7 {
+            0 => fields.push((key >> 3, Proto::Varint(varint(&mut data)?))),
+            1 => data = data.get(8..)?,
+            2 => {
+                let n = varint(&mut data)? as usize;
+                fields.push((key >> 3, Proto::Bytes(data.get(..n)?)));
+                data = &data[n..];
+            },
+            5 => data = data.get(4..)?,
+            _ => return None,
+        }
+    }
+
+    Some(fields)
+}
+
+fn proto_string(data: &[u8]) -> String {
+    String::from_utf8_lossy(data).into_owned()
+}
+
+// Format a compiled `aapt2` item
+//
+// Only references, strings, and the primitive types used by manifests are
+// supported.
+fn proto_item(data: &[u8]) -> Option<String> {
+    for (field, value) in proto_fields(data)? {
+        match (field, value) {
+            // Reference
+            (1, Proto::Bytes(v)) => {
+                for (field, value) in proto_fields(v)? {
+                    if let (2, Proto::Varint(id)) = (field, value) {
+                        return Some(format!("@0x{:08x}", id));
+                    }
+                }
+            },
+            // String, raw string
+            (2, Proto::Bytes(v)) | (3, Proto::Bytes(v)) => {
+                for (field, value) in proto_fields(v)? {
+                    if let (1, Proto::Bytes(v)) = (field, value) {
+                        return Some(proto_string(v));
+                    }
+                }
+            },
+            // Primitive
+            (7, Proto::Bytes(v)) => {
+                for (field, value) in proto_fields(v)? {
+                    match (field, value) {
+                        (6, Proto::Varint(v)) => return Some((v as i32).to_string()),
+                        (7, Proto::Varint(v)) => return Some(format!("0x{:x}", v as u32)),
+                        (8, Proto::Varint(v)) => return Some((v != 0).to_string()),
+                        _ => {},
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+
+    None
+}
+
+fn proto_attribute(data: &[u8]) -> Option<Attribute> {
+    let (mut namespace, mut name, mut value) = (None, String::new(), String::new());
+    let (mut id, mut item) = (None, None);
+
+    for (field, v) in proto_fields(data)? {
+        match (field, v) {
+            (1, Proto::Bytes(v)) if !v.is_empty() => namespace = Some(proto_string(v)),
+            (2, Proto::Bytes(v)) => name = proto_string(v),
+            (3, Proto::Bytes(v)) => value = proto_string(v),
+            (5, Proto::Varint(v)) => id = Some(v as u32),
+            (6, Proto::Bytes(v)) => item = proto_item(v),
+            _ => {},
+        }
+    }
+
+    if name.is_empty() {
+        name = attribute_name(id)?.to_string();
+    }
+    if value.is_empty() {
+        value = item.unwrap_or_default();
+    }
+
+    Some(Attribute { namespace, name, value })
+}
+
+// Decode an `aapt2` XML element
+fn proto_element(data: &[u8]) -> Option<Element> {
+    let mut element = Element::default();
+
+    for (field, v) in proto_fields(data)? {
+        match (field, v) {
+            (1, Proto::Bytes(v)) => {
+                let (mut prefix, mut uri) = (String::new(), String::new());
+                for (field, v) in proto_fields(v)? {
+                    match (field, v) {
+                        (1, Proto::Bytes(v)) => prefix = proto_string(v),
+                        (2, Proto::Bytes(v)) => uri = proto_string(v),
+                        _ => {},
+                    }
+                }
+                element.namespaces.push((prefix, uri));
+            },
+            (2, Proto::Bytes(v)) if !v.is_empty() => element.namespace = Some(proto_string(v)),
+            (3, Proto::Bytes(v)) => element.name = proto_string(v),
+            (4, Proto::Bytes(v)) => element.attributes.extend(proto_attribute(v)),
+            (5, Proto::Bytes(v)) => element.children.extend(proto_node(v)),
+            _ => {},
+        }
+    }
+
+    Some(element)
+}
+
+// Decode an `aapt2` XML node
+//
+// Nodes are either elements or text. Text is not retained.
+fn proto_node(data: &[u8]) -> Option<Element> {
+    proto_fields(data)?.into_iter().find_map(|(field, v)| match (field, v) {
+        (1, Proto::Bytes(v)) => proto_element(v),
+        _ => None,
+    })
+}
+
+/// ## Decode compiled XML
+///
+/// Decode a compiled XML file, either in the binary XML format of APKs, or
+/// in the `aapt2` protocol buffer format of app bundles. The root element is
+/// returned.
+pub fn decode(data: &[u8]) -> Result<Element, ()> {
+    if u16_at(data, 0) == Some(0x0003) && u16_at(data, 2) == Some(0x0008) {
+        decode_binary(data).ok_or(())
+    } else {
+        proto_node(data).filter(|v| !v.name.is_empty()).ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Encode a binary XML chunk
+    fn chunk(chunk_type: u16, header: &[u8], body: &[u8]) -> Vec<u8> {
+        let mut v = Vec::new();
+        v.extend(chunk_type.to_le_bytes());
+        v.extend((8 + header.len() as u16).to_le_bytes());
+        v.extend((8 + header.len() as u32 + body.len() as u32).to_le_bytes());
+        v.extend(header);
+        v.extend(body);
+        v
+    }
+
+    // Encode a protocol buffer length-delimited field
+    fn field(number: u64, data: &[u8]) -> Vec<u8> {
+        let mut v = vec![(number << 3 | 2) as u8];
+        let mut n = data.len();
+        while n >= 0x80 {
+            v.push((n as u8 & 0x7f) | 0x80);
+            n >>= 7;
+        }
+        v.push(n as u8);
+        v.extend(data);
+        v
+    }
+
+    // Verify binary XML decoding
+    //
+    // Encode a small manifest with a UTF-16 string pool, a resource map for
+    // the nameless `versionCode` attribute, and a nested element.
+    #[test]
+    fn decode_binary_basic() {
+        let strings = ["android", ANDROID_NS, "manifest", "package", "foo.bar", "", "uses-permission", "name", "INTERNET"];
+        let mut pool_body = Vec::new();
+        let mut pool_data = Vec::new();
+        for v in strings.iter() {
+            pool_body.extend((pool_data.len() as u32).to_le_bytes());
+            let units: Vec<u16> = v.encode_utf16().collect();
+            pool_data.extend((units.len() as u16).to_le_bytes());
+            for u in units {
+                pool_data.extend(u.to_le_bytes());
+            }
+            pool_data.extend([0, 0]);
+        }
+        pool_body.extend(pool_data);
+        let mut pool_header = Vec::new();
+        pool_header.extend((strings.len() as u32).to_le_bytes());
+        pool_header.extend(0u32.to_le_bytes());
+        pool_header.extend(0u32.to_le_bytes());
+        pool_header.extend((28 + strings.len() as u32 * 4).to_le_bytes());
+        pool_header.extend(0u32.to_le_bytes());
+
+        let node = |v: &[u32]| -> (Vec<u8>, Vec<u8>) {
+            (
+                [1u32, u32::MAX].iter().flat_map(|v| v.to_le_bytes()).collect(),
+                v.iter().flat_map(|v| v.to_le_bytes()).collect(),
+            )
+        };
+        let element = |ns: u32, name: u32, attrs: &[(u32, u32, u32, u8, u32)]| -> Vec<u8> {
+            let (header, _) = node(&[]);
+            let mut body = Vec::new();
+            body.extend(ns.to_le_bytes());
+            body.extend(name.to_le_bytes());
+            body.extend(20u16.to_le_bytes());
+            body.extend(20u16.to_le_bytes());
+            body.extend((attrs.len() as u16).to_le_bytes());
+            body.extend([0u8; 6]);
+            for (ns, name, raw, data_type, data) in attrs {
+                body.extend(ns.to_le_bytes());
+                body.extend(name.to_le_bytes());
+                body.extend(raw.to_le_bytes());
+                body.extend(8u16.to_le_bytes());
+                body.extend([0, *data_type]);
+                body.extend(data.to_le_bytes());
+            }
+            chunk(0x0102, &header, &body)
+        };
+
+        let mut body = Vec::new();
+        body.extend(chunk(0x0001, &pool_header, &pool_body));
+        body.extend(chunk(0x0180, &[], &[0u32, 0, 0, 0, 0, 0x0101021b].iter().flat_map(|v| v.to_le_bytes()).collect::<Vec<_>>()));
+        let (h, b) = node(&[0, 1]);
+        body.extend(chunk(0x0100, &h, &b));
+        body.extend(element(u32::MAX, 2, &[
+            (u32::MAX, 3, 4, 0x03, 4),
+            (1, 5, u32::MAX, 0x10, 42),
+        ]));
+        body.extend(element(u32::MAX, 6, &[(1, 7, 8, 0x03, 8)]));
+        let (h, b) = node(&[u32::MAX, 6]);
+        body.extend(chunk(0x0103, &h, &b));
+        let (h, b) = node(&[u32::MAX, 2]);
+        body.extend(chunk(0x0103, &h, &b));
+        let data = chunk(0x0003, &[], &body);
+
+        let v = decode(&data).unwrap();
+        assert_eq!(v.name, "manifest");
+        assert_eq!(v.namespaces, [("android".to_string(), ANDROID_NS.to_string())]);
+        assert_eq!(v.attribute(None, "package"), Some("foo.bar"));
+        assert_eq!(v.attribute(Some(ANDROID_NS), "versionCode"), Some("42"));
+        let p: Vec<_> = v.children_by_name("uses-permission").collect();
+        assert_eq!(p.len(), 1);
+        assert_eq!(p[0].attribute(Some(ANDROID_NS), "name"), Some("INTERNET"));
+
+        assert_eq!(
+            v.to_text(),
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n",
+                "<manifest\n",
+                "    xmlns:android=\"http://schemas.android.com/apk/res/android\"\n",
+                "    package=\"foo.bar\"\n",
+                "    android:versionCode=\"42\">\n",
+                "    <uses-permission\n",
+                "        android:name=\"INTERNET\" />\n",
+                "</manifest>\n",
+            ),
+        );
+
+        assert!(decode(&data[..data.len() - 20]).is_err());
+    }
+
+    // Verify `aapt2` XML decoding
+    //
+    // Encode a small manifest with a compiled primitive attribute and a
+    // nested element.
+    #[test]
+    fn decode_proto_basic() {
+        let ns = [field(1, b"android"), field(2, ANDROID_NS.as_bytes())].concat();
+        let package = [field(2, b"package"), field(3, b"foo.bar")].concat();
+        let item = field(7, &[6 << 3, 42]);
+        let code = [field(1, ANDROID_NS.as_bytes()), vec![5 << 3, 0x9b, 0x84, 0x84, 0x08], field(6, &item)].concat();
+        let child = field(1, &[field(3, b"application")].concat());
+        let root = [field(1, &ns), field(3, b"manifest"), field(4, &package), field(4, &code), field(5, &child)].concat();
+        let data = field(1, &root);
+
+        let v = decode(&data).unwrap();
+        assert_eq!(v.name, "manifest");
+        assert_eq!(v.attribute(None, "package"), Some("foo.bar"));
+        assert_eq!(v.attribute(Some(ANDROID_NS), "versionCode"), Some("42"));
+        assert_eq!(v.children.len(), 1);
+        assert_eq!(v.children[0].name, "application");
+    }
+}
@@ -43,7 +43,7 @@ impl Cli {
         );
 
         cmd = cmd.subcommand(
-            clap::Command::new("build")
+            Self::build_args(clap::Command::new("build"))
                 .about("Build artifacts for the specified platform")
                 .arg(
                     clap::Arg::new("platform")
@@ -55,6 +55,34 @@ impl Cli {
                 )
         );
 
+        cmd = cmd.subcommand(
+            clap::Command::new("clean")
+                .about("Remove build artifacts of the specified platform")
+                .arg(
+                    clap::Arg::new("platform")
+                        .long("platform")
+                        .value_name("NAME")
+                        .help("ID of the target platform to operate on (all platforms if omitted)")
+                        .value_parser(clap::builder::ValueParser::string())
+                )
+                .arg(
+                    clap::Arg::new("keep-caches")
+                        .long("keep-caches")
+                        .value_name("BOOL")
+                        .help("Whether to retain the Gradle caches")
+                        .default_value("false")
+                        .value_parser(clap::builder::ValueParser::bool())
+                )
+                .arg(
+                    clap::Arg::new("persistent")
+                        .long("persistent")
+                        .value_name("BOOL")
+                        .help("Whether to remove unmodified persistent platform integration")
+                        .default_value("false")
+                        .value_parser(clap::builder::ValueParser::bool())
+                )
+        );
+
         cmd = cmd.subcommand(
             clap::Command::new("emerge")
                 .about("Create a persisting platform integration")
@@ -76,11 +104,278 @@ impl Cli {
                 )
         );
 
+        cmd = cmd.subcommand(
+            clap::Command::new("init")
+                .about("Create a new platform manifest from the Cargo metadata")
+                .arg(
+                    clap::Arg::new("package")
+                        .long("package")
+                        .value_name("NAME")
+                        .help("Name of the Cargo package implementing the application")
+                        .value_parser(clap::builder::ValueParser::string())
+                )
+                .arg(
+                    clap::Arg::new("id")
+                        .long("id")
+                        .value_name("ID")
+                        .help("ID of the application")
+                        .value_parser(clap::builder::ValueParser::string())
+                )
+                .arg(
+                    clap::Arg::new("name")
+                        .long("name")
+                        .value_name("NAME")
+                        .help("Human-readable name of the application")
+                        .value_parser(clap::builder::ValueParser::string())
+                )
+                .arg(
+                    clap::Arg::new("platform")
+                        .long("platform")
+                        .value_name("NAME")
+                        .help("ID of a target platform to add (can be repeated)")
+                        .action(clap::ArgAction::Append)
+                        .value_parser(clap::builder::ValueParser::string())
+                )
+                .arg(
+                    clap::Arg::new("domain")
+                        .long("domain")
+                        .value_name("DOMAIN")
+                        .help("Domain of the publisher, used to derive namespaces")
+                        .value_parser(clap::builder::ValueParser::string())
+                )
+                .arg(
+                    clap::Arg::new("sdk-path")
+                        .long("sdk-path")
+                        .value_name("PATH")
+                        .help("Path to the Android SDK")
+                        .value_parser(clap::builder::ValueParser::string())
+                )
+                .arg(
+                    clap::Arg::new("interactive")
+                        .long("interactive")
+                        .value_name("BOOL")
+                        .help("Whether to confirm proposed values interactively")
+                        .default_value("true")
+                        .value_parser(clap::builder::ValueParser::bool())
+                )
+        );
+
+        cmd = cmd.subcommand(
+            clap::Command::new("manifest")
+                .about("Query or modify the platform manifest")
+                .subcommand_required(true)
+                .subcommand(
+                    clap::Command::new("get")
+                        .about("Print the value of a manifest key")
+                        .arg(
+                            clap::Arg::new("key")
+                                .value_name("KEY")
+                                .help("Dotted path of the key (e.g., 'platform.<id>.android.version-code')")
+                                .required(true)
+                                .value_parser(clap::builder::ValueParser::string())
+                        )
+                )
+                .subcommand(
+                    clap::Command::new("set")
+                        .about("Set the value of a manifest key")
+                        .arg(
+                            clap::Arg::new("key")
+                                .value_name("KEY")
+                                .help("Dotted path of the key (e.g., 'platform.<id>.android.version-code')")
+                                .required(true)
+                                .value_parser(clap::builder::ValueParser::string())
+                        )
+                        .arg(
+                            clap::Arg::new("value")
+                                .value_name("VALUE")
+                                .help("TOML value to set, used as string if not valid TOML")
+                                .required(true)
+                                .value_parser(clap::builder::ValueParser::string())
+                        )
+                )
+                .subcommand(
+                    clap::Command::new("add-platform")
+                        .about("Add a platform to the manifest")
+                        .arg(
+                            clap::Arg::new("platform")
+                                .long("platform")
+                                .value_name("NAME")
+                                .help("ID of the platform to add")
+                                .required(true)
+                                .value_parser(clap::builder::ValueParser::string())
+                        )
+                        .arg(
+                            clap::Arg::new("kind")
+                                .long("kind")
+                                .value_name("KIND")
+                                .help("Kind of the platform to add")
+                                .default_value("android")
+                                .value_parser(["android"])
+                        )
+                        .arg(
+                            clap::Arg::new("domain")
+                                .long("domain")
+                                .value_name("DOMAIN")
+                                .help("Domain of the publisher, used to derive namespaces")
+                                .required(true)
+                                .value_parser(clap::builder::ValueParser::string())
+                        )
+                        .arg(
+                            clap::Arg::new("sdk-path")
+                                .long("sdk-path")
+                                .value_name("PATH")
+                                .help("Path to the Android SDK")
+                                .value_parser(clap::builder::ValueParser::string())
+                        )
+                )
+                .subcommand(
+                    clap::Command::new("remove-platform")
+                        .about("Remove a platform from the manifest")
+                        .arg(
+                            clap::Arg::new("platform")
+                                .long("platform")
+                                .value_name("NAME")
+                                .help("ID of the platform to remove")
+                                .required(true)
+                                .value_parser(clap::builder::ValueParser::string())
+                        )
+                )
+        );
+
+        cmd = cmd.subcommand(
+            Self::build_args(clap::Command::new("run"))
+                .about("Build, deploy, and launch the application on a device")
+                .arg(
+                    clap::Arg::new("platform")
+                        .long("platform")
+                        .value_name("NAME")
+                        .help("ID of the target platform to operate on")
+                        .required(true)
+                        .value_parser(clap::builder::ValueParser::string())
+                )
+                .arg(
+                    clap::Arg::new("variant")
+                        .long("variant")
+                        .value_name("NAME")
+                        .help("Build variant to build and deploy")
+                        .default_value("debug")
+                        .value_parser(clap::builder::ValueParser::string())
+                )
+                .arg(
+                    clap::Arg::new("device")
+                        .long("device")
+                        .value_name("SERIAL")
+                        .help("Serial of the device to deploy to")
+                        .value_parser(clap::builder::ValueParser::string())
+                )
+                .arg(
+                    clap::Arg::new("adb")
+                        .long("adb")
+                        .value_name("PATH")
+                        .help("Path to the adb executable to use")
+                        .value_parser(clap::builder::ValueParser::path_buf())
+                )
+                .arg(
+                    clap::Arg::new("log")
+                        .long("log")
+                        .value_name("BOOL")
+                        .help("Whether to stream the application log after launch")
+                        .default_value("true")
+                        .value_parser(clap::builder::ValueParser::bool())
+                )
+        );
+
+        cmd = cmd.subcommand(
+            clap::Command::new("inspect")
+                .about("Inspect the content of a built artifact")
+                .arg(
+                    clap::Arg::new("platform")
+                        .long("platform")
+                        .value_name("NAME")
+                        .help("ID of the platform whose manifest to compare the artifact against")
+                        .value_parser(clap::builder::ValueParser::string())
+                )
+                .arg(
+                    clap::Arg::new("artifact")
+                        .value_name("PATH")
+                        .help("Path to the APK or app bundle to inspect")
+                        .required(true)
+                        .value_parser(clap::builder::ValueParser::path_buf())
+                )
+        );
+
+        cmd = cmd.subcommand(
+            clap::Command::new("symbolicate")
+                .about("Resolve native backtraces via the archived debug symbols")
+                .arg(
+                    clap::Arg::new("platform")
+                        .long("platform")
+                        .value_name("NAME")
+                        .help("ID of the target platform to operate on")
+                        .required(true)
+                        .value_parser(clap::builder::ValueParser::string())
+                )
+                .arg(
+                    clap::Arg::new("variant")
+                        .long("variant")
+                        .value_name("NAME")
+                        .help("Build variant whose debug symbols to use")
+                        .default_value("release")
+                        .value_parser(clap::builder::ValueParser::string())
+                )
+                .arg(
+                    clap::Arg::new("abi")
+                        .long("abi")
+                        .value_name("ABI")
+                        .help("ABI whose debug symbols to use, if not detected from the input")
+                        .value_parser(clap::builder::ValueParser::string())
+                )
+                .arg(
+                    clap::Arg::new("input")
+                        .value_name("PATH")
+                        .help("Path to the backtrace or tombstone to read, rather than STDIN")
+                        .value_parser(clap::builder::ValueParser::path_buf())
+                )
+        );
+
         Self {
             cmd: cmd,
         }
     }
 
+    fn build_args(cmd: clap::Command) -> clap::Command {
+        cmd
+            .arg(
+                clap::Arg::new("gradle")
+                    .long("gradle")
+                    .value_name("PATH")
+                    .help("Path to the Gradle executable to use")
+                    .value_parser(clap::builder::ValueParser::path_buf())
+            )
+            .arg(
+                clap::Arg::new("java-home")
+                    .long("java-home")
+                    .value_name("PATH")
+                    .help("Path to the Java installation to use for Gradle")
+                    .value_parser(clap::builder::ValueParser::path_buf())
+            )
+            .arg(
+                clap::Arg::new("offline")
+                    .long("offline")
+                    .value_name("BOOL")
+                    .help("Whether to build without network access")
+                    .default_value("false")
+                    .value_parser(clap::builder::ValueParser::bool())
+            )
+            .arg(
+                clap::Arg::new("prebuilt")
+                    .long("prebuilt")
+                    .value_name("DIR")
+                    .help("Path to prebuilt Rust libraries per ABI or target triple, skipping Cargo")
+                    .value_parser(clap::builder::ValueParser::path_buf())
+            )
+    }
+
     fn manifest(
         &self,
         m: &clap::ArgMatches,
@@ -170,6 +465,172 @@ impl Cli {
         }
     }
 
+    fn version_error(
+        error: &osiris_platform::manifest::ErrorVersion,
+    ) -> String {
+        match error {
+            osiris_platform::manifest::ErrorVersion::Semver(name) => {
+                format!("Version name '{}' is not a semantic version", name)
+            },
+            osiris_platform::manifest::ErrorVersion::SemverRange(name) => {
+                format!("Version name '{}' cannot be packed into a version code", name)
+            },
+            osiris_platform::manifest::ErrorVersion::GitCommitCount => {
+                "Failed to count Git commits of the application".to_string()
+            },
+            osiris_platform::manifest::ErrorVersion::Range(code) => {
+                format!("Version code {} is out of range", code)
+            },
+        }
+    }
+
+    fn catalog_error(
+        error: &osiris_platform::manifest::ErrorCatalog,
+    ) -> String {
+        match error {
+            osiris_platform::manifest::ErrorCatalog::FileRead(path) => {
+                format!("Cannot read locale file {:?}", path)
+            },
+            osiris_platform::manifest::ErrorCatalog::FileInvalid(path) => {
+                format!("Locale file {:?} is not a valid locale table", path)
+            },
+        }
+    }
+
+    fn build_finding(
+        finding: &osiris_platform::op::build::ReportFinding,
+    ) -> String {
+        let what = match &finding.kind {
+            osiris_platform::op::build::ReportFindingKind::Format => {
+                "is not a valid ELF file".to_string()
+            },
+            osiris_platform::op::build::ReportFindingKind::Machine(machine) => {
+                format!("has ELF machine {} not matching its ABI", machine)
+            },
+            osiris_platform::op::build::ReportFindingKind::Needed(lib) => {
+                format!("requires '{}', which is neither provided by Android nor packaged", lib)
+            },
+            osiris_platform::op::build::ReportFindingKind::NeededApiLevel(lib, level) => {
+                format!("requires '{}', which is only available since API level {}", lib, level)
+            },
+            osiris_platform::op::build::ReportFindingKind::TextRelocations => {
+                "requires text relocations".to_string()
+            },
+            osiris_platform::op::build::ReportFindingKind::EntryPoint("JNI_OnLoad") => {
+                "exports neither 'JNI_OnLoad' nor any 'Java_*' method".to_string()
+            },
+            osiris_platform::op::build::ReportFindingKind::EntryPoint(symbol) => {
+                format!("does not export '{}'", symbol)
+            },
+            osiris_platform::op::build::ReportFindingKind::PageAlignment(align) => {
+                format!("has a LOAD segment aligned to {:#x}, below the 16 KB page size", align)
+            },
+            osiris_platform::op::build::ReportFindingKind::ApiLevel(level) => {
+                format!("was built for API level {}, above min-sdk", level)
+            },
+        };
+
+        format!("{} library {:?} ({}) {}", finding.variant, finding.path, finding.abi, what)
+    }
+
+    fn build_options(
+        &self,
+        m_op: &clap::ArgMatches,
+    ) -> osiris_platform::op::build::Options {
+        osiris_platform::op::build::Options {
+            gradle: m_op.get_one::<std::path::PathBuf>("gradle").cloned(),
+            java_home: m_op.get_one::<std::path::PathBuf>("java-home").cloned(),
+            offline: *m_op.get_one("offline").expect("Offline-flag lacks a value"),
+            prebuilt: m_op.get_one::<std::path::PathBuf>("prebuilt").cloned(),
+            variant: None,
+        }
+    }
+
+    fn build_error(
+        &self,
+        error: osiris_platform::op::build::Error,
+    ) -> u8 {
+        match error {
+            osiris_platform::op::build::Error::ManifestKey(key) => {
+                eprintln!("Cannot build platform integration: Manifest configuration missing '{}'", key);
+                1
+            },
+            osiris_platform::op::build::Error::PlatformDirectory(dir) => {
+                eprintln!("Cannot build platform integration: Failed to access platform directory {:?}", dir);
+                1
+            },
+            osiris_platform::op::build::Error::DirectoryCreation(dir) => {
+                eprintln!("Cannot build platform integration: Failed to create directory {:?}", dir);
+                1
+            },
+            osiris_platform::op::build::Error::FileRead(file, error) => {
+                eprintln!("Cannot build platform integration: Failed to read {:?} ({})", file, error);
+                1
+            },
+            osiris_platform::op::build::Error::FileUpdate(file, error) => {
+                eprintln!("Cannot build platform integration: Failed to update {:?} ({})", file, error);
+                1
+            },
+            osiris_platform::op::build::Error::FileRemoval(file, error) => {
+                eprintln!("Cannot build platform integration: Failed to remove {:?} ({})", file, error);
+                1
+            },
+            osiris_platform::op::build::Error::Exec(cmd, error) => {
+                eprintln!("Cannot build platform integration: Failed to invoke '{}' ({})", cmd, error);
+                1
+            },
+            osiris_platform::op::build::Error::Build => {
+                eprintln!("Cannot build platform integration: Platform build failed");
+                1
+            },
+            osiris_platform::op::build::Error::GradleExec(gradle, java_home, error) => {
+                let java_home = java_home.unwrap_or_else(|| "<inherited>".into());
+                eprintln!("Cannot build platform integration: Failed to invoke Gradle {:?} with JAVA_HOME {:?} ({})", gradle, java_home, error);
+                1
+            },
+            osiris_platform::op::build::Error::Gradle(gradle, java_home) => {
+                let java_home = java_home.unwrap_or_else(|| "<inherited>".into());
+                eprintln!("Cannot build platform integration: Gradle {:?} with JAVA_HOME {:?} failed", gradle, java_home);
+                1
+            },
+            osiris_platform::op::build::Error::Version(error) => {
+                eprintln!("Cannot build platform integration: {}", Self::version_error(&error));
+                1
+            },
+            osiris_platform::op::build::Error::SigningPassword(env) => {
+                eprintln!("Cannot build platform integration: Signing password variable '{}' not set in environment", env);
+                1
+            },
+            osiris_platform::op::build::Error::Prebuilt(abi, dir) => {
+                eprintln!("Cannot build platform integration: No prebuilt library for ABI '{}' in {:?}", abi, dir);
+                1
+            },
+            osiris_platform::op::build::Error::Ndk(path) => {
+                eprintln!("Cannot build platform integration: No Android NDK with a shared C++ runtime at {:?}", path);
+                1
+            },
+            osiris_platform::op::build::Error::Lint(findings) => {
+                for v in findings.iter() {
+                    eprintln!("Error: Native {}", Self::build_finding(v));
+                }
+                eprintln!("Cannot build platform integration: Linting native libraries failed");
+                1
+            },
+            osiris_platform::op::build::Error::BridgeFile(file) => {
+                eprintln!("Cannot build platform integration: Bridge file {:?} exists, but was not generated by osiris", file);
+                1
+            },
+            osiris_platform::op::build::Error::Icon(file) => {
+                eprintln!("Cannot build platform integration: Cannot render icon image {:?}", file);
+                1
+            },
+            osiris_platform::op::build::Error::Catalog(error) => {
+                eprintln!("Cannot build platform integration: {}", Self::catalog_error(&error));
+                1
+            },
+        }
+    }
+
     fn op_build(
         &self,
         m: &clap::ArgMatches,
@@ -179,40 +640,97 @@ impl Cli {
         let metadata = self.metadata(&manifest.absolute_path(&view_application.path))?;
         let platform = self.platform(m_op, &manifest)?;
 
-        match osiris_platform::op::build::build(
+        let options = self.build_options(m_op);
+
+        let report = osiris_platform::op::build::build(
             &manifest,
             &metadata,
             platform,
+            &options,
+        ).map_err(|v| self.build_error(v))?;
+
+        if let Some(v) = report.android_version {
+            eprintln!("Built version {} (version code {})", v.name, v.code);
+        }
+        for v in report.artifacts.iter() {
+            match v.kind {
+                osiris_platform::op::build::ReportArtifactKind::Apk => {
+                    eprintln!(
+                        "Built {} artifact {:?} (ABI {}, version code {})",
+                        v.variant,
+                        v.path,
+                        v.abi.as_deref().unwrap_or("<all>"),
+                        v.version_code.map(|v| v.to_string()).unwrap_or_else(|| "<unknown>".to_string()),
+                    );
+                },
+                osiris_platform::op::build::ReportArtifactKind::Mapping => {
+                    eprintln!("Built {} R8 mapping {:?}", v.variant, v.path);
+                },
+                osiris_platform::op::build::ReportArtifactKind::DebugSymbols => {
+                    eprintln!("Built {} native debug symbols {:?}", v.variant, v.path);
+                },
+                osiris_platform::op::build::ReportArtifactKind::Aar => {
+                    eprintln!("Built {} library archive {:?}", v.variant, v.path);
+                },
+                osiris_platform::op::build::ReportArtifactKind::Pom => {
+                    eprintln!("Built Maven POM {:?}", v.path);
+                },
+            }
+        }
+        for v in report.findings.iter() {
+            eprintln!("Warning: Native {}", Self::build_finding(v));
+        }
+
+        Ok(())
+    }
+
+    fn op_clean(
+        &self,
+        m: &clap::ArgMatches,
+        m_op: &clap::ArgMatches,
+    ) -> Result<(), u8> {
+        let (manifest, view_application) = self.manifest(m)?;
+        let metadata = self.metadata(&manifest.absolute_path(&view_application.path))?;
+        let platform = match m_op.contains_id("platform") {
+            true => Some(self.platform(m_op, &manifest)?),
+            false => None,
+        };
+
+        let options = osiris_platform::op::clean::Options {
+            keep_caches: *m_op.get_one("keep-caches").expect("Keep-caches-flag lacks a value"),
+            persistent: *m_op.get_one("persistent").expect("Persistent-flag lacks a value"),
+        };
+
+        match osiris_platform::op::clean::clean(
+            &manifest,
+            &metadata,
+            platform,
+            &options,
         ) {
-            Err(osiris_platform::op::build::Error::ManifestKey(key)) => {
-                eprintln!("Cannot build platform integration: Manifest configuration missing '{}'", key);
-                Err(1)
-            },
-            Err(osiris_platform::op::build::Error::PlatformDirectory(dir)) => {
-                eprintln!("Cannot build platform integration: Failed to access platform directory {:?}", dir);
+            Err(osiris_platform::op::clean::Error::ManifestKey(key)) => {
+                eprintln!("Cannot clean platform integration: Manifest configuration missing '{}'", key);
                 Err(1)
             },
-            Err(osiris_platform::op::build::Error::DirectoryCreation(dir)) => {
-                eprintln!("Cannot build platform integration: Failed to create directory {:?}", dir);
+            Err(osiris_platform::op::clean::Error::DirectoryRemoval(dir, error)) => {
+                eprintln!("Cannot clean platform integration: Failed to remove directory {:?} ({})", dir, error);
                 Err(1)
             },
-            Err(osiris_platform::op::build::Error::FileUpdate(file, error)) => {
-                eprintln!("Cannot build platform integration: Failed to update {:?} ({})", file, error);
+            Err(osiris_platform::op::clean::Error::FileRead(file, error)) => {
+                eprintln!("Cannot clean platform integration: Failed to read {:?} ({})", file, error);
                 Err(1)
             },
-            Err(osiris_platform::op::build::Error::FileRemoval(file, error)) => {
-                eprintln!("Cannot build platform integration: Failed to remove {:?} ({})", file, error);
+            Err(osiris_platform::op::clean::Error::FileUpdate(file, error)) => {
+                eprintln!("Cannot clean platform integration: Failed to update {:?} ({})", file, error);
                 Err(1)
             },
-            Err(osiris_platform::op::build::Error::Exec(cmd, error)) => {
-                eprintln!("Cannot build platform integration: Failed to invoke '{}' ({})", cmd, error);
-                Err(1)
-            },
-            Err(osiris_platform::op::build::Error::Build) => {
-                eprintln!("Cannot build platform integration: Platform build failed");
+            Err(osiris_platform::op::clean::Error::FileRemoval(file, error)) => {
+                eprintln!("Cannot clean platform integration: Failed to remove {:?} ({})", file, error);
                 Err(1)
             },
-            Ok(_) => {
+            Ok(kept) => {
+                for v in kept.iter() {
+                    eprintln!("Retaining modified platform integration file {:?}", v);
+                }
                 Ok(())
             },
         }
@@ -223,12 +741,20 @@ impl Cli {
         m: &clap::ArgMatches,
         m_op: &clap::ArgMatches,
     ) -> Result<(), u8> {
-        let (manifest, _) = self.manifest(m)?;
+        let (manifest, view_application) = self.manifest(m)?;
         let platform = self.platform(m_op, &manifest)?;
         let update = *m_op.get_one("update").expect("Update-flag lacks a value");
 
+        // Cargo metadata is optional for emerging, since it is only used to
+        // record the platform properties. They are refreshed on every build,
+        // anyway. Hence, silently skip them when not running under cargo.
+        let metadata = osiris_platform::cargo::Metadata::cargo(
+            &manifest.absolute_path(&view_application.path),
+        ).ok();
+
         match osiris_platform::op::emerge::emerge(
             &manifest,
+            metadata.as_ref(),
             platform,
             None,
             update,
@@ -257,6 +783,407 @@ impl Cli {
                 eprintln!("Cannot emerge platform integration: Failed to remove {:?} ({})", file, error);
                 Err(1)
             },
+            Err(osiris_platform::op::emerge::Error::Version(error)) => {
+                eprintln!("Cannot emerge platform integration: {}", Self::version_error(&error));
+                Err(1)
+            },
+            Err(osiris_platform::op::emerge::Error::BridgeFile(file)) => {
+                eprintln!("Cannot emerge platform integration: Bridge file {:?} exists, but was not generated by osiris", file);
+                Err(1)
+            },
+            Err(osiris_platform::op::emerge::Error::Icon(file)) => {
+                eprintln!("Cannot emerge platform integration: Cannot render icon image {:?}", file);
+                Err(1)
+            },
+            Err(osiris_platform::op::emerge::Error::Catalog(error)) => {
+                eprintln!("Cannot emerge platform integration: {}", Self::catalog_error(&error));
+                Err(1)
+            },
+            Ok(_) => {
+                Ok(())
+            },
+        }
+    }
+
+    fn op_init(
+        &self,
+        m: &clap::ArgMatches,
+        m_op: &clap::ArgMatches,
+    ) -> Result<(), u8> {
+        // Unwrap the manifest-path from the argument. The manifest does not
+        // exist, yet, so query the Cargo metadata of its directory.
+        let manifest_path: &std::ffi::OsString = m.get_one("manifest").expect("Cannot acquire manifest path");
+        let manifest_path = std::path::Path::new(manifest_path);
+        let manifest_dir = match manifest_path.parent() {
+            Some(v) if !v.as_os_str().is_empty() => v,
+            _ => std::path::Path::new("."),
+        };
+        let metadata = self.metadata(manifest_dir)?;
+
+        let options = osiris_platform::op::init::Options {
+            package: m_op.get_one::<String>("package").cloned(),
+            id: m_op.get_one::<String>("id").cloned(),
+            name: m_op.get_one::<String>("name").cloned(),
+            platforms: m_op.get_many::<String>("platform")
+                .map(|v| v.cloned().collect())
+                .unwrap_or_default(),
+            domain: m_op.get_one::<String>("domain").cloned(),
+            sdk_path: m_op.get_one::<String>("sdk-path").cloned(),
+        };
+
+        // Only prompt if requested and connected to a terminal, so scripted
+        // invocations never block.
+        let interactive = *m_op.get_one::<bool>("interactive").expect("Interactive-flag lacks a value")
+            && <std::io::Stdin as std::io::IsTerminal>::is_terminal(&std::io::stdin());
+        let mut prompt = |question: &str, proposal: &str| -> String {
+            let mut v = String::new();
+            eprint!("{} [{}]: ", question, proposal);
+            std::io::stdin().read_line(&mut v).expect("Cannot read from STDIN");
+            v
+        };
+
+        match osiris_platform::op::init::init(
+            &metadata,
+            manifest_path,
+            &options,
+            if interactive { Some(&mut prompt) } else { None },
+        ) {
+            Err(osiris_platform::op::init::Error::Already(path)) => {
+                eprintln!("Cannot initialize platform manifest: Manifest {:?} already exists", path);
+                Err(1)
+            },
+            Err(osiris_platform::op::init::Error::PackageMissing) => {
+                eprintln!("Cannot initialize platform manifest: No cdylib package in the Cargo workspace");
+                Err(1)
+            },
+            Err(osiris_platform::op::init::Error::PackageAmbiguous(packages)) => {
+                eprintln!("Cannot initialize platform manifest: Multiple cdylib packages, select one of {:?} via --package", packages);
+                Err(1)
+            },
+            Err(osiris_platform::op::init::Error::PackageUnknown(package)) => {
+                eprintln!("Cannot initialize platform manifest: No package '{}' in the Cargo workspace", package);
+                Err(1)
+            },
+            Err(osiris_platform::op::init::Error::PlatformUnknown(platform)) => {
+                eprintln!("Cannot initialize platform manifest: Unsupported platform '{}'", platform);
+                Err(1)
+            },
+            Err(osiris_platform::op::init::Error::OptionMissing(option)) => {
+                eprintln!("Cannot initialize platform manifest: Option --{} required", option);
+                Err(1)
+            },
+            Err(osiris_platform::op::init::Error::Domain(domain)) => {
+                eprintln!("Cannot initialize platform manifest: Invalid domain '{}'", domain);
+                Err(1)
+            },
+            Err(osiris_platform::op::init::Error::Manifest) => {
+                eprintln!("Cannot initialize platform manifest: Generated manifest is invalid");
+                Err(1)
+            },
+            Err(osiris_platform::op::init::Error::FileUpdate(file, error)) => {
+                eprintln!("Cannot initialize platform manifest: Failed to write {:?} ({})", file, error);
+                Err(1)
+            },
+            Ok(_) => {
+                Ok(())
+            },
+        }
+    }
+
+    fn op_manifest(
+        &self,
+        m: &clap::ArgMatches,
+        m_op: &clap::ArgMatches,
+    ) -> Result<(), u8> {
+        let manifest_path: &std::ffi::OsString = m.get_one("manifest").expect("Cannot acquire manifest path");
+        let manifest_path = std::path::Path::new(manifest_path);
+
+        let r = match m_op.subcommand() {
+            Some(("get", m_sub)) => {
+                let key: &String = m_sub.get_one("key").expect("Cannot acquire key");
+                osiris_platform::op::manifest::get(manifest_path, key)
+                    .map(|v| println!("{}", v))
+            },
+            Some(("set", m_sub)) => {
+                let key: &String = m_sub.get_one("key").expect("Cannot acquire key");
+                let value: &String = m_sub.get_one("value").expect("Cannot acquire value");
+                osiris_platform::op::manifest::set(manifest_path, key, value)
+            },
+            Some(("add-platform", m_sub)) => {
+                let id: &String = m_sub.get_one("platform").expect("Cannot acquire platform ID");
+                let kind: &String = m_sub.get_one("kind").expect("Cannot acquire platform kind");
+                let options = osiris_platform::op::init::Options {
+                    domain: m_sub.get_one::<String>("domain").cloned(),
+                    sdk_path: m_sub.get_one::<String>("sdk-path").cloned(),
+                    ..Default::default()
+                };
+                osiris_platform::op::manifest::add_platform(manifest_path, id, kind, &options, None)
+            },
+            Some(("remove-platform", m_sub)) => {
+                let id: &String = m_sub.get_one("platform").expect("Cannot acquire platform ID");
+                osiris_platform::op::manifest::remove_platform(manifest_path, id)
+            },
+            _ => std::unreachable!(),
+        };
+
+        match r {
+            Err(osiris_platform::op::manifest::Error::FileRead(file, error)) => {
+                eprintln!("Cannot edit platform manifest: Failed to read {:?} ({})", file, error);
+                Err(1)
+            },
+            Err(osiris_platform::op::manifest::Error::Parse(file)) => {
+                eprintln!("Cannot edit platform manifest: Failed to parse {:?}", file);
+                Err(1)
+            },
+            Err(osiris_platform::op::manifest::Error::Key(key)) => {
+                eprintln!("Cannot edit platform manifest: Malformed key '{}'", key);
+                Err(1)
+            },
+            Err(osiris_platform::op::manifest::Error::KeyMissing(key)) => {
+                eprintln!("Cannot edit platform manifest: No key '{}'", key);
+                Err(1)
+            },
+            Err(osiris_platform::op::manifest::Error::KeyType(key)) => {
+                eprintln!("Cannot edit platform manifest: Key '{}' traverses a non-table value", key);
+                Err(1)
+            },
+            Err(osiris_platform::op::manifest::Error::PlatformExists(id)) => {
+                eprintln!("Cannot edit platform manifest: Platform '{}' already exists", id);
+                Err(1)
+            },
+            Err(osiris_platform::op::manifest::Error::PlatformMissing(id)) => {
+                eprintln!("Cannot edit platform manifest: No platform with ID {}", id);
+                Err(1)
+            },
+            Err(osiris_platform::op::manifest::Error::Init(osiris_platform::op::init::Error::Domain(domain))) => {
+                eprintln!("Cannot edit platform manifest: Invalid domain '{}'", domain);
+                Err(1)
+            },
+            Err(osiris_platform::op::manifest::Error::Init(_)) => {
+                eprintln!("Cannot edit platform manifest: Failed to generate platform table");
+                Err(1)
+            },
+            Err(osiris_platform::op::manifest::Error::Invalid) => {
+                eprintln!("Cannot edit platform manifest: Resulting manifest is invalid");
+                Err(1)
+            },
+            Err(osiris_platform::op::manifest::Error::FileUpdate(file, error)) => {
+                eprintln!("Cannot edit platform manifest: Failed to write {:?} ({})", file, error);
+                Err(1)
+            },
+            Ok(_) => {
+                Ok(())
+            },
+        }
+    }
+
+    fn op_run(
+        &self,
+        m: &clap::ArgMatches,
+        m_op: &clap::ArgMatches,
+    ) -> Result<(), u8> {
+        let (manifest, view_application) = self.manifest(m)?;
+        let metadata = self.metadata(&manifest.absolute_path(&view_application.path))?;
+        let platform = self.platform(m_op, &manifest)?;
+
+        let options = osiris_platform::op::run::Options {
+            build: self.build_options(m_op),
+            variant: m_op.get_one::<String>("variant").cloned(),
+            device: m_op.get_one::<String>("device").cloned(),
+            adb: m_op.get_one::<std::path::PathBuf>("adb").cloned(),
+            log: *m_op.get_one("log").expect("Log-flag lacks a value"),
+        };
+
+        match osiris_platform::op::run::run(
+            &manifest,
+            &metadata,
+            platform,
+            &options,
+        ) {
+            Err(osiris_platform::op::run::Error::ManifestKey(key)) => {
+                eprintln!("Cannot run platform integration: Manifest configuration missing '{}'", key);
+                Err(1)
+            },
+            Err(osiris_platform::op::run::Error::Build(error)) => {
+                Err(self.build_error(error))
+            },
+            Err(osiris_platform::op::run::Error::ArtifactMissing(variant)) => {
+                eprintln!("Cannot run platform integration: No build artifact for variant '{}'", variant);
+                Err(1)
+            },
+            Err(osiris_platform::op::run::Error::ArtifactAmbiguous(variant)) => {
+                eprintln!("Cannot run platform integration: Multiple build artifacts for variant '{}'", variant);
+                Err(1)
+            },
+            Err(osiris_platform::op::run::Error::ArtifactAbi(variant, abis)) => {
+                eprintln!("Cannot run platform integration: No build artifact for variant '{}' supports the device ABIs {:?}", variant, abis);
+                Err(1)
+            },
+            Err(osiris_platform::op::run::Error::DeviceMissing) => {
+                eprintln!("Cannot run platform integration: No device attached");
+                Err(1)
+            },
+            Err(osiris_platform::op::run::Error::DeviceAmbiguous(devices)) => {
+                eprintln!("Cannot run platform integration: Multiple devices attached, select one of {:?} via --device", devices);
+                Err(1)
+            },
+            Err(osiris_platform::op::run::Error::DeviceUnknown(device)) => {
+                eprintln!("Cannot run platform integration: Device '{}' not attached or not ready", device);
+                Err(1)
+            },
+            Err(osiris_platform::op::run::Error::Exec(cmd, error)) => {
+                eprintln!("Cannot run platform integration: Failed to invoke {:?} ({})", cmd, error);
+                Err(1)
+            },
+            Err(osiris_platform::op::run::Error::Tool(cmd, op)) => {
+                eprintln!("Cannot run platform integration: {:?} failed to {}", cmd, op);
+                Err(1)
+            },
+            Err(osiris_platform::op::run::Error::Process(id)) => {
+                eprintln!("Cannot run platform integration: No process of '{}' found after launch", id);
+                Err(1)
+            },
+            Ok(_) => {
+                Ok(())
+            },
+        }
+    }
+
+    fn op_inspect(
+        &self,
+        m: &clap::ArgMatches,
+        m_op: &clap::ArgMatches,
+    ) -> Result<(), u8> {
+        let path = m_op.get_one::<std::path::PathBuf>("artifact")
+            .expect("Artifact lacks a value");
+
+        let inspect_error = |error| {
+            match error {
+                osiris_platform::op::inspect::Error::ManifestKey(key) => {
+                    eprintln!("Cannot inspect artifact: Manifest configuration missing '{}'", key);
+                },
+                osiris_platform::op::inspect::Error::FileRead(file, error) => {
+                    eprintln!("Cannot inspect artifact: Failed to read {:?} ({})", file, error);
+                },
+                osiris_platform::op::inspect::Error::Archive(file) => {
+                    eprintln!("Cannot inspect artifact: {:?} is not a valid archive", file);
+                },
+                osiris_platform::op::inspect::Error::AndroidManifestMissing(file) => {
+                    eprintln!("Cannot inspect artifact: {:?} lacks an AndroidManifest.xml", file);
+                },
+                osiris_platform::op::inspect::Error::AndroidManifestFormat(file) => {
+                    eprintln!("Cannot inspect artifact: Failed to decode the AndroidManifest.xml of {:?}", file);
+                },
+                osiris_platform::op::inspect::Error::Version(error) => {
+                    eprintln!("Cannot inspect artifact: {}", Self::version_error(&error));
+                },
+            }
+            1
+        };
+
+        let report = osiris_platform::op::inspect::inspect(path).map_err(inspect_error)?;
+
+        println!("Format: {:?}", report.format);
+        println!("Entries:");
+        for v in report.entries.iter() {
+            println!("  {:>10} {:>10} {:<8} {}", v.size, v.compressed_size, v.compression, v.name);
+        }
+        println!("Native libraries:");
+        for v in report.libraries.iter() {
+            println!("  {}: {} ({} bytes)", v.abi, v.name, v.size);
+        }
+        println!("AndroidManifest.xml:");
+        print!("{}", report.android_manifest);
+        println!("Application ID: {}", report.application_id.as_deref().unwrap_or("<unset>"));
+        println!("Version code: {}", report.version_code.map(|v| v.to_string()).unwrap_or_else(|| "<unset>".to_string()));
+        println!("Version name: {}", report.version_name.as_deref().unwrap_or("<unset>"));
+        println!("Min SDK: {}", report.min_sdk.map(|v| v.to_string()).unwrap_or_else(|| "<unset>".to_string()));
+        println!("Target SDK: {}", report.target_sdk.map(|v| v.to_string()).unwrap_or_else(|| "<unset>".to_string()));
+        println!("Permissions:");
+        for v in report.permissions.iter() {
+            println!("  {}", v);
+        }
+
+        // Without a platform, there is nothing to compare against.
+        if m_op.get_one::<String>("platform").is_none() {
+            return Ok(());
+        }
+
+        let (manifest, view_application) = self.manifest(m)?;
+        let metadata = self.metadata(&manifest.absolute_path(&view_application.path))?;
+        let platform = self.platform(m_op, &manifest)?;
+
+        let mismatches = osiris_platform::op::inspect::compare(
+            &manifest,
+            &metadata,
+            platform,
+            &report,
+        ).map_err(inspect_error)?;
+
+        for v in mismatches.iter() {
+            eprintln!(
+                "Artifact differs from manifest: {} is '{}', expected '{}'",
+                v.key,
+                v.actual.as_deref().unwrap_or("<unset>"),
+                v.expected,
+            );
+        }
+
+        if mismatches.is_empty() { Ok(()) } else { Err(1) }
+    }
+
+    fn op_symbolicate(
+        &self,
+        m: &clap::ArgMatches,
+        m_op: &clap::ArgMatches,
+    ) -> Result<(), u8> {
+        let (manifest, view_application) = self.manifest(m)?;
+        let metadata = self.metadata(&manifest.absolute_path(&view_application.path))?;
+        let platform = self.platform(m_op, &manifest)?;
+
+        let options = osiris_platform::op::symbolicate::Options {
+            variant: m_op.get_one::<String>("variant").cloned(),
+            abi: m_op.get_one::<String>("abi").cloned(),
+        };
+
+        let mut input: Box<dyn std::io::BufRead> = match m_op.get_one::<std::path::PathBuf>("input") {
+            Some(path) => match std::fs::File::open(path) {
+                Ok(v) => Box::new(std::io::BufReader::new(v)),
+                Err(error) => {
+                    eprintln!("Cannot symbolicate backtrace: Failed to open {:?} ({})", path, error);
+                    return Err(1);
+                },
+            },
+            None => Box::new(std::io::stdin().lock()),
+        };
+
+        match osiris_platform::op::symbolicate::symbolicate(
+            &metadata,
+            platform,
+            &options,
+            &mut input,
+            &mut std::io::stdout().lock(),
+        ) {
+            Err(osiris_platform::op::symbolicate::Error::SymbolsMissing(variant)) => {
+                eprintln!("Cannot symbolicate backtrace: No debug symbols archived for variant '{}', build it first", variant);
+                Err(1)
+            },
+            Err(osiris_platform::op::symbolicate::Error::FileRead(file, error)) => {
+                eprintln!("Cannot symbolicate backtrace: Failed to read {:?} ({})", file, error);
+                Err(1)
+            },
+            Err(osiris_platform::op::symbolicate::Error::Library(file)) => {
+                eprintln!("Cannot symbolicate backtrace: Failed to parse native library {:?}", file);
+                Err(1)
+            },
+            Err(osiris_platform::op::symbolicate::Error::Input(error)) => {
+                eprintln!("Cannot symbolicate backtrace: Failed to read input ({})", error);
+                Err(1)
+            },
+            Err(osiris_platform::op::symbolicate::Error::Output(error)) => {
+                eprintln!("Cannot symbolicate backtrace: Failed to write output ({})", error);
+                Err(1)
+            },
             Ok(_) => {
                 Ok(())
             },
@@ -290,7 +1217,13 @@ impl Cli {
 
         match m.subcommand() {
             Some(("build", m_op)) => self.op_build(&m, &m_op),
+            Some(("clean", m_op)) => self.op_clean(&m, m_op),
             Some(("emerge", m_op)) => self.op_emerge(&m, &m_op),
+            Some(("init", m_op)) => self.op_init(&m, m_op),
+            Some(("inspect", m_op)) => self.op_inspect(&m, m_op),
+            Some(("manifest", m_op)) => self.op_manifest(&m, m_op),
+            Some(("run", m_op)) => self.op_run(&m, m_op),
+            Some(("symbolicate", m_op)) => self.op_symbolicate(&m, m_op),
             _ => std::unreachable!(),
         }
     }
@@ -29,12 +29,29 @@ pub enum Error {
     Data,
 }
 
+/// Reduced Cargo Package Metadata
+///
+/// This struct represents the reduced metadata of a single package of the
+/// cargo workspace.
+pub struct MetadataPackage {
+    /// Name of the package.
+    pub name: String,
+    /// Version of the package.
+    pub version: String,
+    /// Path to the `Cargo.toml` of the package.
+    pub manifest_path: String,
+    /// Crate types of all targets of the package (e.g., `lib`, `cdylib`,
+    /// `bin`), without duplicates.
+    pub crate_types: Vec<String>,
+}
+
 /// Reduced Cargo Metadata
 ///
 /// This struct represents the reduced cargo metadata with only the bits that
 /// are required by the crate.
 pub struct Metadata {
     pub target_directory: String,
+    pub packages: Vec<MetadataPackage>,
 }
 
 impl Metadata {
@@ -81,14 +98,56 @@ impl Metadata {
         //
         //  * `.target_directory`: Directory used by cargo to store build artifacts.
         //
+        //  * `.packages[]`: Packages of the workspace. Since dependencies are
+        //    not resolved, this only lists the workspace members. Of each, we
+        //    extract `.name`, `.version`, `.manifest_path`, and
+        //    `.targets[].crate_types`.
+        //
 
         let data_target_directory = json.get("target_directory").ok_or(Error::Data)?
             .as_str().ok_or(Error::Data)?
             .to_string();
 
+        let mut data_packages = Vec::new();
+        for package in json.get("packages").ok_or(Error::Data)?
+            .as_array().ok_or(Error::Data)?
+        {
+            let name = package.get("name").ok_or(Error::Data)?
+                .as_str().ok_or(Error::Data)?
+                .to_string();
+            let version = package.get("version").ok_or(Error::Data)?
+                .as_str().ok_or(Error::Data)?
+                .to_string();
+            let manifest_path = package.get("manifest_path").ok_or(Error::Data)?
+                .as_str().ok_or(Error::Data)?
+                .to_string();
+
+            let mut crate_types: Vec<String> = Vec::new();
+            for target in package.get("targets").ok_or(Error::Data)?
+                .as_array().ok_or(Error::Data)?
+            {
+                for v in target.get("crate_types").ok_or(Error::Data)?
+                    .as_array().ok_or(Error::Data)?
+                {
+                    let v = v.as_str().ok_or(Error::Data)?;
+                    if !crate_types.iter().any(|c| c == v) {
+                        crate_types.push(v.to_string());
+                    }
+                }
+            }
+
+            data_packages.push(MetadataPackage {
+                name,
+                version,
+                manifest_path,
+                crate_types,
+            });
+        }
+
         Ok(
             Metadata {
                 target_directory: data_target_directory,
+                packages: data_packages,
             }
         )
     }
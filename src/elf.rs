@@ -0,0 +1,186 @@
+//! # ELF Libraries
+//!
+//! Native libraries on Android are ELF shared objects. This module extracts
+//! the properties of such libraries that decide whether the dynamic linker
+//! of the Android platform can load them, without requiring any debug
+//! information. Whether debug information is present is recorded, though.
+
+use addr2line::object::{elf, Endianness, FileKind};
+use addr2line::object::read::elf::{Dyn, FileHeader, ProgramHeader, Sym};
+
+// Note type of `.note.android.ident`, carrying the API level the library was
+// built against.
+const NT_ANDROID_TYPE_IDENT: u32 = 1;
+
+/// ## ELF library
+///
+/// The properties of an ELF shared object relevant to the dynamic linker.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Library {
+    /// ELF machine of the library (`EM_*`).
+    pub machine: u16,
+    /// Whether this is a 64-bit ELF file.
+    pub is_64: bool,
+    /// Names of all libraries required via `DT_NEEDED`, in order.
+    pub needed: Vec<String>,
+    /// Whether the library requires text relocations.
+    pub text_relocations: bool,
+    /// Alignments of all `PT_LOAD` segments, in order.
+    pub load_alignments: Vec<u64>,
+    /// Names of all symbols defined and exported by the library.
+    pub exports: std::collections::BTreeSet<String>,
+    /// API level recorded by the NDK in `.note.android.ident`, if any.
+    pub api_level: Option<u32>,
+    /// Whether the library carries DWARF debug information in
+    /// `.debug_info`.
+    pub debug_info: bool,
+}
+
+impl Library {
+    /// ## Parse library
+    ///
+    /// Parse the given data as ELF shared object. Returns `None` if the data
+    /// is not a valid ELF file.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        match FileKind::parse(data).ok()? {
+            FileKind::Elf32 => Self::parse_elf::<elf::FileHeader32<Endianness>>(data),
+            FileKind::Elf64 => Self::parse_elf::<elf::FileHeader64<Endianness>>(data),
+            _ => None,
+        }
+    }
+
+    fn parse_elf<Elf>(data: &[u8]) -> Option<Self>
+    where
+        Elf: FileHeader<Endian = Endianness>,
+    {
+        let header = Elf::parse(data).ok()?;
+        let endian = header.endian().ok()?;
+        let segments = header.program_headers(endian, data).ok()?;
+        let sections = header.sections(endian, data).ok()?;
+
+        let mut v = Self {
+            machine: header.e_machine(endian),
+            is_64: header.is_class_64(),
+            debug_info: sections.section_by_name(endian, b".debug_info").is_some(),
+            ..Default::default()
+        };
+
+        for segment in segments.iter() {
+            if segment.p_type(endian) == elf::PT_LOAD {
+                v.load_alignments.push(segment.p_align(endian).into());
+            }
+
+            // The NDK CRT objects carry the API level in a note named
+            // `Android`, starting with the level as 32-bit integer.
+            if let Ok(Some(mut notes)) = segment.notes(endian, data) {
+                while let Ok(Some(note)) = notes.next() {
+                    if note.name() == b"Android"
+                        && note.n_type(endian) == NT_ANDROID_TYPE_IDENT
+                        && note.desc().len() >= 4
+                    {
+                        let desc: [u8; 4] = note.desc()[..4].try_into().ok()?;
+                        v.api_level = Some(match endian {
+                            Endianness::Little => u32::from_le_bytes(desc),
+                            Endianness::Big => u32::from_be_bytes(desc),
+                        });
+                    }
+                }
+            }
+        }
+
+        if let Some((dynamic, link)) = sections.dynamic(endian, data).ok()? {
+            let strings = sections.strings(endian, data, link).ok()?;
+            for entry in dynamic.iter() {
+                match entry.tag32(endian) {
+                    Some(elf::DT_NEEDED) => {
+                        let name = entry.string(endian, strings).ok()?;
+                        v.needed.push(String::from_utf8_lossy(name).into_owned());
+                    },
+                    Some(elf::DT_TEXTREL) => {
+                        v.text_relocations = true;
+                    },
+                    Some(elf::DT_FLAGS)
+                        if entry.val32(endian).unwrap_or(0) & elf::DF_TEXTREL != 0 =>
+                    {
+                        v.text_relocations = true;
+                    },
+                    _ => {},
+                }
+            }
+        }
+
+        let symbols = sections.symbols(endian, data, elf::SHT_DYNSYM).ok()?;
+        for symbol in symbols.iter() {
+            let bind = symbol.st_bind();
+            if symbol.st_shndx(endian) == elf::SHN_UNDEF
+                || (bind != elf::STB_GLOBAL && bind != elf::STB_WEAK)
+                || symbol.st_visibility() == elf::STV_HIDDEN
+            {
+                continue;
+            }
+            if let Ok(name) = symbol.name(endian, symbols.strings()) {
+                v.exports.insert(String::from_utf8_lossy(name).into_owned());
+            }
+        }
+
+        Some(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Verify parsing of a local ELF fixture
+    //
+    // Build a small shared library via the system C compiler, and verify the
+    // extracted properties. The test is skipped if no C compiler is available.
+    #[cfg(unix)]
+    #[test]
+    fn library_fixture() {
+        let dir = std::env::temp_dir().join(format!("osiris-elf-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("fixture.c"),
+            concat!(
+                "int JNI_OnLoad(void *vm, void *reserved) {\n",
+                "    return 0x10006;\n",
+                "}\n",
+                "static int osiris_local(void) {\n",
+                "    return 0;\n",
+                "}\n",
+            ),
+        ).unwrap();
+
+        let status = std::process::Command::new("cc")
+            .current_dir(&dir)
+            .args(["-shared", "-fPIC", "-o", "libfixture.so", "fixture.c"])
+            .status();
+        if !status.map(|v| v.success()).unwrap_or(false) {
+            std::fs::remove_dir_all(&dir).unwrap();
+            return;
+        }
+
+        let data = std::fs::read(dir.join("libfixture.so")).unwrap();
+        let library = Library::parse(&data).unwrap();
+        assert!(library.exports.contains("JNI_OnLoad"));
+        assert!(!library.exports.contains("osiris_local"));
+        assert!(!library.load_alignments.is_empty());
+        assert!(!library.text_relocations);
+        assert_eq!(library.api_level, None);
+        assert!(!library.debug_info);
+
+        let status = std::process::Command::new("cc")
+            .current_dir(&dir)
+            .args(["-shared", "-fPIC", "-g", "-o", "libfixture.so", "fixture.c"])
+            .status()
+            .unwrap();
+        assert!(status.success());
+        let data = std::fs::read(dir.join("libfixture.so")).unwrap();
+        assert!(Library::parse(&data).unwrap().debug_info);
+
+        assert_eq!(Library::parse(b"not an ELF file"), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
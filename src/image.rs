@@ -0,0 +1,205 @@
+//! # Image Rendering
+//!
+//! Platforms require application images, like launcher icons, in a range of
+//! sizes and variants. This module renders them from a single source image,
+//! which is either a PNG or SVG file. Vector images are rasterized at the
+//! target size, raster images are scaled with a bicubic filter. No external
+//! tools are required.
+
+use resvg::usvg::TreeParsing;
+
+/// ## Source image
+///
+/// A decoded source image, ready to be rendered at any size.
+pub enum Source {
+    /// Raster image decoded from a PNG file.
+    Raster(tiny_skia::Pixmap),
+    /// Vector image parsed from an SVG file.
+    Vector(Box<resvg::usvg::Tree>),
+}
+
+impl Source {
+    /// ## Parse source image
+    ///
+    /// Parse the given data as PNG, if it carries the PNG signature, or as
+    /// SVG otherwise. Relative references of SVG images are resolved against
+    /// `dir`, if given. Returns `None` if the data is not a valid image.
+    pub fn parse(data: &[u8], dir: Option<&std::path::Path>) -> Option<Self> {
+        if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+            return tiny_skia::Pixmap::decode_png(data).ok().map(Self::Raster);
+        }
+
+        let options = resvg::usvg::Options {
+            resources_dir: dir.map(|v| v.to_path_buf()),
+            ..Default::default()
+        };
+        let mut tree = resvg::usvg::Tree::from_data(data, &options).ok()?;
+
+        // Finalize the tree as required for rendering. Text is not laid
+        // out, since no fonts are loaded, so sources should convert text to
+        // paths beforehand.
+        tree.calculate_abs_transforms();
+        tree.calculate_bounding_boxes();
+
+        Some(Self::Vector(Box::new(tree)))
+    }
+
+    /// ## Load source image
+    ///
+    /// Read the file at the given path and parse it via `parse()`.
+    pub fn load(path: &std::path::Path) -> Option<Self> {
+        let data = std::fs::read(path).ok()?;
+        Self::parse(&data, path.parent())
+    }
+
+    fn size(&self) -> (f32, f32) {
+        match self {
+            Self::Raster(v) => (v.width() as f32, v.height() as f32),
+            Self::Vector(v) => (v.size.width(), v.size.height()),
+        }
+    }
+
+    /// ## Render source image
+    ///
+    /// Render the image onto a transparent square canvas of `size` pixels.
+    /// The image is scaled to fit a centered square of `content` pixels,
+    /// retaining its aspect ratio.
+    pub fn render(&self, size: u32, content: f32) -> Option<tiny_skia::Pixmap> {
+        let mut v = tiny_skia::Pixmap::new(size, size)?;
+
+        let (width, height) = self.size();
+        if width <= 0.0 || height <= 0.0 {
+            return None;
+        }
+        let scale = content / width.max(height);
+        let transform = tiny_skia::Transform::from_row(
+            scale,
+            0.0,
+            0.0,
+            scale,
+            (size as f32 - width * scale) / 2.0,
+            (size as f32 - height * scale) / 2.0,
+        );
+
+        match self {
+            Self::Raster(source) => {
+                let paint = tiny_skia::PixmapPaint {
+                    quality: tiny_skia::FilterQuality::Bicubic,
+                    ..Default::default()
+                };
+                v.draw_pixmap(0, 0, source.as_ref(), &paint, transform, None);
+            },
+            Self::Vector(source) => {
+                resvg::render(source, transform, &mut v.as_mut());
+            },
+        }
+
+        Some(v)
+    }
+}
+
+/// ## Compose onto background
+///
+/// Create a new image of the same size as `pixmap`, filled with the given
+/// RGBA color, and draw `pixmap` on top of it.
+pub fn compose(pixmap: &tiny_skia::Pixmap, background: [u8; 4]) -> Option<tiny_skia::Pixmap> {
+    let mut v = tiny_skia::Pixmap::new(pixmap.width(), pixmap.height())?;
+
+    v.fill(tiny_skia::Color::from_rgba8(
+        background[0],
+        background[1],
+        background[2],
+        background[3],
+    ));
+    v.draw_pixmap(
+        0,
+        0,
+        pixmap.as_ref(),
+        &tiny_skia::PixmapPaint::default(),
+        tiny_skia::Transform::identity(),
+        None,
+    );
+
+    Some(v)
+}
+
+/// ## Turn into monochrome
+///
+/// Replace the color of all pixels with white, retaining only the alpha
+/// channel. This is the format of monochrome layers of themed icons, which
+/// get tinted by the platform.
+pub fn monochrome(pixmap: &mut tiny_skia::Pixmap) {
+    for v in pixmap.pixels_mut() {
+        let a = v.alpha();
+        *v = tiny_skia::PremultipliedColorU8::from_rgba(a, a, a, a)
+            .unwrap_or(tiny_skia::PremultipliedColorU8::TRANSPARENT);
+    }
+}
+
+/// ## Clip to circle
+///
+/// Clear everything outside of the circle inscribed into the image, with an
+/// anti-aliased edge.
+pub fn clip_circle(pixmap: &mut tiny_skia::Pixmap) -> Option<()> {
+    let radius = pixmap.width().min(pixmap.height()) as f32 / 2.0;
+    let path = tiny_skia::PathBuilder::from_circle(
+        pixmap.width() as f32 / 2.0,
+        pixmap.height() as f32 / 2.0,
+        radius,
+    )?;
+
+    let mut mask = tiny_skia::Mask::new(pixmap.width(), pixmap.height())?;
+    mask.fill_path(&path, tiny_skia::FillRule::Winding, true, tiny_skia::Transform::identity());
+    pixmap.apply_mask(&mask);
+
+    Some(())
+}
+
+/// ## Encode as PNG
+///
+/// Encode the image as PNG file. The encoding is deterministic, so equal
+/// images produce equal files.
+pub fn encode(pixmap: &tiny_skia::Pixmap) -> Option<Vec<u8>> {
+    pixmap.encode_png().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Verify rendering of vector and raster sources
+    //
+    // Render a small SVG into a PNG, parse it back as raster source, and
+    // verify scaling, composition, monochrome conversion and circle clipping
+    // on a few selected pixels.
+    #[test]
+    fn render_basic() {
+        let svg = concat!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"10\" height=\"10\">",
+            "<rect width=\"10\" height=\"10\" fill=\"#ff0000\"/>",
+            "</svg>",
+        );
+        let source = Source::parse(svg.as_bytes(), None).unwrap();
+        let v = source.render(20, 10.0).unwrap();
+        assert_eq!(v.pixel(0, 0).unwrap().alpha(), 0);
+        assert_eq!(v.pixel(10, 10).unwrap().demultiply().red(), 0xff);
+
+        let png = encode(&v).unwrap();
+        assert_eq!(encode(&v).unwrap(), png);
+        let source = Source::parse(&png, None).unwrap();
+        let v = source.render(40, 40.0).unwrap();
+        assert_eq!(v.width(), 40);
+        assert_eq!(v.pixel(0, 0).unwrap().alpha(), 0);
+        assert_eq!(v.pixel(20, 20).unwrap().alpha(), 0xff);
+
+        let mut v = compose(&v, [0x00, 0x00, 0xff, 0xff]).unwrap();
+        assert_eq!(v.pixel(0, 0).unwrap().demultiply().blue(), 0xff);
+        clip_circle(&mut v).unwrap();
+        assert_eq!(v.pixel(0, 0).unwrap().alpha(), 0);
+        monochrome(&mut v);
+        assert_eq!(v.pixel(20, 20).unwrap().demultiply().blue(), 0xff);
+        assert_eq!(v.pixel(20, 20).unwrap().demultiply().red(), 0xff);
+
+        assert!(Source::parse(b"not an image", None).is_none());
+    }
+}
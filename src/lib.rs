@@ -71,6 +71,9 @@
 //!  * [Web App] (WIP)
 //!  * [Windows App] (WIP)
 
+mod axml;
+mod elf;
+mod image;
 mod util;
 
 pub mod cargo;
@@ -83,7 +86,13 @@ pub mod manifest;
 /// and can be used independently.
 pub mod op {
     pub mod build;
+    pub mod clean;
     pub mod emerge;
+    pub mod init;
+    pub mod inspect;
+    pub mod manifest;
+    pub mod run;
+    pub mod symbolicate;
 }
 
 /// ## Platform Integration
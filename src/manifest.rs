@@ -14,6 +14,37 @@ pub enum ErrorView {
     MissingKey(&'static str),
 }
 
+/// Catalog Loading Errors
+///
+/// This is the exhaustive list of possible errors raised when loading the
+/// localization catalog of a manifest.
+#[derive(Debug)]
+pub enum ErrorCatalog {
+    /// The locale file at the specified path cannot be read.
+    FileRead(std::ffi::OsString),
+    /// The locale file at the specified path is not a valid locale table.
+    FileInvalid(std::ffi::OsString),
+}
+
+/// Version Resolution Errors
+///
+/// This is the exhaustive list of possible errors raised when resolving the
+/// version of a platform integration at build time.
+#[derive(Debug)]
+pub enum ErrorVersion {
+    /// The specified version name is not a semantic version, but the
+    /// version code was requested to be derived from it.
+    Semver(String),
+    /// The specified version name has components too large to be packed
+    /// into a version code.
+    SemverRange(String),
+    /// The number of Git commits could not be determined.
+    GitCommitCount,
+    /// The resolved version code, including any ABI offset, exceeds the
+    /// maximum version code.
+    Range(u64),
+}
+
 /// Raw Manifest Application Table
 ///
 /// Sub-type of `Raw` representing the `Application` table. This contains all
@@ -33,6 +64,278 @@ pub struct RawApplication {
 
     /// Name of the Cargo package that implements the application entry-point.
     pub package: Option<String>,
+
+    /// Icon of the application, used to generate the launcher icons of
+    /// each platform.
+    pub icon: Option<RawApplicationIcon>,
+
+    /// Branding of the application, used to style the platform integration.
+    pub branding: Option<RawApplicationBranding>,
+
+    /// Language tag (BCP 47) of the locale used by `name` and `strings`.
+    pub locale: Option<String>,
+    /// String resources of the application in the default locale, mapped
+    /// by their name.
+    pub strings: Option<std::collections::BTreeMap<String, String>>,
+    /// Translations of the application, mapped by the language tag
+    /// (BCP 47) of their locale.
+    pub locales: Option<std::collections::BTreeMap<String, RawApplicationLocale>>,
+}
+
+/// Raw Manifest Application Branding Table
+///
+/// Sub-type of `RawApplication` describing the visual identity of the
+/// application. All colors use the `#RRGGBB` notation, and each color has a
+/// variant for dark mode.
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RawApplicationBranding {
+    /// Primary brand color, used for prominent UI elements.
+    pub primary: Option<String>,
+    /// Accent color, used to highlight secondary UI elements.
+    pub accent: Option<String>,
+    /// Background color of the application windows.
+    pub background: Option<String>,
+    /// Primary brand color in dark mode.
+    pub primary_dark: Option<String>,
+    /// Accent color in dark mode.
+    pub accent_dark: Option<String>,
+    /// Background color in dark mode.
+    pub background_dark: Option<String>,
+
+    /// Path to the image shown on splash screens, relative from the
+    /// manifest. Must be a PNG or SVG file.
+    pub splash_icon: Option<String>,
+    /// Whether the application draws behind the system bars, rather than
+    /// letting the system draw them in the background color. Requires an
+    /// activity stub, so it has no effect with `NativeActivity`.
+    pub edge_to_edge: Option<bool>,
+}
+
+/// Raw Manifest Application Locale Table
+///
+/// Sub-type of `RawApplication` holding the translations for a single
+/// locale. Translations can be given inline, or in a separate TOML file
+/// with the same keys (except `file`). Inline entries take precedence.
+#[derive(Default, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RawApplicationLocale {
+    /// Path to a TOML file with further translations, relative from the
+    /// manifest.
+    pub file: Option<String>,
+    /// Translated human-readable name of the application.
+    pub name: Option<String>,
+    /// Translated string resources, mapped by their name. Only names of
+    /// the default string resources can be translated.
+    pub strings: Option<std::collections::BTreeMap<String, String>>,
+}
+
+/// Raw Manifest Application Icon Table
+///
+/// Sub-type of `RawApplication` describing the application icon. A single
+/// source image is rendered into all sizes and variants required by the
+/// target platforms.
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RawApplicationIcon {
+    /// Path to the source image relative from the manifest. Must be a PNG
+    /// or SVG file. The image should be square with transparent background,
+    /// as it is placed on top of the background color.
+    pub path: Option<String>,
+    /// Background color of the icon in `#RRGGBB` notation.
+    pub background: Option<String>,
+    /// Path to a separate source image for monochrome (themed) icons,
+    /// relative from the manifest. Only the alpha channel of the image is
+    /// used.
+    pub monochrome: Option<String>,
+}
+
+/// Android Activity Kinds
+///
+/// Sub-type of `RawPlatformAndroid` selecting the kind of entry-point
+/// activity used by the application.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RawPlatformAndroidActivityKind {
+    /// Java `AppCompatActivity` stub that loads the Rust library via
+    /// `System.loadLibrary()` and shows a layout.
+    AppCompat,
+    /// `android.app.NativeActivity` of the Android framework. No Java code
+    /// is shipped by the application.
+    NativeActivity,
+    /// `GameActivity` of the Android Game Development Kit, as used by
+    /// `android-activity` and friends.
+    GameActivity,
+}
+
+/// Android Artifacts
+///
+/// Sub-type of `RawPlatformAndroid` selecting the kind of artifact produced
+/// by the platform integration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RawPlatformAndroidArtifact {
+    /// Android application, packaged as APK.
+    Application,
+    /// Android library, packaged as AAR for embedding into existing
+    /// applications. Options that only apply to applications (e.g., signing
+    /// or ABI splits) are ignored.
+    Library,
+}
+
+/// Android Languages
+///
+/// Sub-type of `RawPlatformAndroid` selecting the language of the emerged
+/// platform integration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RawPlatformAndroidLanguage {
+    /// Java sources with Groovy-based Gradle build files.
+    Java,
+    /// Kotlin sources with Gradle build files in the Kotlin DSL.
+    Kotlin,
+}
+
+/// Android Native Integrations
+///
+/// Sub-type of `RawPlatformAndroid` selecting how the Rust library is built
+/// as part of the Gradle build.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RawPlatformAndroidIntegration {
+    /// Cargo is invoked directly from Gradle via the rust-android-gradle
+    /// plugin by Mozilla.
+    RustAndroidGradle,
+    /// Cargo is invoked via the CMake integration of the Android Gradle
+    /// Plugin, using Corrosion to import the Cargo package.
+    Cmake,
+}
+
+/// Android ELF Lint Levels
+///
+/// Sub-type of `RawPlatformAndroid` selecting how findings of the ELF lint
+/// of packaged native libraries are treated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RawPlatformAndroidElfLint {
+    /// Native libraries are not linted.
+    Allow,
+    /// Findings are reported, but the build succeeds.
+    Warn,
+    /// Findings are reported and fail the build.
+    Deny,
+}
+
+/// Android Version Code Strategies
+///
+/// Sub-type of `RawPlatformAndroidVersionCode` selecting how the version code
+/// is derived at build time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RawPlatformAndroidVersionCodeStrategy {
+    /// Pack the semantic version of the version name as
+    /// `major * 10000 + minor * 100 + patch`. Pre-release and build metadata
+    /// are ignored.
+    Semver,
+    /// Use the number of commits reachable from the Git `HEAD` of the
+    /// application.
+    GitCommitCount,
+    /// Use the number of minutes since 2020-01-01 UTC at build time.
+    Timestamp,
+}
+
+/// Android Version Codes
+///
+/// Sub-type of `RawPlatformAndroid` specifying the version code either
+/// explicitly as integer, or as the name of a derivation strategy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(untagged)]
+pub enum RawPlatformAndroidVersionCode {
+    /// Explicit version code.
+    Explicit(u32),
+    /// Version code derived at build time.
+    Strategy(RawPlatformAndroidVersionCodeStrategy),
+}
+
+/// Android Signing Table
+///
+/// Sub-type of `RawPlatformAndroid` specifying how release builds are signed.
+/// Passwords are never part of the manifest. Instead, the names of the
+/// environment variables holding them are specified.
+#[derive(Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RawPlatformAndroidSigning {
+    /// Path to the keystore relative from the manifest.
+    pub keystore: Option<String>,
+    /// Alias of the signing key in the keystore.
+    pub key_alias: Option<String>,
+    /// Environment variable holding the keystore password.
+    pub store_password_env: Option<String>,
+    /// Environment variable holding the key password.
+    pub key_password_env: Option<String>,
+}
+
+/// Android Bridge Types
+///
+/// Sub-type of `RawPlatformAndroidBridgeMethod` selecting the type of an
+/// argument or of the return value of a bridge method.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RawPlatformAndroidBridgeType {
+    /// Java `boolean`, Rust `bool`.
+    Boolean,
+    /// Java `byte`, Rust `i8`.
+    Byte,
+    /// Java `char`, Rust `u16`.
+    Char,
+    /// Java `short`, Rust `i16`.
+    Short,
+    /// Java `int`, Rust `i32`.
+    Int,
+    /// Java `long`, Rust `i64`.
+    Long,
+    /// Java `float`, Rust `f32`.
+    Float,
+    /// Java `double`, Rust `f64`.
+    Double,
+    /// Java `String`, Rust `String`.
+    String,
+    /// Java `byte[]`, Rust `Vec<u8>`.
+    ByteArray,
+    /// Java `void`, Rust `()`. Only valid as return type.
+    Void,
+}
+
+/// Android Bridge Method Table
+///
+/// Sub-type of `RawPlatformAndroidBridge` describing the signature of a
+/// single native method.
+#[derive(Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RawPlatformAndroidBridgeMethod {
+    /// Types of all arguments, in order.
+    pub args: Option<Vec<RawPlatformAndroidBridgeType>>,
+    /// Type of the return value.
+    pub ret: Option<RawPlatformAndroidBridgeType>,
+}
+
+/// Android Bridge Table
+///
+/// Sub-type of `RawPlatformAndroid` describing the native methods of a Java
+/// class implemented in Rust. The Java or Kotlin class and the Rust stubs
+/// are generated from this description.
+#[derive(Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RawPlatformAndroidBridge {
+    /// Name of the bridge class, either fully qualified or relative to the
+    /// namespace.
+    pub class: Option<String>,
+    /// Path to the generated Rust file relative from the manifest.
+    pub rust_file: Option<String>,
+    /// Path of the Rust module implementing the methods.
+    pub rust_module: Option<String>,
+    /// Native methods of the bridge, by name.
+    pub methods: Option<std::collections::BTreeMap<String, RawPlatformAndroidBridgeMethod>>,
 }
 
 /// Android-Platform Table
@@ -48,17 +351,49 @@ pub struct RawPlatformAndroid {
     pub application_id: Option<String>,
     pub namespace: Option<String>,
 
+    pub artifact: Option<RawPlatformAndroidArtifact>,
+    pub library_facade: Option<bool>,
+    pub maven_group: Option<String>,
+
+    pub activity_kind: Option<RawPlatformAndroidActivityKind>,
+    pub language: Option<RawPlatformAndroidLanguage>,
+    pub integration: Option<RawPlatformAndroidIntegration>,
+    pub corrosion_path: Option<String>,
+
     pub compile_sdk: Option<u32>,
     pub min_sdk: Option<u32>,
     pub target_sdk: Option<u32>,
 
     pub abis: Option<Vec<String>>,
+    pub abi_splits: Option<bool>,
+    pub universal_apk: Option<bool>,
     pub ndk_level: Option<u32>,
+    pub ndk_path: Option<String>,
+    pub elf_lint: Option<RawPlatformAndroidElfLint>,
+
+    pub native_libraries: Option<std::collections::BTreeMap<String, Vec<String>>>,
+    pub cxx_shared: Option<bool>,
 
-    pub version_code: Option<u32>,
+    pub version_code: Option<RawPlatformAndroidVersionCode>,
+    pub version_code_abi_offsets: Option<std::collections::BTreeMap<String, u32>>,
     pub version_name: Option<String>,
 
+    pub signing: Option<RawPlatformAndroidSigning>,
+
+    pub minify: Option<bool>,
+    pub shrink_resources: Option<bool>,
+    pub jni_classes: Option<Vec<String>>,
+    pub bridge: Option<RawPlatformAndroidBridge>,
+
     pub sdk_path: Option<String>,
+
+    pub gradle_version: Option<String>,
+    pub gradle_path: Option<String>,
+    pub java_home: Option<String>,
+
+    pub versions: Option<std::collections::BTreeMap<String, String>>,
+    pub dependencies: Option<Vec<String>>,
+    pub maven_repositories: Option<Vec<String>>,
 }
 
 #[derive(serde::Deserialize)]
@@ -124,6 +459,80 @@ pub struct ViewApplication {
     pub package: String,
     /// Valid symbol name generated from the package name.
     pub package_symbol: String,
+
+    /// Same as `RawApplication.icon`
+    pub icon: Option<ViewApplicationIcon>,
+    /// Same as `RawApplication.branding`
+    pub branding: Option<ViewApplicationBranding>,
+}
+
+/// Color Palette
+///
+/// A set of brand colors as RGBA quadruples. The palette is platform
+/// independent, so all platforms can use it for their styles (e.g., the
+/// theme color of a web manifest).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ViewPalette {
+    /// Primary brand color.
+    pub primary: [u8; 4],
+    /// Accent color.
+    pub accent: [u8; 4],
+    /// Background color.
+    pub background: [u8; 4],
+}
+
+/// Manifest View of `RawApplicationBranding`
+///
+/// This is a view of `RawApplicationBranding` with suitable defaults.
+pub struct ViewApplicationBranding {
+    /// Palette used in light mode.
+    pub light: ViewPalette,
+    /// Palette used in dark mode.
+    pub dark: ViewPalette,
+    /// Same as `RawApplicationBranding.splash_icon`
+    pub splash_icon: Option<String>,
+    /// Same as `RawApplicationBranding.edge_to_edge`
+    pub edge_to_edge: bool,
+}
+
+/// Manifest View of `RawApplicationIcon`
+///
+/// This is a view of `RawApplicationIcon` with suitable defaults.
+pub struct ViewApplicationIcon {
+    /// Same as `RawApplicationIcon.path`
+    pub path: String,
+    /// Same as `RawApplicationIcon.background`, parsed as RGBA. Defaults to
+    /// white.
+    pub background: [u8; 4],
+    /// Same as `RawApplicationIcon.monochrome`
+    pub monochrome: Option<String>,
+}
+
+/// Localization Catalog
+///
+/// This combines the default strings of `RawApplication` with the
+/// translations of all locales, including the content of referenced locale
+/// files. The catalog is platform independent, so all platforms can use it
+/// for their localized resources and display names.
+pub struct ViewCatalog {
+    /// Same as `RawApplication.locale`, defaulting to `en`.
+    pub locale: String,
+    /// Same as `RawApplication.strings`.
+    pub strings: std::collections::BTreeMap<String, String>,
+    /// All translations, mapped by the language tag of their locale.
+    pub locales: std::collections::BTreeMap<String, ViewCatalogLocale>,
+}
+
+/// Localization Catalog Locale
+///
+/// Sub-type of `ViewCatalog` holding the merged translations of a single
+/// locale.
+#[derive(Default)]
+pub struct ViewCatalogLocale {
+    /// Same as `RawApplicationLocale.name`
+    pub name: Option<String>,
+    /// Same as `RawApplicationLocale.strings`
+    pub strings: std::collections::BTreeMap<String, String>,
 }
 
 /// Manifest View of `RawPlatformAndroid`
@@ -136,6 +545,23 @@ pub struct ViewPlatformAndroid {
     /// Same as `RawPlatformAndroid.namespace`.
     pub namespace: String,
 
+    /// Same as `RawPlatformAndroid.artifact`.
+    pub artifact: RawPlatformAndroidArtifact,
+    /// Same as `RawPlatformAndroid.library_facade`.
+    pub library_facade: bool,
+    /// Same as `RawPlatformAndroid.maven_group`. Defaults to the namespace.
+    pub maven_group: String,
+
+    /// Same as `RawPlatformAndroid.activity_kind`.
+    pub activity_kind: RawPlatformAndroidActivityKind,
+    /// Same as `RawPlatformAndroid.language`.
+    pub language: RawPlatformAndroidLanguage,
+    /// Same as `RawPlatformAndroid.integration`.
+    pub integration: RawPlatformAndroidIntegration,
+    /// Same as `RawPlatformAndroid.corrosion_path`. `None` if Corrosion is
+    /// to be located via CMake, or fetched otherwise.
+    pub corrosion_path: Option<String>,
+
     /// Same as `RawPlatformAndroid.compile_sdk`.
     pub compile_sdk: u32,
     /// Same as `RawPlatformAndroid.min_sdk`.
@@ -145,16 +571,110 @@ pub struct ViewPlatformAndroid {
 
     /// Same as `RawPlatformAndroid.abis`.
     pub abis: Vec<String>,
+    /// Same as `RawPlatformAndroid.abi_splits`.
+    pub abi_splits: bool,
+    /// Same as `RawPlatformAndroid.universal_apk`.
+    pub universal_apk: bool,
     /// Same as `RawPlatformAndroid.ndk_level`.
     pub ndk_level: u32,
+    /// Same as `RawPlatformAndroid.ndk_path`. `None` if the NDK is to be
+    /// located via the environment or the SDK.
+    pub ndk_path: Option<String>,
+    /// Same as `RawPlatformAndroid.elf_lint`.
+    pub elf_lint: RawPlatformAndroidElfLint,
+
+    /// Same as `RawPlatformAndroid.native_libraries`.
+    pub native_libraries: std::collections::BTreeMap<String, Vec<String>>,
+    /// Same as `RawPlatformAndroid.cxx_shared`.
+    pub cxx_shared: bool,
 
     /// Same as `RawPlatformAndroid.version_code`.
-    pub version_code: u32,
-    /// Same as `RawPlatformAndroid.version_name`.
-    pub version_name: String,
+    pub version_code: RawPlatformAndroidVersionCode,
+    /// Same as `RawPlatformAndroid.version_code_abi_offsets`. Defaults to
+    /// `RawPlatformAndroid::ABI_OFFSETS` if ABI splits are enabled.
+    pub version_code_abi_offsets: std::collections::BTreeMap<String, u32>,
+    /// Same as `RawPlatformAndroid.version_name`. `None` if the version of
+    /// the Cargo package is to be used.
+    pub version_name: Option<String>,
+
+    /// Same as `RawPlatformAndroid.signing`. `None` if release builds are
+    /// signed like debug builds.
+    pub signing: Option<ViewPlatformAndroidSigning>,
+
+    /// Same as `RawPlatformAndroid.minify`.
+    pub minify: bool,
+    /// Same as `RawPlatformAndroid.shrink_resources`. Defaults to `minify`.
+    pub shrink_resources: bool,
+    /// Same as `RawPlatformAndroid.jni_classes`.
+    pub jni_classes: Vec<String>,
+    /// Same as `RawPlatformAndroid.bridge`. `None` if no bridge is
+    /// generated.
+    pub bridge: Option<ViewPlatformAndroidBridge>,
 
     /// Same as `RawPlatformAndroid.sdk_path`.
     pub sdk_path: String,
+
+    /// Same as `RawPlatformAndroid.gradle_version`.
+    pub gradle_version: Option<String>,
+    /// Same as `RawPlatformAndroid.gradle_path`.
+    pub gradle_path: Option<String>,
+    /// Same as `RawPlatformAndroid.java_home`.
+    pub java_home: Option<String>,
+
+    /// Same as `RawPlatformAndroid.versions`, but merged over the default
+    /// version catalog.
+    pub versions: std::collections::BTreeMap<String, String>,
+    /// Same as `RawPlatformAndroid.dependencies`.
+    pub dependencies: Vec<String>,
+    /// Same as `RawPlatformAndroid.maven_repositories`. `None` if the
+    /// default public repositories are to be used.
+    pub maven_repositories: Option<Vec<String>>,
+}
+
+/// Manifest View of `RawPlatformAndroidSigning`
+///
+/// This is a view of `RawPlatformAndroidSigning` with suitable defaults.
+pub struct ViewPlatformAndroidSigning {
+    /// Same as `RawPlatformAndroidSigning.keystore`.
+    pub keystore: String,
+    /// Same as `RawPlatformAndroidSigning.key_alias`.
+    pub key_alias: String,
+    /// Same as `RawPlatformAndroidSigning.store_password_env`.
+    pub store_password_env: String,
+    /// Same as `RawPlatformAndroidSigning.key_password_env`.
+    pub key_password_env: String,
+}
+
+/// Manifest View of `RawPlatformAndroidBridge`
+///
+/// This is a view of `RawPlatformAndroidBridge` with suitable defaults.
+pub struct ViewPlatformAndroidBridge {
+    /// Same as `RawPlatformAndroidBridge.class`, but always fully
+    /// qualified. Defaults to `NativeBridge` in the namespace.
+    pub class: String,
+    /// Same as `RawPlatformAndroidBridge.rust_file`. Defaults to
+    /// `src/osiris_bridge.rs` in the application.
+    pub rust_file: String,
+    /// Same as `RawPlatformAndroidBridge.rust_module`. Defaults to
+    /// `crate::bridge`.
+    pub rust_module: String,
+    /// Same as `RawPlatformAndroidBridge.methods`, as `(args, ret)` pairs.
+    pub methods: std::collections::BTreeMap<
+        String,
+        (Vec<RawPlatformAndroidBridgeType>, RawPlatformAndroidBridgeType),
+    >,
+}
+
+/// Resolved Android Version
+///
+/// The version code and version name of an Android platform integration, as
+/// resolved at build time. See `ViewPlatformAndroid::resolve_version()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ViewPlatformAndroidVersion {
+    /// Resolved version code.
+    pub code: u32,
+    /// Resolved version name.
+    pub name: String,
 }
 
 /// Manifest View of `RawPlatform`
@@ -213,6 +733,14 @@ impl RawApplication {
             .ok_or(ErrorView::MissingKey(".package"))?;
         let v_package_symbol = crate::util::symbolize(v_package);
 
+        let v_icon = self.icon
+            .as_ref()
+            .map(|v| v.view())
+            .transpose()?;
+        let v_branding = self.branding
+            .as_ref()
+            .map(|v| v.view());
+
         Ok(ViewApplication {
             id: v_id.clone(),
             id_symbol: v_id_symbol,
@@ -221,11 +749,204 @@ impl RawApplication {
 
             package: v_package.clone(),
             package_symbol: v_package_symbol,
+
+            icon: v_icon,
+            branding: v_branding,
+        })
+    }
+}
+
+impl RawApplicationIcon {
+    /// Create View
+    ///
+    /// Create a new view of this `RawApplicationIcon` instance. This will
+    /// pick suitable defaults for missing values.
+    pub fn view(
+        &self,
+    ) -> Result<ViewApplicationIcon, ErrorView> {
+        // The source image is required, there is no sensible default icon
+        // to fall back to.
+        let v_path = self.path
+            .as_ref()
+            .ok_or(ErrorView::MissingKey(".icon.path"))?;
+
+        // Launcher icons are usually placed on a plain background, so use
+        // white unless specified otherwise. The color was validated when
+        // parsing the manifest.
+        let v_background = self.background
+            .as_deref()
+            .and_then(crate::util::parse_color)
+            .unwrap_or([0xff, 0xff, 0xff, 0xff]);
+
+        Ok(ViewApplicationIcon {
+            path: v_path.clone(),
+            background: v_background,
+            monochrome: self.monochrome.clone(),
+        })
+    }
+}
+
+impl RawApplicationBranding {
+    /// Default palette in light mode (Material baseline colors).
+    pub const LIGHT: ViewPalette = ViewPalette {
+        primary: [0x67, 0x50, 0xa4, 0xff],
+        accent: [0x62, 0x5b, 0x71, 0xff],
+        background: [0xff, 0xfb, 0xfe, 0xff],
+    };
+
+    /// Default palette in dark mode (Material baseline colors).
+    pub const DARK: ViewPalette = ViewPalette {
+        primary: [0xd0, 0xbc, 0xff, 0xff],
+        accent: [0xcc, 0xc2, 0xdc, 0xff],
+        background: [0x1c, 0x1b, 0x1f, 0xff],
+    };
+
+    /// Create View
+    ///
+    /// Create a new view of this `RawApplicationBranding` instance. This
+    /// will pick suitable defaults for missing values.
+    pub fn view(&self) -> ViewApplicationBranding {
+        // Colors were validated when parsing the manifest. Missing colors
+        // use the Material baseline. Missing dark variants use the light
+        // color, if specified, so a single brand color applies to both
+        // modes.
+        let color = |v: &Option<String>| v.as_deref().and_then(crate::util::parse_color);
+        let v_light = ViewPalette {
+            primary: color(&self.primary).unwrap_or(Self::LIGHT.primary),
+            accent: color(&self.accent).unwrap_or(Self::LIGHT.accent),
+            background: color(&self.background).unwrap_or(Self::LIGHT.background),
+        };
+        let v_dark = ViewPalette {
+            primary: color(&self.primary_dark)
+                .or(color(&self.primary))
+                .unwrap_or(Self::DARK.primary),
+            accent: color(&self.accent_dark)
+                .or(color(&self.accent))
+                .unwrap_or(Self::DARK.accent),
+            background: color(&self.background_dark)
+                .or(color(&self.background))
+                .unwrap_or(Self::DARK.background),
+        };
+
+        ViewApplicationBranding {
+            light: v_light,
+            dark: v_dark,
+            splash_icon: self.splash_icon.clone(),
+            edge_to_edge: self.edge_to_edge.unwrap_or(false),
+        }
+    }
+}
+
+impl ViewCatalog {
+    /// Default locale if none is specified.
+    pub const LOCALE: &'static str = "en";
+
+    /// Localized display names
+    ///
+    /// Map all locales of the catalog, including the default locale, to
+    /// the display name of the application. Locales without a translated
+    /// name use `name`, which should be the name in the default locale.
+    pub fn display_names<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> std::collections::BTreeMap<&'a str, &'a str> {
+        let mut v = std::collections::BTreeMap::new();
+
+        v.insert(self.locale.as_str(), name);
+        for (tag, locale) in self.locales.iter() {
+            v.insert(tag.as_str(), locale.name.as_deref().unwrap_or(name));
+        }
+
+        v
+    }
+
+    /// Look up a localized string
+    ///
+    /// Return the string with the given name in the given locale, falling
+    /// back to the default locale if it has no translation.
+    pub fn lookup(&self, locale: &str, key: &str) -> Option<&str> {
+        self.locales
+            .get(locale)
+            .and_then(|v| v.strings.get(key))
+            .or_else(|| self.strings.get(key))
+            .map(|v| v.as_str())
+    }
+}
+
+impl RawPlatformAndroidSigning {
+    /// Default environment variable holding the keystore password.
+    pub const STORE_PASSWORD_ENV: &'static str = "OSIRIS_ANDROID_STORE_PASSWORD";
+
+    /// Create View
+    ///
+    /// Create a new view of this `RawPlatformAndroidSigning` instance. This
+    /// will pick suitable defaults for missing values.
+    pub fn view(
+        &self,
+    ) -> Result<ViewPlatformAndroidSigning, ErrorView> {
+        // The keystore and key alias identify the signing key. There is no
+        // sensible default for either.
+        let v_keystore = self.keystore
+            .as_ref()
+            .ok_or(ErrorView::MissingKey(".signing.keystore"))?;
+        let v_key_alias = self.key_alias
+            .as_ref()
+            .ok_or(ErrorView::MissingKey(".signing.key-alias"))?;
+
+        // Keystores usually use the same password for the store and its
+        // keys (PKCS#12 even requires it), so the key password defaults to
+        // the store password.
+        let v_store_password_env = self.store_password_env
+            .as_deref()
+            .unwrap_or(Self::STORE_PASSWORD_ENV);
+        let v_key_password_env = self.key_password_env
+            .as_deref()
+            .unwrap_or(v_store_password_env);
+
+        Ok(ViewPlatformAndroidSigning {
+            keystore: v_keystore.clone(),
+            key_alias: v_key_alias.clone(),
+            store_password_env: v_store_password_env.to_string(),
+            key_password_env: v_key_password_env.to_string(),
         })
     }
 }
 
 impl RawPlatformAndroid {
+    /// Default ABI Version Code Offsets
+    ///
+    /// These offsets are added to the version code of each ABI split, if ABI
+    /// splits are enabled and no offsets are specified. Split APKs thus get
+    /// `<version-code> + <offset>`, while a universal APK retains the plain
+    /// version code. App stores serve the highest compatible version code,
+    /// so 64-bit ABIs get higher offsets than their 32-bit counterparts, and
+    /// splits are preferred over the universal APK. This leaves room for
+    /// version codes up to 1.7 billion.
+    pub const ABI_OFFSETS: &'static [(&'static str, u32)] = &[
+        ("armeabi-v7a", 100000000),
+        ("x86", 200000000),
+        ("arm64-v8a", 300000000),
+        ("x86_64", 400000000),
+    ];
+
+    /// Default Version Catalog
+    ///
+    /// This is the default version catalog used for the Gradle plugins and
+    /// libraries of the Android platform integration. Entries can be
+    /// overridden via the `versions` table.
+    pub const VERSIONS: &'static [(&'static str, &'static str)] = &[
+        ("agp", "8.10.1"),
+        ("androidx-test-junit", "1.1.5"),
+        ("appcompat", "1.6.1"),
+        ("constraintlayout", "2.1.4"),
+        ("espresso", "3.5.1"),
+        ("games-activity", "2.0.2"),
+        ("junit", "4.13.2"),
+        ("kotlin", "1.8.22"),
+        ("material", "1.9.0"),
+        ("rust-android-gradle", "0.9.3"),
+    ];
+
     /// Create View
     ///
     /// Create a new view of this `RawPlatformAndroid` instance. This will
@@ -257,6 +978,32 @@ impl RawPlatformAndroid {
                     .ok_or(ErrorView::MissingKey(".application-id"))?
             };
 
+        // Applications are built by default. Libraries ship a facade that
+        // loads the native libraries, unless disabled, and are published
+        // under the namespace as Maven group, unless specified otherwise.
+        let v_artifact = self.artifact
+            .unwrap_or(RawPlatformAndroidArtifact::Application);
+        let v_library_facade = self.library_facade.unwrap_or(true);
+        let v_maven_group = self.maven_group
+            .as_ref()
+            .unwrap_or(v_namespace);
+
+        // The entry-point activity defaults to a Java `AppCompatActivity`
+        // stub, since it works with any Rust library. Frameworks built on
+        // `NativeActivity` or `GameActivity` must select those explicitly.
+        let v_activity_kind = self.activity_kind
+            .unwrap_or(RawPlatformAndroidActivityKind::AppCompat);
+
+        // Java and Groovy are the traditional Android languages and remain
+        // the default. Kotlin must be selected explicitly.
+        let v_language = self.language
+            .unwrap_or(RawPlatformAndroidLanguage::Java);
+
+        // rust-android-gradle is the original integration and remains the
+        // default, since it does not require CMake nor Corrosion.
+        let v_integration = self.integration
+            .unwrap_or(RawPlatformAndroidIntegration::RustAndroidGradle);
+
         // `min-sdk` specifies the minimum SDK version required. `target-sdk`
         // specifies the SDK the application is designed for, and `compile-sdk`
         // is the SDK version the build-tools used at compile time. The latter
@@ -301,23 +1048,106 @@ impl RawPlatformAndroid {
                 .iter().map(|v| v.to_string()).collect()
         };
 
+        // ABI splits produce an APK per ABI, rather than a single APK with
+        // all ABIs. They are disabled by default, and so is the additional
+        // universal APK with all ABIs when splits are enabled.
+        let v_abi_splits = self.abi_splits.unwrap_or(false);
+        let v_universal_apk = self.universal_apk.unwrap_or(false);
+
         // We must know the NDK level we build against. While we could pick a
         // suitable default, we really want the caller to decide on a version
         // so they know what they link against.
         let v_ndk_level = self.ndk_level
             .ok_or(ErrorView::MissingKey(".ndk-level"))?;
 
+        // Native libraries are linted after every build. Findings are only
+        // reported by default, since existing builds must not start failing.
+        let v_elf_lint = self.elf_lint
+            .unwrap_or(RawPlatformAndroidElfLint::Warn);
+
+        // Additional native libraries are packaged per ABI. The shared C++
+        // runtime of the NDK is only needed by applications linking C++
+        // code dynamically, so it must be requested explicitly.
+        let v_native_libraries = self.native_libraries.clone().unwrap_or_default();
+        let v_cxx_shared = self.cxx_shared.unwrap_or(false);
+
         // The version-code is a simple positive integer increased for every
         // new build. It allows the app stores to identify the builds and
         // decide which one is the most recent. The code has no other meaning.
         // The version-name is used as user-visible version and purely meant
         // as human-readable identification of the version.
-        // We can use `1` and `0.1.0` as safe default values, if not provided.
-        let v_version_code = self.version_code.unwrap_or(1);
-        let v_version_name = self.version_name
+        // We use `1` as safe default version-code, if not provided.
+        // Both can be derived at build time: the version-name from the version
+        // of the Cargo package, and the version-code via one of the
+        // strategies of `RawPlatformAndroidVersionCodeStrategy`. See
+        // `ViewPlatformAndroid::resolve_version()` for the resolution.
+        let v_version_code = self.version_code
+            .unwrap_or(RawPlatformAndroidVersionCode::Explicit(1));
+        // With ABI splits, each split needs a distinct version code. Unless
+        // the offsets are specified explicitly, use the default scheme of
+        // `ABI_OFFSETS` for all selected ABIs.
+        let v_version_code_abi_offsets = if let Some(v) = self.version_code_abi_offsets.as_ref() {
+            v.clone()
+        } else if v_abi_splits {
+            Self::ABI_OFFSETS.iter()
+                .filter(|(k, _)| v_abis.iter().any(|v| v == k))
+                .map(|(k, v)| (k.to_string(), *v))
+                .collect()
+        } else {
+            Default::default()
+        };
+        let v_version_name = self.version_name.clone();
+
+        // Release builds are signed like debug builds, unless a signing
+        // configuration is given.
+        let v_signing = self.signing
             .as_ref()
-            .map(|v| v.as_str())
-            .unwrap_or("0.1.0");
+            .map(|v| v.view())
+            .transpose()?;
+
+        // Release builds are not minified by default, since R8 strips any
+        // code only reachable via JNI unless told otherwise. With minification
+        // enabled, unused resources are stripped as well, unless disabled
+        // explicitly. Classes used by the Rust library via JNI must be listed
+        // explicitly to be retained.
+        let v_minify = self.minify.unwrap_or(false);
+        let v_shrink_resources = self.shrink_resources.unwrap_or(v_minify);
+        let v_jni_classes = self.jni_classes.clone().unwrap_or_default();
+
+        // The bridge class lives in the namespace, unless qualified. The
+        // Rust stubs are written into the application sources by default,
+        // where the application must declare them as module.
+        let v_bridge = self.bridge.as_ref().map(|v| {
+            let class = match v.class.as_deref() {
+                None => format!("{}.NativeBridge", v_namespace),
+                Some(v) if v.contains('.') => v.to_string(),
+                Some(v) => format!("{}.{}", v_namespace, v),
+            };
+            let rust_file = v.rust_file.clone().unwrap_or_else(|| {
+                let path = raw.application
+                    .as_ref()
+                    .and_then(|v| v.path.as_deref())
+                    .unwrap_or(".");
+                format!("{}/src/osiris_bridge.rs", path)
+            });
+            let methods = v.methods.iter()
+                .flatten()
+                .map(|(k, v)| (
+                    k.clone(),
+                    (
+                        v.args.clone().unwrap_or_default(),
+                        v.ret.unwrap_or(RawPlatformAndroidBridgeType::Void),
+                    ),
+                ))
+                .collect();
+
+            ViewPlatformAndroidBridge {
+                class,
+                rust_file,
+                rust_module: v.rust_module.clone().unwrap_or_else(|| "crate::bridge".to_string()),
+                methods,
+            }
+        });
 
         // We require a path to the Android SDK to build the application. There
         // is no way to guess this path, nor are there any suitable defaults.
@@ -325,21 +1155,141 @@ impl RawPlatformAndroid {
             .as_ref()
             .ok_or(ErrorView::MissingKey(".sdk-path"))?;
 
+        // Start with the default version catalog and apply any overrides from
+        // the manifest. Unknown entries are retained, so they can be used by
+        // custom build files.
+        let mut v_versions: std::collections::BTreeMap<String, String> =
+            Self::VERSIONS.iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+        if let Some(v) = self.versions.as_ref() {
+            v_versions.extend(v.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+
+        // Additional dependencies are purely optional.
+        let v_dependencies = self.dependencies.clone().unwrap_or_default();
+
         Ok(ViewPlatformAndroid {
             application_id: v_application_id.clone(),
             namespace: v_namespace.clone(),
 
+            artifact: v_artifact,
+            library_facade: v_library_facade,
+            maven_group: v_maven_group.clone(),
+
+            activity_kind: v_activity_kind,
+            language: v_language,
+            integration: v_integration,
+            corrosion_path: self.corrosion_path.clone(),
+
             compile_sdk: v_compile_sdk,
             min_sdk: v_min_sdk,
             target_sdk: v_target_sdk,
 
             abis: v_abis,
+            abi_splits: v_abi_splits,
+            universal_apk: v_universal_apk,
             ndk_level: v_ndk_level,
+            ndk_path: self.ndk_path.clone(),
+            elf_lint: v_elf_lint,
+
+            native_libraries: v_native_libraries,
+            cxx_shared: v_cxx_shared,
 
             version_code: v_version_code,
-            version_name: v_version_name.to_string(),
+            version_code_abi_offsets: v_version_code_abi_offsets,
+            version_name: v_version_name,
+
+            signing: v_signing,
+
+            minify: v_minify,
+            shrink_resources: v_shrink_resources,
+            jni_classes: v_jni_classes,
+            bridge: v_bridge,
 
             sdk_path: v_sdk_path.clone(),
+
+            gradle_version: self.gradle_version.clone(),
+            gradle_path: self.gradle_path.clone(),
+            java_home: self.java_home.clone(),
+
+            versions: v_versions,
+            dependencies: v_dependencies,
+            maven_repositories: self.maven_repositories.clone(),
+        })
+    }
+}
+
+impl ViewPlatformAndroid {
+    /// Maximum version code accepted by the Google Play Store.
+    pub const VERSION_CODE_MAX: u32 = 2100000000;
+
+    /// Resolve version
+    ///
+    /// Resolve the version code and version name of the platform
+    /// integration. The version name defaults to `package_version`, which
+    /// should be the version of the Cargo package, or `0.1.0` if not
+    /// available. Derivation strategies for the version code use
+    /// `git_commit_count` to query the number of commits, and `timestamp` as
+    /// the current time in seconds since the UNIX epoch.
+    ///
+    /// The resolved version code is verified to leave room for the largest
+    /// ABI offset.
+    pub fn resolve_version(
+        &self,
+        package_version: Option<&str>,
+        git_commit_count: &mut dyn FnMut() -> Option<u32>,
+        timestamp: u64,
+    ) -> Result<ViewPlatformAndroidVersion, ErrorVersion> {
+        let name = self.version_name
+            .as_deref()
+            .or(package_version)
+            .unwrap_or("0.1.0")
+            .to_string();
+
+        let code: u64 = match self.version_code {
+            RawPlatformAndroidVersionCode::Explicit(v) => v as u64,
+            RawPlatformAndroidVersionCode::Strategy(
+                RawPlatformAndroidVersionCodeStrategy::Semver,
+            ) => {
+                // Strip build metadata and pre-release suffixes, then split
+                // into exactly three numeric components.
+                let core = name.split('+').next().unwrap_or("");
+                let core = core.split('-').next().unwrap_or("");
+                let parts: Vec<u64> = core.split('.')
+                    .map(|v| v.parse::<u64>())
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| ErrorVersion::Semver(name.clone()))?;
+                if parts.len() != 3 {
+                    return Err(ErrorVersion::Semver(name.clone()));
+                }
+                if parts[1] >= 100 || parts[2] >= 100 {
+                    return Err(ErrorVersion::SemverRange(name.clone()));
+                }
+                parts[0] * 10000 + parts[1] * 100 + parts[2]
+            },
+            RawPlatformAndroidVersionCode::Strategy(
+                RawPlatformAndroidVersionCodeStrategy::GitCommitCount,
+            ) => {
+                git_commit_count().ok_or(ErrorVersion::GitCommitCount)? as u64
+            },
+            RawPlatformAndroidVersionCode::Strategy(
+                RawPlatformAndroidVersionCodeStrategy::Timestamp,
+            ) => {
+                // Minutes since 2020-01-01T00:00:00Z. This stays well below
+                // the maximum for centuries, but grows on every build.
+                timestamp.saturating_sub(1577836800) / 60
+            },
+        };
+
+        let max = code + self.version_code_abi_offsets.values().max().copied().unwrap_or(0) as u64;
+        if code == 0 || max > Self::VERSION_CODE_MAX as u64 {
+            return Err(ErrorVersion::Range(max));
+        }
+
+        Ok(ViewPlatformAndroidVersion {
+            code: code as u32,
+            name,
         })
     }
 }
@@ -417,6 +1367,27 @@ impl Raw {
 }
 
 impl Manifest {
+    // Reserved words of Java, Kotlin and Rust, which cannot be used as names
+    // of bridge methods. This includes the methods of `java.lang.Object`,
+    // since static methods of the bridge class cannot hide them.
+    const BRIDGE_RESERVED: &'static [&'static str] = &[
+        "_", "abstract", "as", "assert", "async", "await", "become", "boolean",
+        "box", "break", "byte", "case", "catch", "char", "class", "const",
+        "continue", "crate", "default", "do", "double", "dyn", "else", "enum",
+        "extends", "extern", "false", "final", "finally", "float", "fn", "for",
+        "fun", "gen", "goto", "if", "impl", "implements", "import", "in",
+        "instanceof", "int", "interface", "is", "let", "long", "loop", "macro",
+        "match", "mod", "move", "mut", "native", "new", "null", "object",
+        "override", "package", "priv", "private", "protected", "pub", "public",
+        "record", "ref", "return", "self", "Self", "short", "static",
+        "strictfp", "struct", "super", "switch", "synchronized", "this",
+        "throw", "throws", "trait", "transient", "true", "try", "type",
+        "typealias", "typeof", "unsafe", "unsized", "use", "val", "var",
+        "virtual", "void", "volatile", "when", "where", "while", "yield",
+        "clone", "equals", "finalize", "getClass", "hashCode", "notify",
+        "notifyAll", "toString", "wait",
+    ];
+
     // Check whether a string is a valid identifier
     //
     // This verifies that the given string consists of only alphanumeric
@@ -429,6 +1400,58 @@ impl Manifest {
         )
     }
 
+    // Check whether a string is a valid Java identifier
+    //
+    // This verifies that the given string consists of only ASCII
+    // alphanumeric characters plus `_`, and does not start with a digit.
+    // Empty identifiers are rejected. Unlike `is_identifier()`, the result
+    // is also a valid Kotlin and Rust identifier.
+    fn is_java_identifier(s: &str) -> bool {
+        !s.is_empty()
+            && !s.starts_with(|v: char| v.is_ascii_digit())
+            && s.chars().all(|v| v.is_ascii_alphanumeric() || v == '_')
+    }
+
+    // Check whether a string is a valid language tag
+    //
+    // This verifies that the given string is a BCP 47 language tag, made of
+    // `-`-separated ASCII alphanumeric subtags of up to 8 characters and
+    // starting with an alphabetic language subtag of at least 2 characters.
+    // The subtags are not checked against the registry.
+    fn is_locale_tag(s: &str) -> bool {
+        let mut subtags = s.split('-');
+        let language = subtags.next().unwrap_or("");
+
+        language.len() >= 2
+            && language.len() <= 8
+            && language.chars().all(|v| v.is_ascii_alphabetic())
+            && subtags.all(
+                |v| !v.is_empty() && v.len() <= 8 && v.chars().all(|v| v.is_ascii_alphanumeric())
+            )
+    }
+
+    // Check whether a string is a valid localized text
+    //
+    // Localized texts are escaped properly for each target, but must not
+    // contain control characters other than new-lines and tabs.
+    fn is_text(s: &str) -> bool {
+        s.chars().all(|v| !v.is_control() || v == '\n' || v == '\t')
+    }
+
+    // Check whether translations of a locale are valid
+    //
+    // Translated names and strings must be valid texts, and only the names
+    // of the default string resources can be translated.
+    fn is_locale_valid(
+        locale: &RawApplicationLocale,
+        strings: Option<&std::collections::BTreeMap<String, String>>,
+    ) -> bool {
+        locale.name.as_deref().map(Self::is_text).unwrap_or(true)
+            && locale.strings.iter().flatten().all(
+                |(k, v)| strings.map(|v| v.contains_key(k)).unwrap_or(false) && Self::is_text(v)
+            )
+    }
+
     // Check whether a string contains no quotes or escapes
     //
     // This verifies that a string does not contain quotes or backslashes, nor
@@ -447,6 +1470,14 @@ impl Manifest {
         )
     }
 
+    // Check whether a string can be quoted in Gradle build files
+    //
+    // In addition to `is_quotable()`, this refuses dollar signs, since the
+    // Kotlin DSL would expand them as string templates.
+    fn is_gradle_quotable(s: &str) -> bool {
+        Self::is_quotable(s) && !s.contains('$')
+    }
+
     /// Parse manifest from raw
     ///
     /// Take a raw representation of the manifest and perform post-parsing
@@ -480,6 +1511,75 @@ impl Manifest {
                     return Err(());
                 }
             }
+
+            // Branding colors must be valid colors, and the splash icon
+            // path must not contain control characters.
+            if let Some(branding) = &application.branding {
+                for v in [
+                    &branding.primary,
+                    &branding.accent,
+                    &branding.background,
+                    &branding.primary_dark,
+                    &branding.accent_dark,
+                    &branding.background_dark,
+                ].into_iter().flatten() {
+                    if crate::util::parse_color(v).is_none() {
+                        return Err(());
+                    }
+                }
+                if let Some(v) = &branding.splash_icon {
+                    if !v.chars().all(|v| !v.is_control()) {
+                        return Err(());
+                    }
+                }
+            }
+
+            // String resources become resource names on most platforms, so
+            // restrict them to identifiers. `app_name` is reserved for the
+            // application name.
+            if let Some(strings) = &application.strings {
+                for (k, v) in strings.iter() {
+                    if !Self::is_java_identifier(k) || k == "app_name" || !Self::is_text(v) {
+                        return Err(());
+                    }
+                }
+            }
+
+            // Locales must be valid language tags, and must not repeat the
+            // default locale. Locale files are verified when loading the
+            // catalog.
+            if let Some(v) = &application.locale {
+                if !Self::is_locale_tag(v) {
+                    return Err(());
+                }
+            }
+            if let Some(locales) = &application.locales {
+                let locale = application.locale.as_deref().unwrap_or(ViewCatalog::LOCALE);
+                for (k, v) in locales.iter() {
+                    if !Self::is_locale_tag(k)
+                        || k.eq_ignore_ascii_case(locale)
+                        || !Self::is_locale_valid(v, application.strings.as_ref())
+                        || !v.file.as_deref().map(|v| v.chars().all(|v| !v.is_control())).unwrap_or(true)
+                    {
+                        return Err(());
+                    }
+                }
+            }
+
+            // Icon paths must not contain control characters, and the
+            // background must be a valid color.
+            if let Some(icon) = &application.icon {
+                for v in [&icon.path, &icon.monochrome].into_iter().flatten() {
+                    if !v.chars().all(|v| !v.is_control()) {
+                        return Err(());
+                    }
+                }
+                if let Some(v) = &icon.background {
+                    if crate::util::parse_color(v).is_none() {
+                        return Err(());
+                    }
+                }
+            }
         }
 
         for platform in raw.platform.iter() {
@@ -498,6 +1598,47 @@ impl Manifest {
                     }
                 }
 
+                // Maven groups are written into POMs and repository paths,
+                // so only allow dot-separated identifiers.
+                if let Some(v) = &android.maven_group {
+                    if !v.split('.').all(|v| Self::is_identifier(v) && v.is_ascii()) {
+                        return Err(());
+                    }
+                }
+
+                // Native libraries are loaded by name from Java, so they must
+                // be called `lib<name>.so` with a quotable name. ABIs become
+                // directory names.
+                if let Some(libraries) = &android.native_libraries {
+                    for (abi, list) in libraries.iter() {
+                        if !Self::is_identifier(abi) || !abi.is_ascii() {
+                            return Err(());
+                        }
+                        for v in list.iter() {
+                            let name = std::path::Path::new(v)
+                                .file_name()
+                                .and_then(|v| v.to_str())
+                                .and_then(|v| v.strip_prefix("lib"))
+                                .and_then(|v| v.strip_suffix(".so"));
+                            if !v.chars().all(|v| !v.is_control())
+                                || !name.map(|v| !v.is_empty() && Self::is_quotable(v)).unwrap_or(false)
+                            {
+                                return Err(());
+                            }
+                        }
+                    }
+                }
+
+                // ABI offsets are passed to Gradle as `<abi>=<offset>` list,
+                // so ABIs must be plain identifiers.
+                if let Some(offsets) = &android.version_code_abi_offsets {
+                    for k in offsets.keys() {
+                        if !Self::is_identifier(k) || !k.is_ascii() {
+                            return Err(());
+                        }
+                    }
+                }
+
                 // Ensure version names can be put in quotes.
                 if let Some(v) = &android.version_name {
                     if !Self::is_quotable(&v) {
@@ -505,6 +1646,85 @@ impl Manifest {
                     }
                 }
 
+                // The keystore path must not contain control characters, and
+                // the key alias must be quotable. Environment variable names
+                // are restricted to the portable character-set.
+                if let Some(signing) = &android.signing {
+                    if let Some(v) = &signing.keystore {
+                        if !v.chars().all(|v| !v.is_control()) {
+                            return Err(());
+                        }
+                    }
+                    if let Some(v) = &signing.key_alias {
+                        if !Self::is_quotable(v) {
+                            return Err(());
+                        }
+                    }
+                    for v in [&signing.store_password_env, &signing.key_password_env].into_iter().flatten() {
+                        if v.is_empty()
+                            || v.starts_with(|v: char| v.is_ascii_digit())
+                            || !v.chars().all(|v| v.is_ascii_alphanumeric() || v == '_')
+                        {
+                            return Err(());
+                        }
+                    }
+                }
+
+                // Resource shrinking requires minification. JNI classes are
+                // written into ProGuard rules, so only allow Java class name
+                // characters plus wildcards.
+                if android.shrink_resources == Some(true) && android.minify != Some(true) {
+                    return Err(());
+                }
+                if let Some(classes) = &android.jni_classes {
+                    for v in classes.iter() {
+                        if v.is_empty() || !v.chars().all(
+                            |v| v.is_alphanumeric() || v == '_' || v == '$' || v == '.' || v == '*'
+                        ) {
+                            return Err(());
+                        }
+                    }
+                }
+
+                // The bridge is written into Java, Kotlin and Rust sources,
+                // so restrict all names to ASCII identifiers. Reserved words
+                // of either language are refused, and `void` is only valid
+                // as return type.
+                if let Some(bridge) = &android.bridge {
+                    if let Some(v) = &bridge.class {
+                        // The main activity and the library facade are
+                        // generated in the namespace as well.
+                        let local = match v.rsplit_once('.') {
+                            None => Some(v.as_str()),
+                            Some((ns, name)) if Some(ns) == android.namespace.as_deref() => Some(name),
+                            Some(_) => None,
+                        };
+                        if !v.split('.').all(Self::is_java_identifier)
+                            || local.map(|v| v == "MainActivity" || v == "NativeLibrary").unwrap_or(false)
+                        {
+                            return Err(());
+                        }
+                    }
+                    if let Some(v) = &bridge.rust_file {
+                        if !v.chars().all(|v| !v.is_control()) {
+                            return Err(());
+                        }
+                    }
+                    if let Some(v) = &bridge.rust_module {
+                        if !v.split("::").all(Self::is_java_identifier) {
+                            return Err(());
+                        }
+                    }
+                    for (k, v) in bridge.methods.iter().flatten() {
+                        if !Self::is_java_identifier(k)
+                            || Self::BRIDGE_RESERVED.contains(&k.as_str())
+                            || v.args.iter().flatten().any(|v| *v == RawPlatformAndroidBridgeType::Void)
+                        {
+                            return Err(());
+                        }
+                    }
+                }
+
                 // Verify that the SDK path does not contain new-lines nor
                 // control characters.
                 if let Some(sdk_path) = &android.sdk_path {
@@ -512,6 +1732,63 @@ impl Manifest {
                         return Err(());
                     }
                 }
+
+                // The Gradle version is interpolated into the distribution
+                // URL, so only allow characters used in version strings.
+                if let Some(v) = &android.gradle_version {
+                    if v.is_empty() || !v.chars().all(
+                        |v| v.is_ascii_alphanumeric() || v == '.' || v == '-'
+                    ) {
+                        return Err(());
+                    }
+                }
+
+                // Verify that the Gradle, Java, NDK, and Corrosion paths do not
+                // contain new-lines nor control characters.
+                for path in [
+                    &android.gradle_path,
+                    &android.java_home,
+                    &android.ndk_path,
+                    &android.corrosion_path,
+                ].into_iter().flatten() {
+                    if !path.chars().all(|v| !v.is_control() && v != '\n') {
+                        return Err(());
+                    }
+                }
+
+                // Version catalog entries must be valid ASCII identifiers to
+                // be usable as TOML keys and Gradle aliases. The versions
+                // must be quotable.
+                if let Some(versions) = &android.versions {
+                    for (k, v) in versions.iter() {
+                        if !Self::is_identifier(k)
+                            || !k.is_ascii()
+                            || !Self::is_quotable(v)
+                        {
+                            return Err(());
+                        }
+                    }
+                }
+
+                // Ensure Maven repository URLs and paths can be put in quotes
+                // of Gradle build files.
+                if let Some(repositories) = &android.maven_repositories {
+                    for v in repositories.iter() {
+                        if !Self::is_gradle_quotable(v) {
+                            return Err(());
+                        }
+                    }
+                }
+
+                // Ensure dependency coordinates can be put in quotes of Gradle
+                // build files.
+                if let Some(dependencies) = &android.dependencies {
+                    for v in dependencies.iter() {
+                        if !Self::is_gradle_quotable(v) {
+                            return Err(());
+                        }
+                    }
+                }
             }
         }
 
@@ -553,6 +1830,50 @@ impl Manifest {
             .and_then(|v| Self::parse_str(path, &v))
     }
 
+    /// Load localization catalog
+    ///
+    /// Combine the default strings and all translations of the application
+    /// into a catalog. Referenced locale files are read and verified like
+    /// inline translations, which take precedence over them.
+    pub fn catalog(&self) -> Result<ViewCatalog, ErrorCatalog> {
+        let application = self.raw.application.as_ref();
+        let strings = application.and_then(|v| v.strings.as_ref());
+
+        let mut v_locales = std::collections::BTreeMap::new();
+        for (tag, raw) in application.and_then(|v| v.locales.as_ref()).into_iter().flatten() {
+            let mut v = ViewCatalogLocale::default();
+
+            if let Some(file) = &raw.file {
+                let path = self.absolute_path(file);
+                let content = std::fs::read_to_string(&path)
+                    .map_err(|_| ErrorCatalog::FileRead(path.as_os_str().to_os_string()))?;
+                let file_raw = content.parse::<toml::Table>()
+                    .ok()
+                    .and_then(|v| <RawApplicationLocale as serde::Deserialize>::deserialize(v).ok())
+                    .filter(|v| v.file.is_none() && Self::is_locale_valid(v, strings))
+                    .ok_or_else(|| ErrorCatalog::FileInvalid(path.as_os_str().to_os_string()))?;
+
+                v.name = file_raw.name;
+                v.strings.extend(file_raw.strings.into_iter().flatten());
+            }
+
+            if let Some(name) = &raw.name {
+                v.name = Some(name.clone());
+            }
+            v.strings.extend(raw.strings.iter().flatten().map(|(k, v)| (k.clone(), v.clone())));
+
+            v_locales.insert(tag.clone(), v);
+        }
+
+        Ok(ViewCatalog {
+            locale: application
+                .and_then(|v| v.locale.clone())
+                .unwrap_or_else(|| ViewCatalog::LOCALE.to_string()),
+            strings: strings.cloned().unwrap_or_default(),
+            locales: v_locales,
+        })
+    }
+
     /// Turn an embedded path into an absolute path
     ///
     /// All paths in a manifest are relative to the location of the manifest,
@@ -568,9 +1889,31 @@ impl Manifest {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
+    // Parse a manifest with a minimal Android platform, with the given lines
+    // appended to its `[platform.android]` table. This is shared with the
+    // tests of the operations.
+    pub(crate) fn android(extra: &str) -> Result<Manifest, ()> {
+        let s = format!("
+            version = 1
+            [application]
+            id = \"test\"
+            package = \"test\"
+            path = \"app\"
+            [[platform]]
+            id = \"test\"
+            [platform.android]
+            namespace = \"foo.bar\"
+            min-sdk = 29
+            ndk-level = 21
+            sdk-path = \"/sdk\"
+            {}
+        ", extra);
+        Manifest::parse_str(&".", &s)
+    }
+
     // Verify basic parsing of `Raw`
     //
     // Parse a minimal raw manifest into `Raw` to have a base-level test for
@@ -709,6 +2052,177 @@ mod tests {
         assert!(Manifest::parse_str(&".", s).is_err());
     }
 
+    // Verify parsing of the application branding
+    //
+    // Missing colors use the Material baseline, and dark variants fall back
+    // to the light color if given. Invalid colors must be refused.
+    #[test]
+    fn manifest_parse_application_branding() {
+        let s = "
+            version = 1
+            [application]
+            id = \"foo\"
+            package = \"foo\"
+            [application.branding]
+        ";
+
+        let m = Manifest::parse_str(&".", s).unwrap();
+        let v = m.raw.view_application().ok().unwrap().branding.unwrap();
+        assert_eq!(v.light, RawApplicationBranding::LIGHT);
+        assert_eq!(v.dark, RawApplicationBranding::DARK);
+        assert!(v.splash_icon.is_none());
+        assert!(!v.edge_to_edge);
+
+        let s = "
+            version = 1
+            [application]
+            id = \"foo\"
+            package = \"foo\"
+            [application.branding]
+            primary = \"#102030\"
+            background = \"#ffffff\"
+            background-dark = \"#000\"
+            splash-icon = \"splash.svg\"
+            edge-to-edge = true
+        ";
+
+        let m = Manifest::parse_str(&".", s).unwrap();
+        let v = m.raw.view_application().ok().unwrap().branding.unwrap();
+        assert_eq!(v.light.primary, [0x10, 0x20, 0x30, 0xff]);
+        assert_eq!(v.dark.primary, [0x10, 0x20, 0x30, 0xff]);
+        assert_eq!(v.light.accent, RawApplicationBranding::LIGHT.accent);
+        assert_eq!(v.dark.accent, RawApplicationBranding::DARK.accent);
+        assert_eq!(v.light.background, [0xff, 0xff, 0xff, 0xff]);
+        assert_eq!(v.dark.background, [0x00, 0x00, 0x00, 0xff]);
+        assert_eq!(v.splash_icon.unwrap(), "splash.svg");
+        assert!(v.edge_to_edge);
+
+        let s = "
+            version = 1
+            [application.branding]
+            accent-dark = \"#12345\"
+        ";
+
+        assert!(Manifest::parse_str(&".", s).is_err());
+    }
+
+    // Verify parsing of localizations
+    //
+    // Parse default strings and translations, both inline and from a locale
+    // file, and verify the resulting catalog. Invalid language tags and
+    // translations of unknown strings must be refused.
+    #[test]
+    fn manifest_parse_application_locales() {
+        let dir = std::env::temp_dir().join(format!("osiris-locales-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("fr.toml"),
+            "name = \"Truc\"\n[strings]\nhello = \"Bonjour\"\nbye = \"Salut\"\n",
+        ).unwrap();
+
+        let s = "
+            version = 1
+            [application]
+            name = \"Foo\"
+            strings = { hello = \"Hello\", bye = \"Bye\" }
+            [application.locales]
+            de = { name = \"Dings\", strings = { hello = \"Hallo\" } }
+            fr = { file = \"fr.toml\", strings = { bye = \"Au revoir\" } }
+        ";
+
+        let m = Manifest::parse_str(&dir.join("osiris-platform.toml"), s).unwrap();
+        let c = m.catalog().unwrap();
+        assert_eq!(c.locale, "en");
+        assert_eq!(c.lookup("de", "hello"), Some("Hallo"));
+        assert_eq!(c.lookup("de", "bye"), Some("Bye"));
+        assert_eq!(c.lookup("fr", "hello"), Some("Bonjour"));
+        assert_eq!(c.lookup("fr", "bye"), Some("Au revoir"));
+        assert_eq!(c.lookup("it", "bye"), Some("Bye"));
+        assert_eq!(c.lookup("de", "none"), None);
+        assert_eq!(
+            c.display_names("Foo").into_iter().collect::<Vec<_>>(),
+            vec![("de", "Dings"), ("en", "Foo"), ("fr", "Truc")],
+        );
+
+        std::fs::write(dir.join("fr.toml"), "[strings]\nnone = \"Rien\"\n").unwrap();
+        let m = Manifest::parse_str(&dir.join("osiris-platform.toml"), s).unwrap();
+        assert!(matches!(m.catalog(), Err(ErrorCatalog::FileInvalid(_))));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(matches!(m.catalog(), Err(ErrorCatalog::FileRead(_))));
+
+        for v in [
+            "locales = { de_DE = { name = \"Foo\" } }",
+            "locales = { en = { name = \"Foo\" } }",
+            "locales = { de = { strings = { none = \"Foo\" } } }",
+            "strings = { app_name = \"Foo\" }",
+            "strings = { \"foo-bar\" = \"Foo\" }",
+            "locale = \"e\"",
+        ] {
+            let s = format!("version = 1\n[application]\n{}\n", v);
+            assert!(Manifest::parse_str(&".", &s).is_err());
+        }
+    }
+
+    // Verify parsing of the application icon
+    //
+    // The background defaults to white and must be a valid color, and the
+    // source image is required to create a view.
+    #[test]
+    fn manifest_parse_application_icon() {
+        let s = "
+            version = 1
+            [application]
+            id = \"foo\"
+            package = \"foo\"
+            [application.icon]
+            path = \"icon.svg\"
+        ";
+
+        let m = Manifest::parse_str(&".", s).unwrap();
+        let v = m.raw.view_application().ok().unwrap().icon.unwrap();
+        assert_eq!(v.path, "icon.svg");
+        assert_eq!(v.background, [0xff, 0xff, 0xff, 0xff]);
+        assert!(v.monochrome.is_none());
+
+        let s = "
+            version = 1
+            [application]
+            id = \"foo\"
+            package = \"foo\"
+            [application.icon]
+            path = \"icon.png\"
+            background = \"#102030\"
+            monochrome = \"mono.svg\"
+        ";
+
+        let m = Manifest::parse_str(&".", s).unwrap();
+        let v = m.raw.view_application().ok().unwrap().icon.unwrap();
+        assert_eq!(v.background, [0x10, 0x20, 0x30, 0xff]);
+        assert_eq!(v.monochrome.unwrap(), "mono.svg");
+
+        let s = "
+            version = 1
+            [application]
+            id = \"foo\"
+            package = \"foo\"
+            [application.icon]
+            background = \"#102030\"
+        ";
+
+        let m = Manifest::parse_str(&".", s).unwrap();
+        assert!(m.raw.view_application().is_err());
+
+        let s = "
+            version = 1
+            [application.icon]
+            path = \"icon.png\"
+            background = \"red\"
+        ";
+
+        assert!(Manifest::parse_str(&".", s).is_err());
+    }
+
     // Verify parsing of android platform sdk-paths
     //
     // The manifest verifies that these paths cannot contain newlines. No
@@ -736,4 +2250,396 @@ mod tests {
 
         assert!(Manifest::parse_str(&".", s).is_err());
     }
+
+    // Verify parsing of android platform activity-kinds
+    //
+    // The activity kind is a closed set of kebab-case identifiers. Verify
+    // they are parsed and the view falls back to the `AppCompat` stub.
+    #[test]
+    fn manifest_parse_platform_android_activity_kind() {
+        let s = "
+            version = 1
+            [[platform]]
+            id = \"test\"
+            [platform.android]
+            activity-kind = \"native-activity\"
+        ";
+
+        let m = Manifest::parse_str(&".", s).unwrap();
+        assert_eq!(
+            m.raw.platform[0].android().unwrap().activity_kind.unwrap(),
+            RawPlatformAndroidActivityKind::NativeActivity,
+        );
+
+        let s = "
+            version = 1
+            [[platform]]
+            id = \"test\"
+            [platform.android]
+            activity-kind = \"game-activity\"
+        ";
+
+        let m = Manifest::parse_str(&".", s).unwrap();
+        assert_eq!(
+            m.raw.platform[0].android().unwrap().activity_kind.unwrap(),
+            RawPlatformAndroidActivityKind::GameActivity,
+        );
+
+        let s = "
+            version = 1
+            [application]
+            id = \"test\"
+            [[platform]]
+            id = \"test\"
+            [platform.android]
+            namespace = \"foo.bar\"
+            min-sdk = 29
+            ndk-level = 21
+            sdk-path = \"/sdk\"
+        ";
+
+        let m = Manifest::parse_str(&".", s).unwrap();
+        let v = m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap();
+        assert_eq!(v.activity_kind, RawPlatformAndroidActivityKind::AppCompat);
+        assert_eq!(v.language, RawPlatformAndroidLanguage::Java);
+        assert_eq!(v.integration, RawPlatformAndroidIntegration::RustAndroidGradle);
+    }
+
+    // Verify parsing of android platform languages
+    //
+    // The language selects between Java and Kotlin emission. Verify Kotlin
+    // can be selected explicitly.
+    #[test]
+    fn manifest_parse_platform_android_language() {
+        let s = "
+            version = 1
+            [[platform]]
+            id = \"test\"
+            [platform.android]
+            language = \"kotlin\"
+        ";
+
+        let m = Manifest::parse_str(&".", s).unwrap();
+        assert_eq!(
+            m.raw.platform[0].android().unwrap().language.unwrap(),
+            RawPlatformAndroidLanguage::Kotlin,
+        );
+    }
+
+    // Verify parsing of android native integrations
+    //
+    // rust-android-gradle is the default. Verify CMake can be selected, and
+    // that the Corrosion path is carried over, unless it contains control
+    // characters.
+    #[test]
+    fn manifest_parse_platform_android_integration() {
+        let m = android("").unwrap();
+        let v = m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap();
+        assert_eq!(v.integration, RawPlatformAndroidIntegration::RustAndroidGradle);
+        assert_eq!(v.corrosion_path, None);
+
+        let m = android("integration = \"cmake\"\ncorrosion-path = \"vendor/corrosion\"").unwrap();
+        let v = m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap();
+        assert_eq!(v.integration, RawPlatformAndroidIntegration::Cmake);
+        assert_eq!(v.corrosion_path.as_deref(), Some("vendor/corrosion"));
+
+        assert!(android("corrosion-path = \"foo\\nbar\"").is_err());
+    }
+
+    // Verify android Gradle version validation
+    //
+    // The Gradle version ends up in the distribution URL of the Gradle
+    // wrapper, so anything but plain version strings must be refused.
+    #[test]
+    fn manifest_parse_platform_android_gradle_version() {
+        let m = android("gradle-version = \"8.14.2\"").unwrap();
+        let v = m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap();
+        assert_eq!(v.gradle_version.as_deref(), Some("8.14.2"));
+
+        assert!(android("gradle-version = \"8.0-rc-1\"").is_ok());
+        assert!(android("gradle-version = \"\"").is_err());
+        assert!(android("gradle-version = \"8.0/../x\"").is_err());
+        assert!(android("gradle-version = \"8.0?x=y\"").is_err());
+    }
+
+    // Verify android version catalogs
+    //
+    // The version catalog is merged over the defaults. Verify overrides are
+    // applied and invalid keys are refused.
+    #[test]
+    fn manifest_parse_platform_android_versions() {
+        let s = "
+            version = 1
+            [application]
+            id = \"test\"
+            [[platform]]
+            id = \"test\"
+            [platform.android]
+            namespace = \"foo.bar\"
+            min-sdk = 29
+            ndk-level = 21
+            sdk-path = \"/sdk\"
+            dependencies = [\"foo:bar:1.0\"]
+            [platform.android.versions]
+            agp = \"8.1.0\"
+            foobar = \"1.0\"
+        ";
+
+        let m = Manifest::parse_str(&".", s).unwrap();
+        let v = m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap();
+        assert_eq!(v.versions["agp"], "8.1.0");
+        assert_eq!(v.versions["foobar"], "1.0");
+        assert_eq!(v.versions["kotlin"], "1.8.22");
+        assert_eq!(v.dependencies, ["foo:bar:1.0"]);
+
+        let s = "
+            version = 1
+            [[platform]]
+            id = \"test\"
+            [platform.android.versions]
+            \"foo bar\" = \"1.0\"
+        ";
+
+        assert!(Manifest::parse_str(&".", s).is_err());
+    }
+
+    // Verify android dependency validation
+    //
+    // Dependency coordinates are put in quotes of Gradle build files, so
+    // quotes, backslashes, and string templates of the Kotlin DSL are
+    // refused.
+    #[test]
+    fn manifest_parse_platform_android_dependencies() {
+        let parse = |dependency: &str| android(&format!("dependencies = [\"{}\"]", dependency));
+
+        assert!(parse("androidx.core:core-ktx:1.12.0").is_ok());
+        assert!(parse("foo:bar:\\\"1.0").is_err());
+        assert!(parse("foo:bar:'1.0'").is_err());
+        assert!(parse("foo:bar:\\\\1.0").is_err());
+        assert!(parse("foo:bar:${version}").is_err());
+    }
+
+    // Verify android Maven repository validation
+    //
+    // Repositories default to the public registries, and are refused if
+    // they cannot be put in quotes of Gradle build files.
+    #[test]
+    fn manifest_parse_platform_android_maven_repositories() {
+        let m = android("").unwrap();
+        let v = m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap();
+        assert_eq!(v.maven_repositories, None);
+
+        let m = android("maven-repositories = [\"file:///srv/maven\", \"https://m.example/r\"]").unwrap();
+        let v = m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap();
+        assert_eq!(
+            v.maven_repositories.unwrap(),
+            ["file:///srv/maven", "https://m.example/r"],
+        );
+
+        assert!(android("maven-repositories = [\"https://m.example/\\\"r\"]").is_err());
+        assert!(android("maven-repositories = [\"https://m.example/${repo}\"]").is_err());
+    }
+
+    // Verify android version resolution
+    //
+    // Version names default to the package version, and version codes are
+    // either explicit or derived via the selected strategy. ABI offsets must
+    // fit into the valid range.
+    #[test]
+    fn manifest_parse_platform_android_version() {
+        let view = |extra: &str| {
+            let m = android(extra).unwrap();
+            m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap()
+        };
+        let mut git = || Some(42);
+
+        let v = view("");
+        assert_eq!(v.version_code, RawPlatformAndroidVersionCode::Explicit(1));
+        assert_eq!(v.version_name, None);
+        let r = v.resolve_version(Some("1.2.3"), &mut git, 0).unwrap();
+        assert_eq!((r.code, r.name.as_str()), (1, "1.2.3"));
+        let r = v.resolve_version(None, &mut git, 0).unwrap();
+        assert_eq!((r.code, r.name.as_str()), (1, "0.1.0"));
+
+        let v = view("version-code = \"semver\"");
+        let r = v.resolve_version(Some("1.2.3-rc.1+abc"), &mut git, 0).unwrap();
+        assert_eq!((r.code, r.name.as_str()), (10203, "1.2.3-rc.1+abc"));
+        assert!(matches!(v.resolve_version(Some("1.2"), &mut git, 0), Err(ErrorVersion::Semver(_))));
+        assert!(matches!(v.resolve_version(Some("1.100.0"), &mut git, 0), Err(ErrorVersion::SemverRange(_))));
+
+        let v = view("version-code = \"git-commit-count\"\nversion-name = \"2.0\"");
+        let r = v.resolve_version(Some("1.2.3"), &mut git, 0).unwrap();
+        assert_eq!((r.code, r.name.as_str()), (42, "2.0"));
+        assert!(matches!(v.resolve_version(None, &mut || None, 0), Err(ErrorVersion::GitCommitCount)));
+
+        let v = view("version-code = \"timestamp\"");
+        let r = v.resolve_version(None, &mut git, 1577836800 + 3600).unwrap();
+        assert_eq!(r.code, 60);
+
+        let v = view("version-code = 2099999999\nversion-code-abi-offsets = { x86 = 1 }");
+        assert!(v.resolve_version(None, &mut git, 0).is_ok());
+        let v = view("version-code = 2099999999\nversion-code-abi-offsets = { x86 = 2 }");
+        assert!(matches!(v.resolve_version(None, &mut git, 0), Err(ErrorVersion::Range(_))));
+    }
+
+    // Verify android ABI splits
+    //
+    // Default ABI offsets are only applied with ABI splits enabled, and are
+    // limited to the configured ABIs. Explicit offsets always take precedence.
+    #[test]
+    fn manifest_parse_platform_android_splits() {
+        let view = |extra: &str| {
+            let m = android(extra).unwrap();
+            m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap()
+        };
+
+        let v = view("");
+        assert!(!v.abi_splits);
+        assert!(!v.universal_apk);
+        assert!(v.version_code_abi_offsets.is_empty());
+
+        let v = view("abi-splits = true\nuniversal-apk = true\nabis = [\"arm64-v8a\", \"x86_64\"]");
+        assert!(v.abi_splits);
+        assert!(v.universal_apk);
+        assert_eq!(v.version_code_abi_offsets.len(), 2);
+        assert_eq!(v.version_code_abi_offsets["arm64-v8a"], 300000000);
+        assert_eq!(v.version_code_abi_offsets["x86_64"], 400000000);
+
+        let v = view("abi-splits = true\nversion-code-abi-offsets = { x86 = 7 }");
+        assert_eq!(v.version_code_abi_offsets.len(), 1);
+        assert_eq!(v.version_code_abi_offsets["x86"], 7);
+    }
+
+    // Verify android signing configuration
+    //
+    // The keystore and key alias are required, while the password variables
+    // have defaults. Variable names must be portable.
+    #[test]
+    fn manifest_parse_platform_android_signing() {
+        let m = android("").unwrap();
+        let v = m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap();
+        assert!(v.signing.is_none());
+
+        let m = android("signing = { keystore = \"release.jks\", key-alias = \"upload\" }").unwrap();
+        let v = m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap();
+        let v = v.signing.unwrap();
+        assert_eq!(v.keystore, "release.jks");
+        assert_eq!(v.key_alias, "upload");
+        assert_eq!(v.store_password_env, "OSIRIS_ANDROID_STORE_PASSWORD");
+        assert_eq!(v.key_password_env, "OSIRIS_ANDROID_STORE_PASSWORD");
+
+        let m = android("signing = { keystore = \"release.jks\" }").unwrap();
+        assert!(matches!(
+            m.raw.platform[0].android().unwrap().view(&m.raw),
+            Err(ErrorView::MissingKey(".signing.key-alias")),
+        ));
+
+        assert!(android("signing = { store-password-env = \"FOO-BAR\" }").is_err());
+        assert!(android("signing = { key-password-env = \"1FOO\" }").is_err());
+    }
+
+    // Verify android minification options
+    //
+    // Resource shrinking follows minification, but cannot be enabled on its
+    // own. JNI classes must be valid class names or patterns.
+    #[test]
+    fn manifest_parse_platform_android_minify() {
+        let m = android("").unwrap();
+        let v = m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap();
+        assert!(!v.minify && !v.shrink_resources);
+        assert!(v.jni_classes.is_empty());
+
+        let m = android("minify = true\njni-classes = [\"foo.Bar$Baz\", \"foo.cb.**\"]").unwrap();
+        let v = m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap();
+        assert!(v.minify && v.shrink_resources);
+        assert_eq!(v.jni_classes, ["foo.Bar$Baz", "foo.cb.**"]);
+
+        let m = android("minify = true\nshrink-resources = false").unwrap();
+        let v = m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap();
+        assert!(v.minify && !v.shrink_resources);
+
+        assert!(android("shrink-resources = true").is_err());
+        assert!(android("minify = true\njni-classes = [\"foo { *; }\"]").is_err());
+    }
+
+    // Verify android native library options
+    //
+    // Extra libraries are listed per ABI and must be named `lib<name>.so`,
+    // so they can be loaded by name.
+    #[test]
+    fn manifest_parse_platform_android_native_libraries() {
+        let m = android("").unwrap();
+        let v = m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap();
+        assert!(v.native_libraries.is_empty());
+        assert!(!v.cxx_shared);
+
+        let m = android("cxx-shared = true\nnative-libraries = { arm64-v8a = [\"vendor/libfoo.so\"] }").unwrap();
+        let v = m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap();
+        assert!(v.cxx_shared);
+        assert_eq!(v.native_libraries["arm64-v8a"], ["vendor/libfoo.so"]);
+
+        assert!(android("native-libraries = { arm64-v8a = [\"vendor/foo.so\"] }").is_err());
+        assert!(android("native-libraries = { arm64-v8a = [\"vendor/lib.so\"] }").is_err());
+        assert!(android("native-libraries = { \"arm/64\" = [\"libfoo.so\"] }").is_err());
+    }
+
+    // Verify Android artifact parsing
+    //
+    // Applications are built by default, while libraries default to a
+    // facade and the namespace as Maven group.
+    #[test]
+    fn manifest_parse_platform_android_artifact() {
+        let m = android("").unwrap();
+        let v = m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap();
+        assert_eq!(v.artifact, RawPlatformAndroidArtifact::Application);
+
+        let m = android("artifact = \"library\"").unwrap();
+        let v = m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap();
+        assert_eq!(v.artifact, RawPlatformAndroidArtifact::Library);
+        assert!(v.library_facade);
+        assert_eq!(v.maven_group, "foo.bar");
+
+        let m = android("artifact = \"library\"\nlibrary-facade = false\nmaven-group = \"org.example\"").unwrap();
+        let v = m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap();
+        assert!(!v.library_facade);
+        assert_eq!(v.maven_group, "org.example");
+
+        assert!(android("maven-group = \"org/example\"").is_err());
+        assert!(android("maven-group = \"org..example\"").is_err());
+    }
+
+    // Verify Android bridge parsing
+    //
+    // Bridge classes are qualified with the namespace, unless qualified
+    // already. Reserved words, methods of `java.lang.Object`, and `void`
+    // arguments are refused.
+    #[test]
+    fn manifest_parse_platform_android_bridge() {
+        let parse = |extra: &str| android(&format!("[platform.android.bridge]\n{}", extra));
+
+        let m = parse("methods = { add = { args = [\"int\", \"int\"], ret = \"int\" }, run = {} }").unwrap();
+        let v = m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap();
+        let bridge = v.bridge.unwrap();
+        assert_eq!(bridge.class, "foo.bar.NativeBridge");
+        assert_eq!(bridge.rust_file, "app/src/osiris_bridge.rs");
+        assert_eq!(bridge.rust_module, "crate::bridge");
+        assert_eq!(
+            bridge.methods["add"],
+            (vec![RawPlatformAndroidBridgeType::Int; 2], RawPlatformAndroidBridgeType::Int),
+        );
+        assert_eq!(bridge.methods["run"], (vec![], RawPlatformAndroidBridgeType::Void));
+
+        let m = parse("class = \"Bridge\"").unwrap();
+        let v = m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap();
+        assert_eq!(v.bridge.unwrap().class, "foo.bar.Bridge");
+
+        assert!(parse("class = \"org.example.MainActivity\"").is_ok());
+        assert!(parse("class = \"foo.bar.MainActivity\"").is_err());
+        assert!(parse("class = \"NativeLibrary\"").is_err());
+        assert!(parse("class = \"foo.1bar\"").is_err());
+        assert!(parse("rust-module = \"crate::foo-bar\"").is_err());
+        assert!(parse("methods = { match = {} }").is_err());
+        assert!(parse("methods = { notifyAll = {} }").is_err());
+        assert!(parse("methods = { run = { args = [\"void\"] } }").is_err());
+    }
 }
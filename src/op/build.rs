@@ -14,6 +14,8 @@ pub enum Error {
     PlatformDirectory(std::ffi::OsString),
     /// Cannot create the specified build artifact directory.
     DirectoryCreation(std::ffi::OsString),
+    /// Reading the file at the specified path failed with the given error.
+    FileRead(std::ffi::OsString, std::io::Error),
     /// Updating the file at the specified path failed with the given error.
     FileUpdate(std::ffi::OsString, std::io::Error),
     /// Removing the file at the specified path failed with the given error.
@@ -22,6 +24,152 @@ pub enum Error {
     Exec(String, std::io::Error),
     /// Platform build tools failed.
     Build,
+    /// Gradle could not be invoked. Contains the resolved Gradle executable,
+    /// the resolved Java home (if any), and the error.
+    GradleExec(std::ffi::OsString, Option<std::ffi::OsString>, std::io::Error),
+    /// Gradle failed. Contains the resolved Gradle executable and the resolved
+    /// Java home (if any).
+    Gradle(std::ffi::OsString, Option<std::ffi::OsString>),
+    /// Resolving the version of the platform integration failed.
+    Version(crate::manifest::ErrorVersion),
+    /// The specified environment variable holding a signing password is not
+    /// set.
+    SigningPassword(String),
+    /// No prebuilt Rust library was found for the specified ABI in the
+    /// specified prebuilt directory.
+    Prebuilt(String, std::ffi::OsString),
+    /// No Android NDK was found at the specified path, but it is required to
+    /// package the shared C++ runtime.
+    Ndk(std::ffi::OsString),
+    /// Linting the packaged native libraries yielded the given findings,
+    /// and the manifest denies them.
+    Lint(Vec<ReportFinding>),
+    /// The Rust bridge file at the specified path was not generated by the
+    /// emerge operation, so it is not overwritten.
+    BridgeFile(std::ffi::OsString),
+    /// The icon image at the specified path cannot be loaded or rendered.
+    Icon(std::ffi::OsString),
+    /// Loading the localization catalog failed.
+    Catalog(crate::manifest::ErrorCatalog),
+}
+
+/// Build Artifact Kinds
+///
+/// Sub-type of `ReportArtifact` describing the kind of an artifact.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportArtifactKind {
+    /// Android application package.
+    Apk,
+    /// R8/ProGuard mapping of obfuscated symbols to their original names.
+    Mapping,
+    /// Archive of unstripped native libraries for symbolication.
+    DebugSymbols,
+    /// Android library archive, published to the local Maven repository.
+    Aar,
+    /// Maven POM describing the published Android library archives.
+    Pom,
+}
+
+/// Build Artifact
+///
+/// This describes a single artifact produced by a build.
+pub struct ReportArtifact {
+    /// Kind of the artifact.
+    pub kind: ReportArtifactKind,
+    /// Path to the artifact.
+    pub path: std::path::PathBuf,
+    /// Build variant that produced the artifact (e.g., `debug`). This is
+    /// empty for artifacts that are not specific to a variant.
+    pub variant: String,
+    /// ABI of the artifact, or `None` if it is not specific to an ABI.
+    pub abi: Option<String>,
+    /// Version code of the artifact, if known.
+    pub version_code: Option<u32>,
+}
+
+/// Build Finding Kinds
+///
+/// Sub-type of `ReportFinding` describing what is wrong with a native
+/// library.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReportFindingKind {
+    /// The library is not a valid ELF file.
+    Format,
+    /// The ELF machine of the library does not match its ABI.
+    Machine(u16),
+    /// The library requires the specified library, which is neither provided
+    /// by the platform nor packaged with the application.
+    Needed(String),
+    /// The library requires the specified platform library, which is only
+    /// available starting with the given API level.
+    NeededApiLevel(String, u32),
+    /// The library requires text relocations, which are refused by the
+    /// dynamic linker since API level 23.
+    TextRelocations,
+    /// The library lacks the specified entry-point symbol required by the
+    /// activity kind.
+    EntryPoint(&'static str),
+    /// The library has a `PT_LOAD` segment with the given alignment, which
+    /// cannot be loaded on devices with 16 KB pages.
+    PageAlignment(u64),
+    /// The library was built for the given API level, which exceeds
+    /// `min-sdk`.
+    ApiLevel(u32),
+}
+
+/// Build Finding
+///
+/// This describes a problem of a native library packaged by a build, found
+/// by linting the library after the build.
+#[derive(Clone, Debug)]
+pub struct ReportFinding {
+    /// What is wrong with the library.
+    pub kind: ReportFindingKind,
+    /// Path to the library.
+    pub path: std::path::PathBuf,
+    /// Build variant that packaged the library (e.g., `debug`).
+    pub variant: String,
+    /// ABI of the library.
+    pub abi: String,
+}
+
+/// Build Report
+///
+/// This describes the result of a successful build. Fields are only set if
+/// they apply to the built platform.
+#[derive(Default)]
+pub struct Report {
+    /// Resolved version of an Android build.
+    pub android_version: Option<crate::manifest::ViewPlatformAndroidVersion>,
+    /// All artifacts produced by the build.
+    pub artifacts: Vec<ReportArtifact>,
+    /// All findings of linting the packaged native libraries, if they are
+    /// not denied by the manifest.
+    pub findings: Vec<ReportFinding>,
+}
+
+/// Build Options
+///
+/// This contains all options of the build operation that are not part of
+/// the manifest. Defaults are used for all options left unset.
+#[derive(Clone, Default)]
+pub struct Options {
+    /// Path to the Gradle executable. Overrides the manifest and any Gradle
+    /// wrapper of the platform integration.
+    pub gradle: Option<std::path::PathBuf>,
+    /// Path to the Java installation used by Gradle. Overrides the manifest.
+    pub java_home: Option<std::path::PathBuf>,
+    /// Whether to build without network access, relying on local Maven
+    /// repositories and pre-populated caches.
+    pub offline: bool,
+    /// Path to a directory with prebuilt Rust libraries, laid out per ABI
+    /// (`<abi>/`) or per Rust target triple (`<triple>/` or
+    /// `<triple>/release/`). If set, the libraries are packaged as they are,
+    /// without invoking Cargo.
+    pub prebuilt: Option<std::path::PathBuf>,
+    /// Build variant to build (e.g., `debug`). If unset, all variants are
+    /// built, and linted and tested as well.
+    pub variant: Option<String>,
 }
 
 impl Error {
@@ -30,6 +178,23 @@ impl Error {
             crate::manifest::ErrorView::MissingKey(v) => Self::ManifestKey(v),
         }
     }
+
+    fn from_emerge_error(error: crate::op::emerge::Error) -> Self {
+        match error {
+            crate::op::emerge::Error::Already => {
+                unreachable!("Emerging with updates allowed must not yield");
+            },
+            crate::op::emerge::Error::ManifestKey(key) => Self::ManifestKey(key),
+            crate::op::emerge::Error::PlatformDirectory(dir) => Self::PlatformDirectory(dir),
+            crate::op::emerge::Error::DirectoryCreation(dir) => Self::DirectoryCreation(dir),
+            crate::op::emerge::Error::FileUpdate(file, error) => Self::FileUpdate(file, error),
+            crate::op::emerge::Error::FileRemoval(file, error) => Self::FileRemoval(file, error),
+            crate::op::emerge::Error::Version(error) => Self::Version(error),
+            crate::op::emerge::Error::BridgeFile(file) => Self::BridgeFile(file),
+            crate::op::emerge::Error::Icon(file) => Self::Icon(file),
+            crate::op::emerge::Error::Catalog(error) => Self::Catalog(error),
+        }
+    }
 }
 
 // Add Gradle `KEY=VALUE` to command-line.
@@ -66,20 +231,848 @@ fn cmd_gradle_system_prop(
     cmd_gradle_key_value(cmd, key, value)
 }
 
+// Collect Android APKs
+//
+// Gradle writes the APKs of each variant to `<variant>/` in the given
+// directory, alongside `output-metadata.json`, which describes the ABI
+// filters and version code of each APK. If the metadata is not available,
+// all APKs of the directory are reported without ABI nor version code.
+fn collect_android_apks(path: &std::path::Path) -> Vec<ReportArtifact> {
+    let mut artifacts = Vec::new();
+
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return artifacts;
+    };
+
+    for entry in entries.filter_map(|v| v.ok()) {
+        let dir = entry.path();
+        if !dir.is_dir() {
+            continue;
+        }
+        let variant = entry.file_name().to_string_lossy().into_owned();
+
+        let metadata = std::fs::read_to_string(dir.join("output-metadata.json"))
+            .ok()
+            .and_then(|v| serde_json::from_str::<serde_json::Value>(&v).ok());
+        let elements = metadata.as_ref()
+            .and_then(|v| v.get("elements"))
+            .and_then(|v| v.as_array());
+
+        if let Some(elements) = elements {
+            for element in elements {
+                let Some(file) = element.get("outputFile").and_then(|v| v.as_str()) else {
+                    continue;
+                };
+                let abi = element.get("filters")
+                    .and_then(|v| v.as_array())
+                    .and_then(|v| {
+                        v.iter().find(
+                            |f| f.get("filterType").and_then(|v| v.as_str()) == Some("ABI"),
+                        )
+                    })
+                    .and_then(|v| v.get("value"))
+                    .and_then(|v| v.as_str())
+                    .map(|v| v.to_string());
+                let version_code = element.get("versionCode")
+                    .and_then(|v| v.as_u64())
+                    .and_then(|v| u32::try_from(v).ok());
+
+                artifacts.push(ReportArtifact {
+                    kind: ReportArtifactKind::Apk,
+                    path: dir.join(file),
+                    variant: variant.clone(),
+                    abi,
+                    version_code,
+                });
+            }
+        } else if let Ok(files) = std::fs::read_dir(&dir) {
+            for file in files.filter_map(|v| v.ok()).map(|v| v.path()) {
+                if file.extension().map(|v| v == "apk").unwrap_or(false) {
+                    artifacts.push(ReportArtifact {
+                        kind: ReportArtifactKind::Apk,
+                        path: file,
+                        variant: variant.clone(),
+                        abi: None,
+                        version_code: None,
+                    });
+                }
+            }
+        }
+    }
+
+    artifacts.sort_by(|a, b| (&a.variant, &a.path).cmp(&(&b.variant, &b.path)));
+    artifacts
+}
+
+// Collect Android R8 mappings
+//
+// With minification enabled, R8 writes the mapping of obfuscated symbols of
+// each variant to `<variant>/mapping.txt` in the given directory. It is
+// needed to deobfuscate stack traces of that exact build.
+fn collect_android_mappings(path: &std::path::Path) -> Vec<ReportArtifact> {
+    let mut artifacts = Vec::new();
+
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return artifacts;
+    };
+
+    for entry in entries.filter_map(|v| v.ok()) {
+        let file = entry.path().join("mapping.txt");
+        if file.is_file() {
+            artifacts.push(ReportArtifact {
+                kind: ReportArtifactKind::Mapping,
+                path: file,
+                variant: entry.file_name().to_string_lossy().into_owned(),
+                abi: None,
+                version_code: None,
+            });
+        }
+    }
+
+    artifacts.sort_by(|a, b| a.variant.cmp(&b.variant));
+    artifacts
+}
+
+// Collect Android library archives
+//
+// Gradle writes the AARs of all variants to the given directory, named
+// `<name>-<variant>.aar` after the root project. Variants with product
+// flavors are joined by dashes (e.g., `free-debug`).
+fn collect_android_aars(path: &std::path::Path, name: &str) -> Vec<ReportArtifact> {
+    let mut artifacts = Vec::new();
+
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return artifacts;
+    };
+
+    let prefix = format!("{}-", name);
+
+    for file in entries.filter_map(|v| v.ok()).map(|v| v.path()) {
+        if file.extension().map(|v| v != "aar").unwrap_or(true) {
+            continue;
+        }
+        let Some(variant) = file.file_stem()
+            .and_then(|v| v.to_str())
+            .and_then(|v| v.strip_prefix(prefix.as_str()))
+            .map(|v| v.to_string())
+        else {
+            continue;
+        };
+
+        artifacts.push(ReportArtifact {
+            kind: ReportArtifactKind::Aar,
+            path: file,
+            variant,
+            abi: None,
+            version_code: None,
+        });
+    }
+
+    artifacts.sort_by(|a, b| a.variant.cmp(&b.variant));
+    artifacts
+}
+
+// Create Android library POM
+//
+// Create the Maven POM of an Android library with the given coordinates.
+// The dependencies of the manifest are given as `group:artifact:version`
+// and become runtime dependencies of the library, since they are not part
+// of its API. Dependencies in any other notation are omitted.
+fn android_pom(
+    group: &str,
+    artifact: &str,
+    version: &str,
+    name: &str,
+    dependencies: &[String],
+) -> String {
+    let escape = |v: &str| v.replace('&', "&amp;").replace('<', "&lt;");
+
+    let mut deps = String::new();
+    for v in dependencies.iter() {
+        let mut parts = v.split(':');
+        let (Some(g), Some(a), Some(v), None) = (
+            parts.next(), parts.next(), parts.next(), parts.next(),
+        ) else {
+            continue;
+        };
+
+        deps.push_str(&format!(
+            concat!(
+                "    <dependency>\n",
+                "      <groupId>{0}</groupId>\n",
+                "      <artifactId>{1}</artifactId>\n",
+                "      <version>{2}</version>\n",
+                "      <scope>runtime</scope>\n",
+                "    </dependency>\n",
+            ),
+            escape(g),
+            escape(a),
+            escape(v),
+        ));
+    }
+    if !deps.is_empty() {
+        deps = format!("  <dependencies>\n{}  </dependencies>\n", deps);
+    }
+
+    format!(
+        concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<!-- Generated by osiris-platform -->\n",
+            "<project xmlns=\"http://maven.apache.org/POM/4.0.0\"",
+            " xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\"",
+            " xsi:schemaLocation=\"http://maven.apache.org/POM/4.0.0 https://maven.apache.org/xsd/maven-4.0.0.xsd\">\n",
+            "  <modelVersion>4.0.0</modelVersion>\n",
+            "  <groupId>{0}</groupId>\n",
+            "  <artifactId>{1}</artifactId>\n",
+            "  <version>{2}</version>\n",
+            "  <packaging>aar</packaging>\n",
+            "  <name>{3}</name>\n",
+            "{4}",
+            "</project>\n",
+        ),
+        escape(group),
+        escape(artifact),
+        escape(version),
+        escape(name),
+        deps,
+    )
+}
+
+// Publish Android library archives
+//
+// Copy the given AARs into a local Maven repository at the given path, as
+// `<group>/<artifact>/<version>/<artifact>-<version>.aar`, and write the
+// POM alongside. The release variant is the main artifact, all other
+// variants are published with their name as classifier. The reported
+// artifacts refer to the copies in the repository.
+fn publish_android_aars(
+    path_maven: &std::path::Path,
+    aars: Vec<ReportArtifact>,
+    view_application: &crate::manifest::ViewApplication,
+    view_android: &crate::manifest::ViewPlatformAndroid,
+    version: &crate::manifest::ViewPlatformAndroidVersion,
+) -> Result<Vec<ReportArtifact>, Error> {
+    let mut artifacts = Vec::new();
+
+    if aars.is_empty() {
+        return Ok(artifacts);
+    }
+
+    let artifact = view_application.package.as_str();
+    let mut dir = path_maven.to_path_buf();
+    dir.extend(view_android.maven_group.split('.'));
+    dir.push(artifact);
+    dir.push(&version.name);
+    std::fs::create_dir_all(&dir).map_err(
+        |_| Error::DirectoryCreation(dir.as_os_str().to_os_string()),
+    )?;
+
+    for aar in aars.into_iter() {
+        let file = match aar.variant.as_str() {
+            "release" => format!("{}-{}.aar", artifact, version.name),
+            v => format!("{}-{}-{}.aar", artifact, version.name, v),
+        };
+        let path = dir.join(file);
+        std::fs::copy(&aar.path, &path).map_err(
+            |v| Error::FileUpdate(path.as_os_str().to_os_string(), v),
+        )?;
+        artifacts.push(ReportArtifact { path, ..aar });
+    }
+
+    let path = dir.join(format!("{}-{}.pom", artifact, version.name));
+    let content = android_pom(
+        &view_android.maven_group,
+        artifact,
+        &version.name,
+        &view_application.name,
+        &view_android.dependencies,
+    );
+    std::fs::write(&path, content).map_err(
+        |v| Error::FileUpdate(path.as_os_str().to_os_string(), v),
+    )?;
+    artifacts.push(ReportArtifact {
+        kind: ReportArtifactKind::Pom,
+        path,
+        variant: String::new(),
+        abi: None,
+        version_code: None,
+    });
+
+    Ok(artifacts)
+}
+
+// Find native libraries
+//
+// Recursively search the given directory for native libraries in the layout
+// `lib/<abi>/<name>.so`, and return them as `(abi, path)` pairs.
+fn find_native_libs(
+    path: &std::path::Path,
+    libs: &mut Vec<(String, std::path::PathBuf)>,
+) {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return;
+    };
+
+    for entry in entries.filter_map(|v| v.ok()) {
+        let path = entry.path();
+        if path.is_dir() {
+            find_native_libs(&path, libs);
+        } else if path.extension().map(|v| v == "so").unwrap_or(false) {
+            let abi = path.parent().and_then(|v| v.file_name());
+            let lib = path.parent().and_then(|v| v.parent()).and_then(|v| v.file_name());
+            if let (Some(abi), Some(lib)) = (abi, lib) {
+                if lib == "lib" {
+                    libs.push((abi.to_string_lossy().into_owned(), path));
+                }
+            }
+        }
+    }
+}
+
+// Android platform libraries
+//
+// These are the stable native libraries of the NDK, which every device
+// provides, together with the API level they were introduced at. Libraries
+// available since before API level 21 (the minimum of current NDKs) are
+// listed with level 1.
+const ANDROID_PLATFORM_LIBRARIES: &[(&str, u32)] = &[
+    ("libEGL.so", 1),
+    ("libGLESv1_CM.so", 1),
+    ("libGLESv2.so", 1),
+    ("libGLESv3.so", 1),
+    ("libOpenMAXAL.so", 1),
+    ("libOpenSLES.so", 1),
+    ("libaaudio.so", 26),
+    ("libamidi.so", 29),
+    ("libandroid.so", 1),
+    ("libbinder_ndk.so", 29),
+    ("libc.so", 1),
+    ("libcamera2ndk.so", 24),
+    ("libdl.so", 1),
+    ("libicu.so", 31),
+    ("libjnigraphics.so", 1),
+    ("liblog.so", 1),
+    ("libm.so", 1),
+    ("libmediandk.so", 21),
+    ("libnativewindow.so", 26),
+    ("libneuralnetworks.so", 27),
+    ("libstdc++.so", 1),
+    ("libsync.so", 26),
+    ("libvulkan.so", 24),
+    ("libz.so", 1),
+];
+
+// Lint an Android native library
+//
+// Check the given library for problems that make the dynamic linker of the
+// device refuse it, or that break the application at runtime. `packaged`
+// lists the file names of all libraries packaged for the same ABI, which
+// satisfy `DT_NEEDED` entries. The entry-point symbol (see
+// `android_entry_point()`) is only required from the library of the
+// application itself.
+fn lint_android_library(
+    library: &crate::elf::Library,
+    abi: &str,
+    packaged: &[String],
+    entry_point: Option<&'static str>,
+    min_sdk: u32,
+) -> Vec<ReportFindingKind> {
+    use addr2line::object::elf;
+
+    let mut findings = Vec::new();
+
+    let (machine, page_size_16k) = match abi {
+        "armeabi-v7a" => (Some(elf::EM_ARM), false),
+        "arm64-v8a" => (Some(elf::EM_AARCH64), true),
+        "x86" => (Some(elf::EM_386), false),
+        "x86_64" => (Some(elf::EM_X86_64), true),
+        _ => (None, false),
+    };
+    if machine.map(|v| v != library.machine).unwrap_or(false) {
+        findings.push(ReportFindingKind::Machine(library.machine));
+    }
+
+    for v in library.needed.iter() {
+        if let Some((_, level)) = ANDROID_PLATFORM_LIBRARIES.iter().find(|(k, _)| k == v) {
+            if *level > min_sdk {
+                findings.push(ReportFindingKind::NeededApiLevel(v.clone(), *level));
+            }
+        } else if !packaged.iter().any(|k| k == v) {
+            findings.push(ReportFindingKind::Needed(v.clone()));
+        }
+    }
+
+    if library.text_relocations {
+        findings.push(ReportFindingKind::TextRelocations);
+    }
+
+    // Libraries merely loaded via `System.loadLibrary()` must either
+    // register natives in `JNI_OnLoad`, or export JNI methods by name.
+    if let Some(symbol) = entry_point {
+        let jni = symbol == "JNI_OnLoad"
+            && library.exports.iter().any(|v| v.starts_with("Java_"));
+        if !jni && !library.exports.contains(symbol) {
+            findings.push(ReportFindingKind::EntryPoint(symbol));
+        }
+    }
+
+    // Devices with 16 KB pages only exist for 64-bit ABIs.
+    if page_size_16k {
+        if let Some(v) = library.load_alignments.iter().find(|v| **v < 0x4000) {
+            findings.push(ReportFindingKind::PageAlignment(*v));
+        }
+    }
+
+    if let Some(v) = library.api_level {
+        if v > min_sdk {
+            findings.push(ReportFindingKind::ApiLevel(v));
+        }
+    }
+
+    findings
+}
+
+// Android entry-point symbol
+//
+// Return the symbol the Rust library must export to be entered. The
+// `AppCompat` stub and library facades merely load the library via JNI,
+// while `NativeActivity` and `GameActivity` call their own entry-points.
+fn android_entry_point(view_android: &crate::manifest::ViewPlatformAndroid) -> &'static str {
+    match (view_android.artifact, view_android.activity_kind) {
+        (crate::manifest::RawPlatformAndroidArtifact::Library, _) => "JNI_OnLoad",
+        (_, crate::manifest::RawPlatformAndroidActivityKind::AppCompat) => "JNI_OnLoad",
+        (_, crate::manifest::RawPlatformAndroidActivityKind::NativeActivity) => "ANativeActivity_onCreate",
+        (_, crate::manifest::RawPlatformAndroidActivityKind::GameActivity) => "GameActivity_onCreate",
+    }
+}
+
+// Lint Android native libraries
+//
+// Lint all native libraries merged by the Android Gradle Plugin for each
+// variant, laid out as `lib/<abi>/<name>.so` below the given directory.
+fn lint_android_libraries(
+    path_intermediates: &std::path::Path,
+    view_application: &crate::manifest::ViewApplication,
+    view_android: &crate::manifest::ViewPlatformAndroid,
+) -> Result<Vec<ReportFinding>, Error> {
+    let mut findings = Vec::new();
+
+    let Ok(entries) = std::fs::read_dir(path_intermediates) else {
+        return Ok(findings);
+    };
+
+    let main = format!("lib{}.so", view_application.package_symbol);
+
+    for entry in entries.filter_map(|v| v.ok()) {
+        let variant = entry.file_name().to_string_lossy().into_owned();
+        let mut libs = Vec::new();
+        find_native_libs(&entry.path(), &mut libs);
+        libs.sort();
+
+        for (abi, lib) in libs.iter() {
+            let packaged: Vec<String> = libs.iter()
+                .filter(|(v, _)| v == abi)
+                .filter_map(|(_, v)| v.file_name())
+                .map(|v| v.to_string_lossy().into_owned())
+                .collect();
+            let entry_point = (lib.file_name() == Some(main.as_ref()))
+                .then(|| android_entry_point(view_android));
+
+            let data = std::fs::read(lib).map_err(
+                |v| Error::FileRead(lib.as_os_str().to_os_string(), v),
+            )?;
+            let kinds = match crate::elf::Library::parse(&data) {
+                Some(v) => lint_android_library(&v, abi, &packaged, entry_point, view_android.min_sdk),
+                None => vec![ReportFindingKind::Format],
+            };
+
+            findings.extend(kinds.into_iter().map(|v| ReportFinding {
+                kind: v,
+                path: lib.clone(),
+                variant: variant.clone(),
+                abi: abi.clone(),
+            }));
+        }
+    }
+
+    findings.sort_by(|a, b| (&a.variant, &a.path).cmp(&(&b.variant, &b.path)));
+    Ok(findings)
+}
+
+// Archive Android native debug symbols
+//
+// The Android Gradle Plugin strips native libraries before packaging, but
+// retains the unstripped libraries of each variant in its intermediates.
+// Copy them to `<variant>/<abi>/` in the symbol directory, where
+// `symbolicate()` looks them up, and pack them into
+// `<variant>/native-debug-symbols.zip` with the `<abi>/<name>.so` layout
+// expected by the Play Console.
+//
+// Only libraries with DWARF debug information are archived, and no artifact
+// is reported for variants without any, since their symbols would be of no
+// use.
+fn archive_android_symbols(
+    path_intermediates: &std::path::Path,
+    path_symbols: &std::path::Path,
+) -> Result<Vec<ReportArtifact>, Error> {
+    let mut artifacts = Vec::new();
+
+    let Ok(entries) = std::fs::read_dir(path_intermediates) else {
+        return Ok(artifacts);
+    };
+
+    for entry in entries.filter_map(|v| v.ok()) {
+        let variant = entry.file_name().to_string_lossy().into_owned();
+        let mut libs = Vec::new();
+        find_native_libs(&entry.path(), &mut libs);
+        if libs.is_empty() {
+            continue;
+        }
+        libs.sort();
+
+        // Replace any previous archive of this variant, so no stale
+        // libraries are left behind.
+        let path_variant = path_symbols.join(&variant);
+        if let Err(v) = std::fs::remove_dir_all(&path_variant) {
+            if v.kind() != std::io::ErrorKind::NotFound {
+                return Err(Error::FileRemoval(path_variant.into_os_string(), v));
+            }
+        }
+
+        let mut libs_debug = Vec::new();
+        for (abi, lib) in libs.into_iter() {
+            let data = std::fs::read(&lib).map_err(
+                |v| Error::FileRead(lib.as_os_str().to_os_string(), v),
+            )?;
+            if crate::elf::Library::parse(&data).map(|v| v.debug_info).unwrap_or(false) {
+                libs_debug.push((abi, lib, data));
+            }
+        }
+        if libs_debug.is_empty() {
+            continue;
+        }
+
+        let path_zip = path_variant.join("native-debug-symbols.zip");
+        let mut zip_data = std::io::Cursor::new(Vec::new());
+        let mut zip = zip::ZipWriter::new(&mut zip_data);
+        let zip_options = zip::write::FileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+
+        for (abi, lib, data) in libs_debug.iter() {
+            let name = lib.file_name().unwrap_or_default();
+            let path_abi = path_variant.join(abi);
+            std::fs::create_dir_all(&path_abi).map_err(
+                |_| Error::DirectoryCreation(path_abi.as_os_str().to_os_string()),
+            )?;
+
+            let path_lib = path_abi.join(name);
+            std::fs::write(&path_lib, data).map_err(
+                |v| Error::FileUpdate(path_lib.as_os_str().to_os_string(), v),
+            )?;
+
+            zip.start_file(format!("{}/{}", abi, name.to_string_lossy()), zip_options)
+                .map_err(|v| Error::FileUpdate(path_zip.as_os_str().to_os_string(), v.into()))?;
+            std::io::Write::write_all(&mut zip, data)
+                .map_err(|v| Error::FileUpdate(path_zip.as_os_str().to_os_string(), v))?;
+        }
+
+        zip.finish().map_err(
+            |v| Error::FileUpdate(path_zip.as_os_str().to_os_string(), v.into()),
+        )?;
+        drop(zip);
+        std::fs::write(&path_zip, zip_data.into_inner()).map_err(
+            |v| Error::FileUpdate(path_zip.as_os_str().to_os_string(), v),
+        )?;
+
+        artifacts.push(ReportArtifact {
+            kind: ReportArtifactKind::DebugSymbols,
+            path: path_zip,
+            variant,
+            abi: None,
+            version_code: None,
+        });
+    }
+
+    artifacts.sort_by(|a, b| a.variant.cmp(&b.variant));
+    Ok(artifacts)
+}
+
+// Resolve Gradle executable and Java installation
+//
+// The Gradle executable is taken from the options, or the manifest. If
+// neither specifies it, the Gradle wrapper of the platform integration is
+// preferred, if present. Otherwise, `gradle` is looked up in `$PATH`. The
+// Java installation is taken from the options, or the manifest, and left
+// to Gradle if neither specifies it.
+fn gradle_resolve(
+    options: &Options,
+    manifest: &crate::manifest::Manifest,
+    view_android: &crate::manifest::ViewPlatformAndroid,
+    wrapper: Option<std::path::PathBuf>,
+) -> (std::path::PathBuf, Option<std::path::PathBuf>) {
+    let gradle = if let Some(v) = options.gradle.as_ref() {
+        v.clone()
+    } else if let Some(v) = view_android.gradle_path.as_ref() {
+        manifest.absolute_path(v)
+    } else if let Some(v) = wrapper {
+        v
+    } else {
+        "gradle".into()
+    };
+
+    let java_home = if let Some(v) = options.java_home.as_ref() {
+        Some(v.clone())
+    } else {
+        view_android.java_home.as_ref().map(|v| manifest.absolute_path(v))
+    };
+
+    (gradle, java_home)
+}
+
+// Create Gradle command
+//
+// Prepare the invocation of Gradle with the given Gradle executable, Java
+// installation, and Android SDK. A single variant is built via its
+// `assemble<Variant>` task, otherwise the `build` task builds, lints, and
+// tests all variants. All further arguments depend on the platform
+// integration and are added by the caller.
+fn gradle_command(
+    gradle: &std::path::Path,
+    java_home: Option<&std::path::Path>,
+    sdk: &std::path::Path,
+    offline: bool,
+    variant: Option<&str>,
+) -> std::process::Command {
+    let mut cmd = std::process::Command::new(gradle);
+
+    // Gradle uses `JAVA_HOME` to pick the Java installation, if set. Only
+    // override it if requested, otherwise the environment is inherited.
+    if let Some(v) = java_home {
+        cmd.env("JAVA_HOME", v);
+    }
+
+    // Set the SDK path via `ANDROID_HOME`. This is required by the Android SDK
+    // Gradle build. Alternatively, this can be set via `local.properties`, but
+    // Gradle has no official support for this, so we avoid it.
+    cmd.env("ANDROID_HOME", sdk);
+
+    if let Some(v) = variant {
+        let mut chars = v.chars();
+        let first = chars.next().map(|v| v.to_ascii_uppercase());
+        cmd.arg(format!("assemble{}{}", first.unwrap_or_default(), chars.as_str()));
+    } else {
+        cmd.arg("build");
+    }
+
+    cmd.arg("--no-scan");
+    cmd.arg("--no-watch-fs");
+    cmd.arg("--parallel");
+    cmd.arg("--quiet");
+
+    // In offline mode, Gradle must resolve everything from its caches and
+    // local repositories. Cargo is invoked by Gradle, so tell it as well.
+    if offline {
+        cmd.arg("--offline");
+        cmd.env("CARGO_NET_OFFLINE", "true");
+    }
+
+    cmd
+}
+
+// Locate the Android NDK
+//
+// Use the NDK path of the manifest, if given. Otherwise, use the NDK of the
+// environment (`ANDROID_NDK_ROOT` or `ANDROID_NDK_HOME`), or the highest
+// version installed side-by-side in the SDK at `<sdk>/ndk/<version>`.
+fn android_ndk(
+    manifest: &crate::manifest::Manifest,
+    view_android: &crate::manifest::ViewPlatformAndroid,
+) -> std::path::PathBuf {
+    if let Some(v) = view_android.ndk_path.as_ref() {
+        return manifest.absolute_path(v);
+    }
+
+    for v in ["ANDROID_NDK_ROOT", "ANDROID_NDK_HOME"] {
+        if let Some(v) = std::env::var_os(v).filter(|v| !v.is_empty()) {
+            return v.into();
+        }
+    }
+
+    let path = manifest.absolute_path(&view_android.sdk_path).join("ndk");
+    let version = |v: &std::path::Path| -> Vec<u64> {
+        v.file_name()
+            .map(|v| v.to_string_lossy().split('.').map(|v| v.parse().unwrap_or(0)).collect())
+            .unwrap_or_default()
+    };
+    std::fs::read_dir(&path).ok()
+        .and_then(|v| {
+            v.filter_map(|v| v.ok())
+                .map(|v| v.path())
+                .filter(|v| v.is_dir())
+                .max_by_key(|v| version(v))
+        })
+        .unwrap_or(path)
+}
+
+// Locate the shared C++ runtime of the Android NDK
+//
+// The NDK ships `libc++_shared.so` in the sysroot of its LLVM toolchain, in
+// the library directory of the target triple of each ABI. There is a single
+// prebuilt toolchain for the host the NDK was downloaded for.
+fn android_ndk_cxx_shared(
+    path_ndk: &std::path::Path,
+    abi: &str,
+) -> Result<std::path::PathBuf, Error> {
+    let triple = match abi {
+        "armeabi-v7a" => "arm-linux-androideabi",
+        "arm64-v8a" => "aarch64-linux-android",
+        "x86" => "i686-linux-android",
+        "x86_64" => "x86_64-linux-android",
+        _ => return Err(Error::Ndk(path_ndk.as_os_str().to_os_string())),
+    };
+
+    let path_prebuilt = path_ndk.join("toolchains").join("llvm").join("prebuilt");
+    std::fs::read_dir(&path_prebuilt).ok()
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.ok())
+        .map(|v| {
+            let mut v = v.path();
+            v.extend(["sysroot", "usr", "lib", triple, "libc++_shared.so"]);
+            v
+        })
+        .find(|v| v.is_file())
+        .ok_or_else(|| Error::Ndk(path_ndk.as_os_str().to_os_string()))
+}
+
+// Locate a prebuilt Android Rust library
+//
+// Look up the library of the given ABI in the prebuilt directory. It is
+// either laid out per ABI (`<abi>/<name>`), or per Rust target triple, either
+// directly (`<triple>/<name>`) or as in a Cargo target directory
+// (`<triple>/release/<name>`).
+fn android_prebuilt(
+    path_prebuilt: &std::path::Path,
+    abi: &str,
+    name: &str,
+) -> Result<std::path::PathBuf, Error> {
+    let triple = match abi {
+        "armeabi-v7a" => Some("armv7-linux-androideabi"),
+        "arm64-v8a" => Some("aarch64-linux-android"),
+        "x86" => Some("i686-linux-android"),
+        "x86_64" => Some("x86_64-linux-android"),
+        _ => None,
+    };
+
+    let mut candidates = vec![path_prebuilt.join(abi).join(name)];
+    if let Some(v) = triple {
+        candidates.push(path_prebuilt.join(v).join(name));
+        candidates.push(path_prebuilt.join(v).join("release").join(name));
+    }
+
+    candidates.into_iter()
+        .find(|v| v.is_file())
+        .ok_or_else(|| Error::Prebuilt(abi.to_string(), path_prebuilt.as_os_str().to_os_string()))
+}
+
+// Stage Android native libraries
+//
+// Copy the additional native libraries of each ABI to `<abi>/` in the given
+// directory, including the shared C++ runtime of the NDK if requested. With
+// a prebuilt directory given, the prebuilt Rust library of each ABI is staged
+// as well, and every ABI is required to be present. The Gradle build picks
+// this directory up as source of `jniLibs`. Previously staged libraries are
+// removed, so no stale libraries are packaged.
+fn stage_android_jni_libs(
+    manifest: &crate::manifest::Manifest,
+    view_application: &crate::manifest::ViewApplication,
+    view_android: &crate::manifest::ViewPlatformAndroid,
+    path_prebuilt: Option<&std::path::Path>,
+    path_jni_libs: &std::path::Path,
+) -> Result<(), Error> {
+    if let Err(v) = std::fs::remove_dir_all(path_jni_libs) {
+        if v.kind() != std::io::ErrorKind::NotFound {
+            return Err(Error::FileRemoval(path_jni_libs.as_os_str().to_os_string(), v));
+        }
+    }
+
+    let path_ndk = view_android.cxx_shared.then(|| android_ndk(manifest, view_android));
+    let name = format!("lib{}.so", view_application.package_symbol);
+
+    for abi in view_android.abis.iter() {
+        let mut libs = Vec::new();
+        if let Some(v) = path_prebuilt {
+            libs.push(android_prebuilt(v, abi, &name)?);
+        }
+        if let Some(v) = path_ndk.as_ref() {
+            libs.push(android_ndk_cxx_shared(v, abi)?);
+        }
+        if let Some(list) = view_android.native_libraries.get(abi) {
+            libs.extend(list.iter().map(|v| manifest.absolute_path(v)));
+        }
+        if libs.is_empty() {
+            continue;
+        }
+
+        let path_abi = path_jni_libs.join(abi);
+        std::fs::create_dir_all(&path_abi).map_err(
+            |_| Error::DirectoryCreation(path_abi.as_os_str().to_os_string()),
+        )?;
+
+        for lib in libs.iter() {
+            let data = std::fs::read(lib).map_err(
+                |v| Error::FileRead(lib.as_os_str().to_os_string(), v),
+            )?;
+            let path_lib = path_abi.join(lib.file_name().unwrap_or_default());
+            std::fs::write(&path_lib, data).map_err(
+                |v| Error::FileUpdate(path_lib.as_os_str().to_os_string(), v),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
 // Android-specific backend to `build()`.
 fn build_android(
     manifest: &crate::manifest::Manifest,
     metadata: &crate::cargo::Metadata,
-    _platform: &crate::manifest::RawPlatform,
+    options: &Options,
+    platform: &crate::manifest::RawPlatform,
     android: &crate::manifest::RawPlatformAndroid,
     path_platform: std::path::PathBuf,
     mut path_build: std::path::PathBuf,
-) -> Result<(), Error> {
+) -> Result<Report, Error> {
     let view_application = manifest.raw.view_application()
         .map_err(Error::from_manifest_error_view)?;
     let view_android = android.view(&manifest.raw)
         .map_err(Error::from_manifest_error_view)?;
 
+    // Stage additional native libraries before Gradle packages them. This
+    // includes the prebuilt Rust libraries, if requested. In that case, drop
+    // any libraries left behind by previous Cargo builds via
+    // rust-android-gradle, since they would clash with the prebuilt ones.
+    let path_prebuilt = options.prebuilt.as_ref().map(|v| {
+        std::env::current_dir()
+            .expect("Cannot query current working directory")
+            .join(v)
+    });
+    stage_android_jni_libs(
+        manifest,
+        &view_application,
+        &view_android,
+        path_prebuilt.as_deref(),
+        &jni_libs_directory(metadata, &platform.id),
+    )?;
+    if path_prebuilt.is_some() {
+        let path = path_build.join("gradle-build").join("rustJniLibs");
+        if let Err(v) = std::fs::remove_dir_all(&path) {
+            if v.kind() != std::io::ErrorKind::NotFound {
+                return Err(Error::FileRemoval(path.into_os_string(), v));
+            }
+        }
+    }
+
     // Invoke Gradle
     //
     // We simply invoke the gradle-build with the requested target. Since
@@ -92,24 +1085,21 @@ fn build_android(
     // from the Gradle defaults too much. Hence, run this in containers to
     // avoid all the gradle peculiarities.
 
-    let bin = "gradle".to_string();
-    let mut cmd = std::process::Command::new(&bin);
+    let (gradle, java_home) = gradle_resolve(
+        options,
+        manifest,
+        &view_android,
+        Some(path_platform.join("gradlew")).filter(|v| v.is_file()),
+    );
 
-    // Set the SDK path via `ANDROID_HOME`. This is required by the Android SDK
-    // Gradle build. Alternatively, this can be set via `local.properties`, but
-    // Gradle has no official support for this, so we avoid it.
-    cmd.env(
-        "ANDROID_HOME",
+    let mut cmd = gradle_command(
+        &gradle,
+        java_home.as_deref(),
         &manifest.absolute_path(&view_android.sdk_path),
+        options.offline,
+        options.variant.as_deref(),
     );
 
-    cmd.arg("build");
-
-    cmd.arg("--no-scan");
-    cmd.arg("--no-watch-fs");
-    cmd.arg("--parallel");
-    cmd.arg("--quiet");
-
     // Tell Gradle the path to the platform integration.
     cmd.arg("--project-dir");
     cmd.arg(path_platform.as_path());
@@ -127,103 +1117,217 @@ fn build_android(
     cmd_gradle_project_prop(&mut cmd, "buildDir", &path_build);
     path_build.pop();
 
-    // Write Gradle system-properties for early configuration. This is needed
-    // for these to be available in `settings.gradle`.
-    cmd_gradle_system_prop(
-        &mut cmd,
-        "osiris.system.name",
-        &view_application.name,
+    // Write all Gradle properties of the platform integration. System
+    // properties are used for early configuration. This is needed for these
+    // to be available in `settings.gradle`. Additionally, record the
+    // properties in `osiris.properties`, so IDEs opening the platform
+    // integration directly see the same configuration.
+    let version = crate::op::emerge::android_version(
+        manifest,
+        metadata,
+        &view_application,
+        &view_android,
+    ).map_err(Error::Version)?;
+    let mut properties = crate::op::emerge::android_properties(
+        manifest,
+        metadata,
+        platform,
+        &view_application,
+        &view_android,
+        &version,
     );
+    crate::op::emerge::emerge_android_osiris_properties(
+        &path_platform,
+        &properties,
+    ).map_err(Error::from_emerge_error)?;
 
-    //
-    // Write `osiris.application.*` properties.
-    //
-
-    cmd_gradle_project_prop(
-        &mut cmd,
-        "osiris.application.packageSymbol",
-        &view_application.package_symbol,
-    );
+    // Prebuilt libraries only apply to this build. IDEs opening the
+    // platform integration keep building via Cargo.
+    if path_prebuilt.is_some() {
+        for (key, value) in properties.iter_mut() {
+            if *key == "osiris.android.prebuilt" {
+                *value = "true".into();
+            }
+        }
+    }
 
-    cmd_gradle_project_prop(
-        &mut cmd,
-        "osiris.application.path",
-        &manifest.absolute_path(&view_application.path),
-    );
+    for (key, value) in properties.iter() {
+        if key.starts_with("osiris.system.") {
+            cmd_gradle_system_prop(&mut cmd, key, value);
+        } else {
+            cmd_gradle_project_prop(&mut cmd, key, value);
+        }
+    }
 
+    // Pass the signing passwords as Gradle project properties via the
+    // environment (`ORG_GRADLE_PROJECT_<key>`). Unlike the command-line, the
+    // environment is not visible to other users, and unlike other properties,
+    // they are never recorded in `osiris.properties`. The keys must be plain
+    // identifiers, since POSIX shells (and thus the Gradle wrapper) drop
+    // environment variables with other names.
     //
-    // Write `osiris.android.*` properties.
-    //
+    // Only the `release` build type is signed, so the passwords are not
+    // required when building other variants only.
+    let signed = !matches!(options.variant.as_deref(), Some(v) if v != "release");
+    if let (true, Some(v)) = (signed, view_android.signing.as_ref()) {
+        for (key, env) in [
+            ("osirisSigningStorePassword", &v.store_password_env),
+            ("osirisSigningKeyPassword", &v.key_password_env),
+        ] {
+            let password = std::env::var_os(env)
+                .ok_or_else(|| Error::SigningPassword(env.clone()))?;
+            cmd.env(format!("ORG_GRADLE_PROJECT_{}", key), password);
+        }
+    }
 
-    cmd_gradle_project_prop(
-        &mut cmd,
-        "osiris.android.applicationId",
-        &view_android.application_id,
-    );
-    cmd_gradle_project_prop(
-        &mut cmd,
-        "osiris.android.namespace",
-        &view_android.namespace,
-    );
+    cmd.stderr(std::process::Stdio::inherit());
+    cmd.stdout(std::process::Stdio::inherit());
 
-    cmd_gradle_project_prop(
-        &mut cmd,
-        "osiris.android.compileSdk",
-        &view_android.compile_sdk.to_string(),
-    );
-    cmd_gradle_project_prop(
-        &mut cmd,
-        "osiris.android.minSdk",
-        &view_android.min_sdk.to_string(),
-    );
-    cmd_gradle_project_prop(
-        &mut cmd,
-        "osiris.android.targetSdk",
-        &view_android.target_sdk.to_string(),
-    );
+    let output = cmd.output().map_err(
+        |v| Error::GradleExec(
+            gradle.as_os_str().to_os_string(),
+            java_home.as_ref().map(|v| v.as_os_str().to_os_string()),
+            v,
+        )
+    )?;
+    if !output.status.success() {
+        return Err(Error::Gradle(
+            gradle.into_os_string(),
+            java_home.map(|v| v.into_os_string()),
+        ));
+    }
 
-    cmd_gradle_project_prop(
-        &mut cmd,
-        "osiris.android.ndkLevel",
-        &view_android.ndk_level.to_string(),
-    );
+    // Report all APKs of all variants. With ABI splits, there is an APK
+    // per ABI, plus an optional universal APK. Libraries produce an AAR
+    // per variant instead, which is published to the local Maven
+    // repository. Additionally, report the R8 mappings of minified
+    // variants.
+    path_build.push("gradle-build");
+    path_build.push("outputs");
+    let mut artifacts = match view_android.artifact {
+        crate::manifest::RawPlatformAndroidArtifact::Application => {
+            collect_android_apks(&path_build.join("apk"))
+        },
+        crate::manifest::RawPlatformAndroidArtifact::Library => {
+            publish_android_aars(
+                &maven_directory(metadata, &platform.id),
+                collect_android_aars(&path_build.join("aar"), &view_application.name),
+                &view_application,
+                &view_android,
+                &version,
+            )?
+        },
+    };
+    path_build.push("mapping");
+    artifacts.extend(collect_android_mappings(&path_build));
+    path_build.pop();
+    path_build.pop();
 
-    cmd_gradle_project_prop(
-        &mut cmd,
-        "osiris.android.versionCode",
-        &view_android.version_code.to_string(),
-    );
-    cmd_gradle_project_prop(
-        &mut cmd,
-        "osiris.android.versionName",
-        &view_android.version_name,
-    );
+    // Archive the unstripped native libraries of each variant, so crashes
+    // can be symbolicated later on.
+    path_build.push("intermediates");
+    path_build.push("merged_native_libs");
+    artifacts.extend(archive_android_symbols(
+        &path_build,
+        &symbols_directory(metadata, &platform.id),
+    )?);
 
-    cmd_gradle_project_prop(
-        &mut cmd,
-        "osiris.android.abis",
-        &view_android.abis.join(";"),
-    );
+    // Lint the packaged native libraries, so problems that would only show
+    // on a device are caught right away.
+    let findings = match view_android.elf_lint {
+        crate::manifest::RawPlatformAndroidElfLint::Allow => Vec::new(),
+        crate::manifest::RawPlatformAndroidElfLint::Warn => {
+            lint_android_libraries(&path_build, &view_application, &view_android)?
+        },
+        crate::manifest::RawPlatformAndroidElfLint::Deny => {
+            let v = lint_android_libraries(&path_build, &view_application, &view_android)?;
+            if !v.is_empty() {
+                return Err(Error::Lint(v));
+            }
+            v
+        },
+    };
 
-    //
-    // Write `osiris.metadata.*` properties.
-    //
+    Ok(Report {
+        android_version: Some(version),
+        artifacts,
+        findings,
+    })
+}
 
-    cmd_gradle_project_prop(
-        &mut cmd,
-        "osiris.metadata.targetDirectory",
-        &metadata.target_directory,
-    );
+/// JNI libraries directory
+///
+/// Return the path to the directory used to stage additional native libraries
+/// of the specified platform, laid out as `<abi>/<name>.so`. This is
+/// `<target>/osiris/build/<platform>/jniLibs`.
+pub fn jni_libs_directory(
+    metadata: &crate::cargo::Metadata,
+    platform_id: &str,
+) -> std::path::PathBuf {
+    let mut v = build_directory(metadata, platform_id);
+    v.push("jniLibs");
+    v
+}
 
-    cmd.stderr(std::process::Stdio::inherit());
-    cmd.stdout(std::process::Stdio::inherit());
+/// Maven directory
+///
+/// Return the path to the local Maven repository that Android libraries of
+/// the specified platform are published to. This is
+/// `<target>/osiris/build/<platform>/maven`.
+pub fn maven_directory(
+    metadata: &crate::cargo::Metadata,
+    platform_id: &str,
+) -> std::path::PathBuf {
+    let mut v = build_directory(metadata, platform_id);
+    v.push("maven");
+    v
+}
 
-    let output = cmd.output().map_err(|v| Error::Exec(bin, v))?;
-    if !output.status.success() {
-        return Err(Error::Build);
-    }
+/// Ephemeral platform directory
+///
+/// Return the path to the directory used for ephemeral platform integration
+/// of the specified platform, if no persistent platform integration exists.
+/// This is `<target>/osiris/platform/<platform>`.
+pub fn ephemeral_platform_directory(
+    metadata: &crate::cargo::Metadata,
+    platform_id: &str,
+) -> std::path::PathBuf {
+    let mut v = std::path::PathBuf::new();
+    v.push(&metadata.target_directory);
+    v.push("osiris");
+    v.push("platform");
+    v.push(platform_id);
+    v
+}
 
-    Ok(())
+/// Build directory
+///
+/// Return the path to the directory used for all build artifacts of the
+/// specified platform. This is `<target>/osiris/build/<platform>`.
+pub fn build_directory(
+    metadata: &crate::cargo::Metadata,
+    platform_id: &str,
+) -> std::path::PathBuf {
+    let mut v = std::path::PathBuf::new();
+    v.push(&metadata.target_directory);
+    v.push("osiris");
+    v.push("build");
+    v.push(platform_id);
+    v
+}
+
+/// Symbols directory
+///
+/// Return the path to the directory used to archive the unstripped native
+/// libraries of the specified platform, grouped by build variant and ABI.
+/// This is `<target>/osiris/build/<platform>/symbols`.
+pub fn symbols_directory(
+    metadata: &crate::cargo::Metadata,
+    platform_id: &str,
+) -> std::path::PathBuf {
+    let mut v = build_directory(metadata, platform_id);
+    v.push("symbols");
+    v
 }
 
 /// Build platform integration
@@ -235,11 +1339,15 @@ fn build_android(
 /// The target directory of the current crate is used to store any build
 /// artifacts. Hence, you likely want to call this through `cargo <external>`
 /// to ensure cargo integration is hooked up as expected.
+///
+/// On success, a report of the build is returned, including the resolved
+/// version of the application.
 pub fn build(
     manifest: &crate::manifest::Manifest,
     metadata: &crate::cargo::Metadata,
     platform: &crate::manifest::RawPlatform,
-) -> Result<(), Error> {
+    options: &Options,
+) -> Result<Report, Error> {
     let view_platform = platform.view()
         .map_err(Error::from_manifest_error_view)?;
 
@@ -270,52 +1378,25 @@ pub fn build(
     // and emerge ephemeral platform integration into it. The directory is
     // created at `<target>/osiris/platform/<platform>/`.
     if !accessible {
-        path_platform.clear();
-        path_platform.push(&metadata.target_directory);
-        path_platform.push("osiris");
-        path_platform.push("platform");
-        path_platform.push(&platform.id);
+        path_platform = ephemeral_platform_directory(metadata, &platform.id);
 
         std::fs::create_dir_all(path_platform.as_path()).map_err(
             |_| Error::DirectoryCreation(path_platform.as_os_str().to_os_string())
         )?;
 
-        match crate::op::emerge::emerge(
+        crate::op::emerge::emerge(
             manifest,
+            Some(metadata),
             platform,
             Some(path_platform.as_path()),
             true,
-        ) {
-            Err(crate::op::emerge::Error::Already) => {
-                unreachable!("Emerging with updates allowed must not yield");
-            },
-            Err(crate::op::emerge::Error::ManifestKey(key)) => {
-                return Err(Error::ManifestKey(key));
-            },
-            Err(crate::op::emerge::Error::PlatformDirectory(dir)) => {
-                return Err(Error::PlatformDirectory(dir));
-            },
-            Err(crate::op::emerge::Error::DirectoryCreation(dir)) => {
-                return Err(Error::DirectoryCreation(dir));
-            },
-            Err(crate::op::emerge::Error::FileUpdate(file, error)) => {
-                return Err(Error::FileUpdate(file, error));
-            },
-            Err(crate::op::emerge::Error::FileRemoval(file, error)) => {
-                return Err(Error::FileRemoval(file, error));
-            },
-            Ok(_) => {
-            },
-        }
+        ).map_err(Error::from_emerge_error)?;
     }
 
     // Create a build directory for all output artifacts of the build process.
     // Re-use the existing directory, if possible, to speed up builds. The
     // directory is created at: `<target>/osiris/build/<platform>`.
-    path_build.push(&metadata.target_directory);
-    path_build.push("osiris");
-    path_build.push("build");
-    path_build.push(&platform.id);
+    path_build.push(build_directory(metadata, &platform.id));
     std::fs::create_dir_all(path_build.as_path()).map_err(
         |_| Error::DirectoryCreation(path_build.as_os_str().to_os_string())
     )?;
@@ -324,8 +1405,241 @@ pub fn build(
     // it can reuse it for further operations.
     match platform.configuration {
         Some(crate::manifest::RawPlatformConfiguration::Android(ref v)) => {
-            build_android(manifest, metadata, platform, v, path_platform, path_build)
+            build_android(manifest, metadata, options, platform, v, path_platform, path_build)
         },
-        None => Ok(()),
+        None => Ok(Report::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Verify the lint of Android native libraries
+    //
+    // A clean library yields no findings, while each problem yields a
+    // finding of its own. Libraries packaged alongside satisfy `DT_NEEDED`.
+    #[test]
+    fn lint_android_basic() {
+        let mut library = crate::elf::Library {
+            machine: addr2line::object::elf::EM_AARCH64,
+            is_64: true,
+            needed: vec!["libc.so".to_string(), "libc++_shared.so".to_string()],
+            load_alignments: vec![0x4000, 0x4000],
+            exports: ["ANativeActivity_onCreate".to_string()].into(),
+            api_level: Some(21),
+            ..Default::default()
+        };
+        let packaged = ["libapp.so".to_string(), "libc++_shared.so".to_string()];
+        let kind = Some("ANativeActivity_onCreate");
+
+        assert_eq!(lint_android_library(&library, "arm64-v8a", &packaged, kind, 21), []);
+
+        library.needed.push("libaaudio.so".to_string());
+        library.text_relocations = true;
+        library.load_alignments.push(0x1000);
+        library.api_level = Some(26);
+        assert_eq!(
+            lint_android_library(&library, "arm64-v8a", &packaged[..1], kind, 21),
+            [
+                ReportFindingKind::Needed("libc++_shared.so".to_string()),
+                ReportFindingKind::NeededApiLevel("libaaudio.so".to_string(), 26),
+                ReportFindingKind::TextRelocations,
+                ReportFindingKind::PageAlignment(0x1000),
+                ReportFindingKind::ApiLevel(26),
+            ],
+        );
+
+        // 32-bit ABIs are not affected by 16 KB pages, and JNI libraries
+        // can export JNI methods instead of `JNI_OnLoad`.
+        library.needed.clear();
+        library.text_relocations = false;
+        library.api_level = None;
+        assert_eq!(
+            lint_android_library(
+                &library,
+                "armeabi-v7a",
+                &packaged,
+                Some("JNI_OnLoad"),
+                21,
+            ),
+            [
+                ReportFindingKind::Machine(addr2line::object::elf::EM_AARCH64),
+                ReportFindingKind::EntryPoint("JNI_OnLoad"),
+            ],
+        );
+        library.exports.insert("Java_org_example_NativeLibrary_run".to_string());
+        library.load_alignments.pop();
+        assert_eq!(lint_android_library(&library, "arm64-v8a", &packaged, Some("JNI_OnLoad"), 21), []);
+    }
+
+    // Verify the publication of Android library archives
+    //
+    // AARs are reported per variant, and published with the release
+    // variant as main artifact, alongside a POM listing the dependencies.
+    #[test]
+    fn publish_android_layout() {
+        let dir = std::env::temp_dir().join(format!("osiris-aar-{}", std::process::id()));
+        let dir_aar = dir.join("aar");
+        std::fs::create_dir_all(&dir_aar).unwrap();
+        for v in ["My App-debug.aar", "My App-free-release.aar", "My App-release.aar", "other.aar"] {
+            std::fs::write(dir_aar.join(v), b"").unwrap();
+        }
+
+        let aars = collect_android_aars(&dir_aar, "My App");
+        assert_eq!(
+            aars.iter().map(|v| v.variant.as_str()).collect::<Vec<_>>(),
+            ["debug", "free-release", "release"],
+        );
+
+        let pom = android_pom(
+            "org.example",
+            "app",
+            "1.0",
+            "My App",
+            &["androidx.core:core:1.12.0".to_string(), "files('x.jar')".to_string()],
+        );
+        assert!(pom.contains("<groupId>org.example</groupId>"));
+        assert!(pom.contains("<packaging>aar</packaging>"));
+        assert!(pom.contains("<artifactId>core</artifactId>"));
+        assert!(!pom.contains("x.jar"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // Verify the lookup of prebuilt Rust libraries
+    //
+    // Libraries are found per ABI, per target triple, or in the release
+    // directory of a target triple, and missing ABIs are refused.
+    #[test]
+    fn prebuilt_android_layout() {
+        let dir = std::env::temp_dir().join(format!("osiris-prebuilt-{}", std::process::id()));
+        for v in ["arm64-v8a", "x86_64-linux-android", "i686-linux-android/release"] {
+            std::fs::create_dir_all(dir.join(v)).unwrap();
+            std::fs::write(dir.join(v).join("libapp.so"), b"").unwrap();
+        }
+
+        assert_eq!(
+            android_prebuilt(&dir, "arm64-v8a", "libapp.so").ok().unwrap(),
+            dir.join("arm64-v8a/libapp.so"),
+        );
+        assert_eq!(
+            android_prebuilt(&dir, "x86_64", "libapp.so").ok().unwrap(),
+            dir.join("x86_64-linux-android/libapp.so"),
+        );
+        assert_eq!(
+            android_prebuilt(&dir, "x86", "libapp.so").ok().unwrap(),
+            dir.join("i686-linux-android/release/libapp.so"),
+        );
+        assert!(matches!(
+            android_prebuilt(&dir, "armeabi-v7a", "libapp.so"),
+            Err(Error::Prebuilt(v, _)) if v == "armeabi-v7a",
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // Verify archiving of native debug symbols
+    //
+    // Only libraries with debug information are archived, and variants
+    // without any are not reported. The test is skipped if no C compiler is
+    // available.
+    #[cfg(unix)]
+    #[test]
+    fn archive_android_symbols_debug_info() {
+        let dir = std::env::temp_dir().join(format!("osiris-symbols-{}", std::process::id()));
+        let intermediates = dir.join("intermediates");
+        let symbols = dir.join("symbols");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("fixture.c"), "int osiris_fixture(void) { return 0; }\n").unwrap();
+
+        for (variant, debug) in [("debug", true), ("release", false)] {
+            let path = intermediates.join(variant).join("out/lib/arm64-v8a");
+            std::fs::create_dir_all(&path).unwrap();
+            let mut cmd = std::process::Command::new("cc");
+            cmd.current_dir(&dir).args(["-shared", "-fPIC", "-o"]).arg(path.join("libapp.so"));
+            if debug {
+                cmd.arg("-g");
+            }
+            if !cmd.arg("fixture.c").status().map(|v| v.success()).unwrap_or(false) {
+                std::fs::remove_dir_all(&dir).unwrap();
+                return;
+            }
+        }
+
+        // A stale archive of a variant without debug information is removed.
+        std::fs::create_dir_all(symbols.join("release")).unwrap();
+
+        let artifacts = archive_android_symbols(&intermediates, &symbols).ok().unwrap();
+        assert_eq!(artifacts.len(), 1);
+        assert_eq!(artifacts[0].kind, ReportArtifactKind::DebugSymbols);
+        assert_eq!(artifacts[0].variant, "debug");
+        assert_eq!(artifacts[0].path, symbols.join("debug/native-debug-symbols.zip"));
+        assert!(symbols.join("debug/arm64-v8a/libapp.so").exists());
+        assert!(!symbols.join("release").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // Verify that offline mode applies to both Gradle and Cargo, that the
+    // environment is only overridden where requested, and that a selected
+    // variant is assembled on its own.
+    #[test]
+    fn gradle_command_offline() {
+        let has_arg = |cmd: &std::process::Command, arg: &str| cmd.get_args().any(|v| v == arg);
+        let env = |cmd: &std::process::Command, key: &str| {
+            cmd.get_envs().find(|v| v.0 == key).and_then(|v| v.1.map(|v| v.to_os_string()))
+        };
+
+        let cmd = gradle_command("gradle".as_ref(), None, "/sdk".as_ref(), true, None);
+        assert_eq!(cmd.get_program(), "gradle");
+        assert!(has_arg(&cmd, "build"));
+        assert!(has_arg(&cmd, "--offline"));
+        assert_eq!(env(&cmd, "CARGO_NET_OFFLINE"), Some("true".into()));
+        assert_eq!(env(&cmd, "ANDROID_HOME"), Some("/sdk".into()));
+        assert_eq!(env(&cmd, "JAVA_HOME"), None);
+
+        let cmd = gradle_command("gradle".as_ref(), Some("/jdk".as_ref()), "/sdk".as_ref(), false, Some("debug"));
+        assert!(has_arg(&cmd, "assembleDebug"));
+        assert!(!has_arg(&cmd, "build"));
+        assert!(!has_arg(&cmd, "--offline"));
+        assert_eq!(env(&cmd, "CARGO_NET_OFFLINE"), None);
+        assert_eq!(env(&cmd, "JAVA_HOME"), Some("/jdk".into()));
+    }
+
+    // Verify the precedence of the Gradle executable and Java installation:
+    // options first, then the manifest, then the Gradle wrapper (for the
+    // executable), and finally the default.
+    #[test]
+    fn gradle_resolve_precedence() {
+        let resolve = |m: &crate::manifest::Manifest, options: &Options, wrapper: Option<&str>| {
+            let v = m.raw.platform[0].android().unwrap().view(&m.raw).ok().unwrap();
+            gradle_resolve(options, m, &v, wrapper.map(|v| v.into()))
+        };
+
+        let full = crate::manifest::tests::android("gradle-path = \"bin/gradle\"\njava-home = \"jdk\"").unwrap();
+        let bare = crate::manifest::tests::android("").unwrap();
+        let options = Options {
+            gradle: Some("/opt/gradle".into()),
+            java_home: Some("/opt/jdk".into()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            resolve(&full, &options, Some("/p/gradlew")),
+            ("/opt/gradle".into(), Some("/opt/jdk".into())),
+        );
+        assert_eq!(
+            resolve(&full, &Options::default(), Some("/p/gradlew")),
+            (full.absolute_path(&"bin/gradle"), Some(full.absolute_path(&"jdk"))),
+        );
+        assert_eq!(
+            resolve(&bare, &Options::default(), Some("/p/gradlew")),
+            ("/p/gradlew".into(), None),
+        );
+        assert_eq!(
+            resolve(&bare, &Options::default(), None),
+            ("gradle".into(), None),
+        );
     }
 }
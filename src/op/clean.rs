@@ -0,0 +1,366 @@
+//! Clean Platform Integration
+//!
+//! Remove the artifacts created by the build and emerge operations. This
+//! covers ephemeral platform integration, Gradle caches, and collected build
+//! outputs. Optionally, persistent platform integration is removed as well,
+//! as long as it was not modified since it was emerged.
+
+/// Clean Errors
+///
+/// This is the exhaustive list of possible errors raised by the clean
+/// operation. See each error for details.
+pub enum Error {
+    /// Specified key required but missing in manifest.
+    ManifestKey(&'static str),
+    /// Removing the directory at the specified path failed with the given
+    /// error.
+    DirectoryRemoval(std::ffi::OsString, std::io::Error),
+    /// Reading the file at the specified path failed with the given error.
+    FileRead(std::ffi::OsString, std::io::Error),
+    /// Updating the file at the specified path failed with the given error.
+    FileUpdate(std::ffi::OsString, std::io::Error),
+    /// Removing the file at the specified path failed with the given error.
+    FileRemoval(std::ffi::OsString, std::io::Error),
+}
+
+impl Error {
+    fn from_manifest_error_view(error: crate::manifest::ErrorView) -> Self {
+        match error {
+            crate::manifest::ErrorView::MissingKey(v) => Self::ManifestKey(v),
+        }
+    }
+}
+
+/// Clean Options
+///
+/// This contains all options of the clean operation that are not part of
+/// the manifest. Defaults are used for all options left unset.
+#[derive(Default)]
+pub struct Options {
+    /// Whether to retain the Gradle caches, speeding up subsequent builds.
+    pub keep_caches: bool,
+    /// Whether to remove persistent platform integration as well. Only
+    /// files that are unmodified since they were emerged are removed.
+    pub persistent: bool,
+}
+
+// Remove directory tree if it exists
+//
+// Remove the directory at the specified path recursively, but only if it
+// exists. Errors about missing directories are ignored.
+fn remove_dir(path: &std::path::Path) -> Result<(), Error> {
+    match std::fs::remove_dir_all(path) {
+        Err(v) if v.kind() != std::io::ErrorKind::NotFound => {
+            Err(Error::DirectoryRemoval(path.as_os_str().to_os_string(), v))
+        },
+        _ => {
+            Ok(())
+        }
+    }
+}
+
+// Remove file if it exists
+//
+// Remove the file at the specified path, but only if it exists. Errors about
+// missing files are ignored.
+fn remove_file(path: &std::path::Path) -> Result<(), Error> {
+    match std::fs::remove_file(path) {
+        Err(v) if v.kind() != std::io::ErrorKind::NotFound => {
+            Err(Error::FileRemoval(path.as_os_str().to_os_string(), v))
+        },
+        _ => {
+            Ok(())
+        }
+    }
+}
+
+// Prune empty directories
+//
+// Remove the directory at the specified path, and then each of its parents,
+// as long as they are empty. Stop at `root`, which is pruned as well if
+// empty. Failures are ignored, since non-empty directories are retained on
+// purpose.
+fn prune_dirs(root: &std::path::Path, path: &std::path::Path) {
+    let mut path = Some(path);
+
+    while let Some(v) = path {
+        if !v.starts_with(root) || std::fs::remove_dir(v).is_err() {
+            break;
+        }
+        path = v.parent();
+    }
+}
+
+// Clean the build directory
+//
+// Remove all content of the build directory of a platform. If caches are to
+// be retained, the Gradle cache is skipped, otherwise the entire directory
+// is removed.
+fn clean_build(
+    options: &Options,
+    path_build: &std::path::Path,
+) -> Result<(), Error> {
+    if !options.keep_caches {
+        return remove_dir(path_build);
+    }
+
+    let entries = match std::fs::read_dir(path_build) {
+        Ok(v) => v,
+        Err(v) if v.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(v) => {
+            return Err(Error::DirectoryRemoval(path_build.as_os_str().to_os_string(), v));
+        },
+    };
+
+    for entry in entries {
+        let entry = entry.map_err(
+            |v| Error::DirectoryRemoval(path_build.as_os_str().to_os_string(), v),
+        )?;
+
+        if entry.file_name() == "gradle-cache" {
+            continue;
+        }
+
+        let path = entry.path();
+        if path.is_dir() {
+            remove_dir(&path)?;
+        } else {
+            remove_file(&path)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Clean persistent platform integration
+//
+// Remove all files recorded in the emerge state of the platform directory,
+// unless they were modified since. Modified files are retained and kept in
+// the emerge state, and their paths are added to `kept`. Directories that
+// end up empty are removed.
+fn clean_persistent(
+    manifest: &crate::manifest::Manifest,
+    platform: &crate::manifest::RawPlatform,
+    kept: &mut Vec<std::path::PathBuf>,
+) -> Result<(), Error> {
+    let view_platform = platform.view()
+        .map_err(Error::from_manifest_error_view)?;
+
+    let root = manifest.absolute_path(&view_platform.path);
+    let path_state = root.join(crate::op::emerge::State::FILE);
+
+    // Without emerge state, there is no persistent platform integration, or
+    // it predates the emerge state. In both cases, leave it untouched.
+    let content = match std::fs::read_to_string(&path_state) {
+        Ok(v) => v,
+        Err(v) if v.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(v) => {
+            return Err(Error::FileRead(path_state.into_os_string(), v));
+        },
+    };
+
+    let state = crate::op::emerge::State::parse(&content);
+    let mut state_kept = crate::op::emerge::State::default();
+
+    for (file, hash) in state.files.into_iter() {
+        let path: std::path::PathBuf = root.join(
+            file.split('/').collect::<std::path::PathBuf>(),
+        );
+
+        let data = match std::fs::read(&path) {
+            Ok(v) => v,
+            Err(v) if v.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(v) => {
+                return Err(Error::FileRead(path.into_os_string(), v));
+            },
+        };
+
+        if crate::util::fnv1a64(&data) == hash {
+            remove_file(&path)?;
+            if let Some(v) = path.parent() {
+                prune_dirs(&root, v);
+            }
+        } else {
+            state_kept.files.insert(file, hash);
+            kept.push(path);
+        }
+    }
+
+    // `osiris.properties` is refreshed on every build, so it is never
+    // considered modified.
+    remove_file(&root.join("osiris.properties"))?;
+
+    if state_kept.files.is_empty() {
+        remove_file(&path_state)?;
+    } else {
+        std::fs::write(&path_state, state_kept.format()).map_err(
+            |v| Error::FileUpdate(path_state.as_os_str().to_os_string(), v),
+        )?;
+    }
+
+    prune_dirs(&root, &root);
+
+    Ok(())
+}
+
+/// Clean platform integration
+///
+/// Remove the ephemeral platform integration, the Gradle caches, and the
+/// collected build outputs of the specified platform, or of all platforms if
+/// none is specified. See `Options` for parameters to retain caches or remove
+/// persistent platform integration as well.
+///
+/// On success, the paths of all persistent files that were retained due to
+/// local modifications are returned.
+pub fn clean(
+    manifest: &crate::manifest::Manifest,
+    metadata: &crate::cargo::Metadata,
+    platform: Option<&crate::manifest::RawPlatform>,
+    options: &Options,
+) -> Result<Vec<std::path::PathBuf>, Error> {
+    let mut kept = Vec::new();
+    let platforms: Vec<&crate::manifest::RawPlatform> = match platform {
+        Some(v) => vec![v],
+        None => manifest.raw.platform.iter().collect(),
+    };
+
+    for platform in platforms {
+        clean_build(
+            options,
+            &crate::op::build::build_directory(metadata, &platform.id),
+        )?;
+        remove_dir(&crate::op::build::ephemeral_platform_directory(metadata, &platform.id))?;
+
+        if options.persistent {
+            clean_persistent(manifest, platform, &mut kept)?;
+        }
+    }
+
+    // Remove the shared parent directories, if nothing is left in them.
+    let mut path = std::path::PathBuf::new();
+    path.push(&metadata.target_directory);
+    path.push("osiris");
+    prune_dirs(&path, &path.join("build"));
+    prune_dirs(&path, &path.join("platform"));
+
+    Ok(kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Create an empty temporary directory unique to the given test case.
+    fn tmpdir(case: &str) -> std::path::PathBuf {
+        let v = std::env::temp_dir().join(
+            format!("osiris-clean-{}-{}", std::process::id(), case),
+        );
+        let _ = std::fs::remove_dir_all(&v);
+        std::fs::create_dir_all(&v).unwrap();
+        v
+    }
+
+    // Verify that unmodified persistent files are removed, modified files
+    // are retained, and the emerge state is rewritten to list only the
+    // retained files.
+    #[test]
+    fn clean_persistent_basic() {
+        let dir = tmpdir("persistent");
+        let manifest = crate::manifest::Manifest::parse_str(
+            &dir.join("osiris-platform.toml"),
+            "version = 1\n[[platform]]\nid = \"android\"\npath = \"platform\"\n",
+        ).unwrap();
+        let root = dir.join("platform");
+
+        std::fs::create_dir_all(root.join("app/src")).unwrap();
+        std::fs::write(root.join("build.gradle"), "foo").unwrap();
+        std::fs::write(root.join("app/src/Main.java"), "bar").unwrap();
+        std::fs::write(root.join("app/local.txt"), "modified").unwrap();
+        std::fs::write(root.join("osiris.properties"), "").unwrap();
+        std::fs::write(dir.join("outside"), "baz").unwrap();
+
+        let mut state = crate::op::emerge::State::default();
+        state.files.insert("build.gradle".into(), crate::util::fnv1a64(b"foo"));
+        state.files.insert("app/src/Main.java".into(), crate::util::fnv1a64(b"bar"));
+        state.files.insert("app/local.txt".into(), crate::util::fnv1a64(b"original"));
+        state.files.insert("missing.txt".into(), 0);
+        let content = format!(
+            "{}{:016x} ../outside\n",
+            state.format(),
+            crate::util::fnv1a64(b"baz"),
+        );
+        std::fs::write(root.join("osiris.emerge"), content).unwrap();
+
+        let mut kept = Vec::new();
+        clean_persistent(&manifest, &manifest.raw.platform[0], &mut kept).ok().unwrap();
+
+        assert_eq!(kept, vec![root.join("app/local.txt")]);
+        assert!(!root.join("build.gradle").exists());
+        assert!(!root.join("osiris.properties").exists());
+        assert!(!root.join("app/src").exists());
+        assert!(root.join("app/local.txt").exists());
+        assert!(dir.join("outside").exists());
+
+        let v = crate::op::emerge::State::parse(
+            &std::fs::read_to_string(root.join("osiris.emerge")).unwrap(),
+        );
+        assert_eq!(v.files.len(), 1);
+        assert_eq!(
+            v.files.get("app/local.txt"),
+            Some(&crate::util::fnv1a64(b"original")),
+        );
+
+        // Once modifications are reverted, the state and the platform
+        // directory are removed entirely.
+        std::fs::write(root.join("app/local.txt"), "original").unwrap();
+        clean_persistent(&manifest, &manifest.raw.platform[0], &mut kept).ok().unwrap();
+        assert!(!root.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // Verify that the Gradle cache is only retained if requested.
+    #[test]
+    fn clean_build_keep_caches() {
+        let dir = tmpdir("build");
+        let build = dir.join("android");
+
+        std::fs::create_dir_all(build.join("gradle-cache/caches")).unwrap();
+        std::fs::create_dir_all(build.join("outputs/apk")).unwrap();
+        std::fs::write(build.join("log.txt"), "").unwrap();
+
+        let options = Options { keep_caches: true, ..Default::default() };
+        clean_build(&options, &build).ok().unwrap();
+        assert!(build.join("gradle-cache/caches").exists());
+        assert!(!build.join("outputs").exists());
+        assert!(!build.join("log.txt").exists());
+
+        clean_build(&Options::default(), &build).ok().unwrap();
+        assert!(!build.exists());
+        clean_build(&options, &build).ok().unwrap();
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // Verify that empty directories are pruned up to and including the
+    // root, but never beyond it, and that non-empty directories are kept.
+    #[test]
+    fn prune_dirs_basic() {
+        let dir = tmpdir("prune");
+        let root = dir.join("root");
+
+        std::fs::create_dir_all(root.join("a/b/c")).unwrap();
+        std::fs::create_dir_all(root.join("d")).unwrap();
+        prune_dirs(&root, &root.join("a/b/c"));
+        assert!(!root.join("a").exists());
+        assert!(root.join("d").exists());
+
+        std::fs::remove_dir(root.join("d")).unwrap();
+        std::fs::create_dir_all(root.join("a/b")).unwrap();
+        prune_dirs(&root, &root.join("a/b"));
+        assert!(!root.exists());
+        assert!(dir.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
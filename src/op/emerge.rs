@@ -4,6 +4,10 @@
 //! Unlike just-in-time integration at build time, this allows adjusting the
 //! platform integration to specific needs and retaining modifications across
 //! builds.
+//!
+//! All emerged files are recorded with a hash of their content in
+//! `osiris.emerge` in the platform directory. This allows the clean
+//! operation to tell unmodified files apart from local modifications.
 
 /// Emerge Errors
 ///
@@ -23,6 +27,15 @@ pub enum Error {
     FileUpdate(std::ffi::OsString, std::io::Error),
     /// Removing the file at the specified path failed with the given error.
     FileRemoval(std::ffi::OsString, std::io::Error),
+    /// Resolving the version of the platform integration failed.
+    Version(crate::manifest::ErrorVersion),
+    /// The Rust bridge file at the specified path exists, but was not
+    /// generated by the emerge operation, so it is not overwritten.
+    BridgeFile(std::ffi::OsString),
+    /// The icon image at the specified path cannot be loaded or rendered.
+    Icon(std::ffi::OsString),
+    /// Loading the localization catalog failed.
+    Catalog(crate::manifest::ErrorCatalog),
 }
 
 impl Error {
@@ -33,12 +46,42 @@ impl Error {
     }
 }
 
-// Escape XML PCDATA
+// Escape Android string resources
 //
-// Ensure `data` is suitably escaped to be used verbatim in XML PCDATA.
-fn escape_xml_pcdata(data: &str) -> String {
-    data.replace("&", "&amp;")
-        .replace("<", "&lt;")
+// Ensure `data` is suitably escaped to be used verbatim as string resource in
+// Android resource XML. Besides XML escaping, this escapes quotes,
+// backslashes and control characters, which `aapt2` would interpret
+// otherwise, as well as a leading `@` or `?`, which would turn the string
+// into a reference. Leading, trailing and repeated spaces are escaped, since
+// `aapt2` collapses white-space.
+fn escape_android_string(data: &str) -> String {
+    let mut v = String::with_capacity(data.len());
+    let n = data.chars().count();
+    let mut prev = None;
+
+    for (i, c) in data.chars().enumerate() {
+        match c {
+            '&' => v.push_str("&amp;"),
+            '<' => v.push_str("&lt;"),
+            '>' => v.push_str("&gt;"),
+            '\\' => v.push_str("\\\\"),
+            '"' => v.push_str("\\\""),
+            '\'' => v.push_str("\\'"),
+            '\n' => v.push_str("\\n"),
+            '\t' => v.push_str("\\t"),
+            '@' | '?' if i == 0 => {
+                v.push('\\');
+                v.push(c);
+            },
+            ' ' if i == 0 || prev == Some(' ') || i + 1 == n => {
+                v.push_str("\\u0020");
+            },
+            _ => v.push(c),
+        }
+        prev = Some(c);
+    }
+
+    v
 }
 
 // Ensure directory exists
@@ -69,6 +112,17 @@ fn ensure_dir(
 fn update_file(
     path: &std::path::Path,
     content: &str,
+) -> Result<(), Error> {
+    update_file_bytes(path, content.as_bytes())
+}
+
+// Update a binary file if required
+//
+// This is the same as `update_file()`, but takes arbitrary binary content
+// rather than text.
+fn update_file_bytes(
+    path: &std::path::Path,
+    content: &[u8],
 ) -> Result<(), Error> {
     // Open the file read+write and create it if it does not exist, yet.
     let mut f = std::fs::OpenOptions::new()
@@ -81,8 +135,8 @@ fn update_file(
         )?;
 
     // Read the entire file content into memory.
-    let mut old = String::new();
-    <std::fs::File as std::io::Read>::read_to_string(&mut f, &mut old)
+    let mut old = Vec::new();
+    <std::fs::File as std::io::Read>::read_to_end(&mut f, &mut old)
         .map_err(
             |v| Error::FileUpdate(path.as_os_str().to_os_string(), v),
         )?;
@@ -99,7 +153,7 @@ fn update_file(
             |v| Error::FileUpdate(path.as_os_str().to_os_string(), v),
         )?;
 
-        <std::fs::File as std::io::Write>::write_all(&mut f, content.as_bytes())
+        <std::fs::File as std::io::Write>::write_all(&mut f, content)
             .map_err(
                 |v| Error::FileUpdate(path.as_os_str().to_os_string(), v),
             )?;
@@ -129,6 +183,376 @@ fn unlink_file(path: &std::path::Path) -> Result<(), Error> {
     }
 }
 
+/// Emerge State
+///
+/// The emerge state records all files written by the emerge operation,
+/// relative to the platform directory, together with a hash of their
+/// content. This allows detecting whether emerged files were modified
+/// afterwards. The state is stored in `osiris.emerge` in the platform
+/// directory.
+#[derive(Default)]
+pub(crate) struct State {
+    /// Emerged files relative to the platform directory, using `/` as
+    /// separator, mapped to the hash of their content.
+    pub files: std::collections::BTreeMap<String, u64>,
+}
+
+impl State {
+    /// File-name of the emerge state in the platform directory.
+    pub const FILE: &'static str = "osiris.emerge";
+
+    /// Parse the emerge state from its file content. Each line contains
+    /// the content hash in hexadecimal and the relative path, separated
+    /// by a single space. Comments and malformed lines are ignored, and so
+    /// are paths that could escape the platform directory, like absolute
+    /// paths or paths with `..` components.
+    pub fn parse(content: &str) -> Self {
+        let mut v = Self::default();
+
+        for line in content.lines() {
+            if line.starts_with('#') {
+                continue;
+            }
+            if let Some((hash, file)) = line.split_once(' ') {
+                if let Ok(hash) = u64::from_str_radix(hash, 16) {
+                    if Self::valid(file) {
+                        v.files.insert(file.to_string(), hash);
+                    }
+                }
+            }
+        }
+
+        v
+    }
+
+    // Check whether a recorded path is relative and `/`-separated, with
+    // each component naming a plain file or directory. Anything else is
+    // rejected, since recorded files are removed relative to the platform
+    // directory and must never resolve outside of it.
+    fn valid(file: &str) -> bool {
+        !file.is_empty() && file.split('/').all(|v| {
+            let mut components = std::path::Path::new(v).components();
+            matches!(
+                (components.next(), components.next()),
+                (Some(std::path::Component::Normal(c)), None) if c == v,
+            )
+        })
+    }
+
+    /// Format the emerge state as file content.
+    pub fn format(&self) -> String {
+        let mut v = String::new();
+
+        v.push_str("# Generated by osiris-platform\n");
+        for (file, hash) in self.files.iter() {
+            v.push_str(&format!("{:016x} {}\n", hash, file));
+        }
+
+        v
+    }
+}
+
+// Emit a file and record it in the emerge state
+//
+// Update the file via `update_file()` and record it with the hash of its
+// content in the emerge state. The path is recorded relative to the
+// platform directory `root`.
+fn emit_file(
+    state: &mut State,
+    root: &std::path::Path,
+    path: &std::path::Path,
+    content: &str,
+) -> Result<(), Error> {
+    emit_file_bytes(state, root, path, content.as_bytes())
+}
+
+// Emit a binary file and record it in the emerge state
+//
+// This is the same as `emit_file()`, but takes arbitrary binary content
+// rather than text.
+fn emit_file_bytes(
+    state: &mut State,
+    root: &std::path::Path,
+    path: &std::path::Path,
+    content: &[u8],
+) -> Result<(), Error> {
+    update_file_bytes(path, content)?;
+
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    let relative = relative.iter()
+        .map(|v| v.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    state.files.insert(relative, crate::util::fnv1a64(content));
+
+    Ok(())
+}
+
+// Sweep leftovers of a previous emerge operation
+//
+// Remove all files recorded in the `previous` emerge state, but no longer in
+// the current `state`. Files modified since they were emerged are retained,
+// so local changes are never lost.
+fn sweep_files(
+    previous: &State,
+    state: &State,
+    root: &std::path::Path,
+) -> Result<(), Error> {
+    for (file, hash) in previous.files.iter() {
+        if state.files.contains_key(file) {
+            continue;
+        }
+
+        let path = root.join(file);
+        if let Ok(data) = std::fs::read(&path) {
+            if crate::util::fnv1a64(&data) == *hash {
+                unlink_file(path.as_path())?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Escape Java properties values
+//
+// Ensure `data` is suitably escaped to be used verbatim as value in a Java
+// properties file. Gradle loads properties via `Properties.load()` from a
+// byte stream, which decodes ISO-8859-1, so all characters outside of
+// printable ASCII are written as `\uXXXX` escapes of their UTF-16 code
+// units. The separators and comment characters `=`, `:`, `#` and `!` are
+// escaped as well.
+fn escape_properties_value(data: &str) -> String {
+    let mut v = String::with_capacity(data.len());
+
+    for (i, c) in data.chars().enumerate() {
+        match c {
+            '\\' => v.push_str("\\\\"),
+            '\n' => v.push_str("\\n"),
+            '\r' => v.push_str("\\r"),
+            '\t' => v.push_str("\\t"),
+            ' ' if i == 0 => v.push_str("\\ "),
+            '=' | ':' | '#' | '!' => {
+                v.push('\\');
+                v.push(c);
+            },
+            c if !(' '..='~').contains(&c) => {
+                let mut buf = [0u16; 2];
+                for u in c.encode_utf16(&mut buf) {
+                    v.push_str(&format!("\\u{:04X}", u));
+                }
+            },
+            _ => v.push(c),
+        }
+    }
+
+    v
+}
+
+/// Resolve Android version
+///
+/// Resolve the version code and version name of the Android platform
+/// integration via `ViewPlatformAndroid::resolve_version()`. The version of
+/// the Cargo package of the application is used as default version name, the
+/// Git history of the application directory for commit counts, and the
+/// current system time for timestamps.
+pub(crate) fn android_version(
+    manifest: &crate::manifest::Manifest,
+    metadata: &crate::cargo::Metadata,
+    view_application: &crate::manifest::ViewApplication,
+    view_android: &crate::manifest::ViewPlatformAndroid,
+) -> Result<crate::manifest::ViewPlatformAndroidVersion, crate::manifest::ErrorVersion> {
+    let package_version = metadata.packages.iter()
+        .find(|v| v.name == view_application.package)
+        .map(|v| v.version.as_str());
+
+    let mut git_commit_count = || -> Option<u32> {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(manifest.absolute_path(&view_application.path))
+            .args(["rev-list", "--count", "HEAD"])
+            .stderr(std::process::Stdio::null())
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        std::str::from_utf8(&output.stdout).ok()?.trim().parse().ok()
+    };
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|v| v.as_secs())
+        .unwrap_or(0);
+
+    view_android.resolve_version(package_version, &mut git_commit_count, timestamp)
+}
+
+/// Collect Android platform properties
+///
+/// Collect all properties that parameterize the Android platform integration.
+/// They are passed to Gradle on the command-line by the build operation, and
+/// are recorded in `osiris.properties` as fallback for IDEs that open the
+/// platform integration directly. Keys starting with `osiris.system.` are
+/// Gradle system-properties, all others are Gradle project-properties.
+pub(crate) fn android_properties(
+    manifest: &crate::manifest::Manifest,
+    metadata: &crate::cargo::Metadata,
+    platform: &crate::manifest::RawPlatform,
+    view_application: &crate::manifest::ViewApplication,
+    view_android: &crate::manifest::ViewPlatformAndroid,
+    version: &crate::manifest::ViewPlatformAndroidVersion,
+) -> Vec<(&'static str, std::ffi::OsString)> {
+    let abi_offsets = view_android.version_code_abi_offsets.iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join(";");
+
+    let mut properties: Vec<(&'static str, std::ffi::OsString)> = vec![
+        // `osiris.system.*` properties
+        ("osiris.system.name", view_application.name.clone().into()),
+
+        // `osiris.application.*` properties
+        ("osiris.application.packageSymbol", view_application.package_symbol.clone().into()),
+        ("osiris.application.path", manifest.absolute_path(&view_application.path).into()),
+
+        // `osiris.android.*` properties
+        ("osiris.android.applicationId", view_android.application_id.clone().into()),
+        ("osiris.android.namespace", view_android.namespace.clone().into()),
+        ("osiris.android.compileSdk", view_android.compile_sdk.to_string().into()),
+        ("osiris.android.minSdk", view_android.min_sdk.to_string().into()),
+        ("osiris.android.targetSdk", view_android.target_sdk.to_string().into()),
+        ("osiris.android.ndkLevel", view_android.ndk_level.to_string().into()),
+        ("osiris.android.versionCode", version.code.to_string().into()),
+        ("osiris.android.versionCodeAbiOffsets", abi_offsets.into()),
+        ("osiris.android.versionName", version.name.clone().into()),
+        ("osiris.android.abis", view_android.abis.join(";").into()),
+        ("osiris.android.universalApk", view_android.universal_apk.to_string().into()),
+        ("osiris.android.shrinkResources", view_android.shrink_resources.to_string().into()),
+        (
+            "osiris.android.corrosionPath",
+            view_android.corrosion_path.as_ref()
+                .map(|v| manifest.absolute_path(v).into())
+                .unwrap_or_default(),
+        ),
+
+        // `osiris.metadata.*` properties
+        ("osiris.metadata.targetDirectory", metadata.target_directory.clone().into()),
+    ];
+
+    // Only the location of the signing key and the names of the environment
+    // variables holding its passwords are recorded. The passwords are never
+    // written to disk. The build operation passes them to Gradle via the
+    // environment.
+    if let Some(v) = view_android.signing.as_ref() {
+        properties.extend([
+            ("osiris.android.signing.storeFile", manifest.absolute_path(&v.keystore).into()),
+            ("osiris.android.signing.keyAlias", v.key_alias.clone().into()),
+            ("osiris.android.signing.storePasswordEnv", v.store_password_env.clone().into()),
+            ("osiris.android.signing.keyPasswordEnv", v.key_password_env.clone().into()),
+        ]);
+    }
+
+    // Additional native libraries, as well as prebuilt Rust libraries, are
+    // staged by the build operation in the build directory, rather than the
+    // platform integration. Only the build operation knows whether prebuilt
+    // libraries are used, so Cargo is invoked by default.
+    properties.extend([
+        ("osiris.android.jniLibs", crate::op::build::jni_libs_directory(metadata, &platform.id).into()),
+        ("osiris.android.prebuilt", "false".into()),
+    ]);
+
+    properties
+}
+
+// Android native library load order
+//
+// Return the names of the native libraries to load before the Rust library,
+// as passed to `System.loadLibrary()`. Before API level 23, the dynamic
+// linker does not find dependencies in the application, so they must be
+// loaded in dependency order. The C++ runtime goes first, followed by the
+// additional libraries in the order listed. Libraries not listed for all
+// ABIs cannot be loaded unconditionally, so they are left to the dynamic
+// linker.
+fn android_preload_libraries(view_android: &crate::manifest::ViewPlatformAndroid) -> Vec<String> {
+    let mut v = Vec::new();
+
+    if view_android.cxx_shared {
+        v.push("c++_shared".to_string());
+    }
+
+    let names = |abi: &str| -> Vec<String> {
+        view_android.native_libraries.get(abi)
+            .map(|list| {
+                list.iter()
+                    .filter_map(|v| std::path::Path::new(v).file_name())
+                    .filter_map(|v| v.to_str())
+                    .filter_map(|v| v.strip_prefix("lib"))
+                    .filter_map(|v| v.strip_suffix(".so"))
+                    .map(|v| v.to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    if let Some(first) = view_android.abis.first() {
+        for name in names(first) {
+            if !v.contains(&name) && view_android.abis.iter().all(|abi| names(abi).contains(&name)) {
+                v.push(name);
+            }
+        }
+    }
+
+    v
+}
+
+/// Emerge Android `osiris.properties`
+///
+/// Write the properties collected by `android_properties()` into
+/// `osiris.properties` in the given platform directory. The Gradle files of
+/// the platform integration load this file as fallback for any property not
+/// provided on the command-line.
+///
+/// The file contains absolute paths of the local machine, and thus should
+/// not be committed to version control. It is refreshed on every build.
+pub(crate) fn emerge_android_osiris_properties(
+    path: &std::path::Path,
+    properties: &[(&'static str, std::ffi::OsString)],
+) -> Result<(), Error> {
+    let mut content = String::new();
+
+    content.push_str("# Generated by osiris-platform\n");
+    for (key, value) in properties.iter() {
+        content.push_str(&format!(
+            "{}={}\n",
+            key,
+            escape_properties_value(&value.to_string_lossy()),
+        ));
+    }
+
+    update_file(path.join("osiris.properties").as_path(), content.as_str())
+}
+
+// Emerge Android `.gitignore`
+//
+// Exclude files from version control that are local to the machine, or are
+// refreshed on every build. This covers `osiris.properties`, which contains
+// absolute paths of the local machine.
+fn emerge_android_gitignore(
+    path: &mut std::path::PathBuf,
+    state: &mut State,
+    root: &std::path::Path,
+) -> Result<(), Error> {
+    let content = concat!(
+        "# Generated by osiris-platform\n",
+        "/osiris.properties\n",
+    );
+    path.push(".gitignore");
+    emit_file(state, root, path.as_path(), content)?;
+    path.pop();
+    Ok(())
+}
+
 // Emerge Android `gradle.properties`
 //
 // `gradle.properties` is a key-value store read by Gradle before startup. It
@@ -144,6 +568,8 @@ fn unlink_file(path: &std::path::Path) -> Result<(), Error> {
 //    the build.
 fn emerge_android_gradle_properties(
     path: &mut std::path::PathBuf,
+    state: &mut State,
+    root: &std::path::Path,
 ) -> Result<(), Error> {
     let content = concat!(
         "# Generated by osiris-platform\n",
@@ -153,7 +579,7 @@ fn emerge_android_gradle_properties(
         "android.nonTransitiveRClass=true\n",
     );
     path.push("gradle.properties");
-    update_file(path.as_path(), content)?;
+    emit_file(state, root, path.as_path(), content)?;
     path.pop();
     Ok(())
 }
@@ -172,6 +598,114 @@ fn emerge_android_local_properties(
     Ok(())
 }
 
+// Gradle DSL
+//
+// Gradle build files are written either in the Groovy DSL or in the Kotlin
+// DSL, depending on the selected language. Both describe the same structure,
+// so the build files are assembled once, and only the syntax that differs is
+// rendered via the helpers below.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum GradleDsl {
+    Groovy,
+    Kotlin,
+}
+
+impl GradleDsl {
+    fn new(language: crate::manifest::RawPlatformAndroidLanguage) -> Self {
+        match language {
+            crate::manifest::RawPlatformAndroidLanguage::Java => Self::Groovy,
+            crate::manifest::RawPlatformAndroidLanguage::Kotlin => Self::Kotlin,
+        }
+    }
+
+    // Quote a string literal.
+    fn quote(self, v: &str) -> String {
+        match self {
+            Self::Groovy => format!("'{}'", v),
+            Self::Kotlin => format!("\"{}\"", v),
+        }
+    }
+
+    // Create a list literal of string literals.
+    fn list(self, v: &[&str]) -> String {
+        let v = v.iter().map(|v| self.quote(v)).collect::<Vec<_>>().join(", ");
+        match self {
+            Self::Groovy => format!("[{}]", v),
+            Self::Kotlin => format!("listOf({})", v),
+        }
+    }
+
+    // Read an Osiris property as string, integer, or boolean.
+    fn property(self, key: &str) -> String {
+        format!("osirisProperty({})", self.quote(key))
+    }
+
+    fn property_int(self, key: &str) -> String {
+        match self {
+            Self::Groovy => format!("Integer.parseInt({})", self.property(key)),
+            Self::Kotlin => format!("{}.toInt()", self.property(key)),
+        }
+    }
+
+    fn property_bool(self, key: &str) -> String {
+        match self {
+            Self::Groovy => format!("Boolean.parseBoolean({})", self.property(key)),
+            Self::Kotlin => format!("{}.toBoolean()", self.property(key)),
+        }
+    }
+
+    // Read a project property passed on the command-line, if any.
+    fn project_property(self, key: &str) -> String {
+        match self {
+            Self::Groovy => format!("project.findProperty({})", self.quote(key)),
+            Self::Kotlin => format!("(project.findProperty({}) as String?)", self.quote(key)),
+        }
+    }
+
+    // Assign a value to a property. Boolean properties use `flag()`
+    // instead, since the Kotlin DSL prefixes their names with `is`.
+    fn assign(self, name: &str, value: &str) -> String {
+        match self {
+            Self::Groovy => format!("{} {}", name, value),
+            Self::Kotlin => format!("{} = {}", name, value),
+        }
+    }
+
+    fn flag(self, name: &str, value: &str) -> String {
+        match self {
+            Self::Groovy => self.assign(name, value),
+            Self::Kotlin => self.assign(
+                &format!("is{}{}", name[..1].to_ascii_uppercase(), &name[1..]),
+                value,
+            ),
+        }
+    }
+
+    // Call a method with the given arguments.
+    fn call(self, name: &str, args: &str) -> String {
+        match self {
+            Self::Groovy => format!("{} {}", name, args),
+            Self::Kotlin => format!("{}({})", name, args),
+        }
+    }
+
+    // Refer to an existing element of a named container, or create a new
+    // one.
+    fn named(self, name: &str) -> String {
+        match self {
+            Self::Groovy => name.to_string(),
+            Self::Kotlin => format!("getByName({})", self.quote(name)),
+        }
+    }
+
+    fn create(self, name: &str) -> String {
+        match self {
+            Self::Groovy => name.to_string(),
+            Self::Kotlin => format!("create({})", self.quote(name)),
+        }
+    }
+}
+
 // Emerge Android `settings.gradle`
 //
 // `settings.gradle` is the root configuration for Gradle. It is similar to
@@ -179,35 +713,204 @@ fn emerge_android_local_properties(
 // key-value store. It is read before the `build.gradle` file. We use it to:
 //
 //  * Configure the Gradle module resolution behavior and specify which module
-//    registries are used.
+//    registries are used. If the manifest specifies Maven repositories, they
+//    replace the default public registries (e.g., for air-gapped builds
+//    against a local mirror).
 //
 //  * Configure the root project name. This is used in file-names for build
-//    artifacts.
+//    artifacts. It is taken from the system-properties, or from
+//    `osiris.properties` as fallback.
+//
+// With the Kotlin language selected, `settings.gradle.kts` is written
+// instead, using the Gradle Kotlin DSL (see `GradleDsl`). The file of the
+// other language is left to the sweep of the emerge state.
 fn emerge_android_settings_gradle(
     path: &mut std::path::PathBuf,
+    state: &mut State,
+    root: &std::path::Path,
+    language: crate::manifest::RawPlatformAndroidLanguage,
+    maven_repositories: Option<&[String]>,
 ) -> Result<(), Error> {
+    let d = GradleDsl::new(language);
+    let (file, name) = match d {
+        GradleDsl::Groovy => (
+            "settings.gradle",
+            concat!(
+                "var osirisProperties = new Properties()\n",
+                "var osirisPropertiesFile = new File(settingsDir, 'osiris.properties')\n",
+                "if (osirisPropertiesFile.exists()) {\n",
+                "    osirisPropertiesFile.withInputStream { osirisProperties.load(it) }\n",
+                "}\n",
+                "rootProject.name = System.getProperty('osiris.system.name')",
+                " ?: osirisProperties.getProperty('osiris.system.name')\n",
+            ),
+        ),
+        GradleDsl::Kotlin => (
+            "settings.gradle.kts",
+            concat!(
+                "val osirisProperties = java.util.Properties().apply {\n",
+                "    val f = File(settingsDir, \"osiris.properties\")\n",
+                "    if (f.exists()) {\n",
+                "        f.inputStream().use { load(it) }\n",
+                "    }\n",
+                "}\n",
+                "rootProject.name = System.getProperty(\"osiris.system.name\")",
+                " ?: osirisProperties.getProperty(\"osiris.system.name\")\n",
+            ),
+        ),
+    };
+
+    let (repositories_plugins, repositories_dependencies) = match maven_repositories {
+        None => (
+            concat!(
+                "        google()\n",
+                "        mavenCentral()\n",
+                "        gradlePluginPortal()\n",
+            ).to_string(),
+            concat!(
+                "        google()\n",
+                "        mavenCentral()\n",
+            ).to_string(),
+        ),
+        Some(list) => {
+            let mut v = String::new();
+            for url in list {
+                v.push_str(&format!("        maven {{ url = uri({}) }}\n", d.quote(url)));
+            }
+            (v.clone(), v)
+        },
+    };
+
     let content = format!(
         concat!(
             "// Generated by osiris-platform\n",
             "pluginManagement {{\n",
             "    repositories {{\n",
-            "        google()\n",
-            "        mavenCentral()\n",
-            "        gradlePluginPortal()\n",
+            "{0}",
             "    }}\n",
             "}}\n",
             "dependencyResolutionManagement {{\n",
             "    repositoriesMode.set(RepositoriesMode.FAIL_ON_PROJECT_REPOS)\n",
             "    repositories {{\n",
-            "        google()\n",
-            "        mavenCentral()\n",
+            "{1}",
             "    }}\n",
             "}}\n",
-            "rootProject.name = System.getProperty('osiris.system.name')\n",
+            "{2}",
         ),
+        repositories_plugins,
+        repositories_dependencies,
+        name,
     );
-    path.push("settings.gradle");
-    update_file(path.as_path(), content.as_str())?;
+
+    path.push(file);
+    emit_file(state, root, path.as_path(), content.as_str())?;
+    path.pop();
+    Ok(())
+}
+
+// Android runtime dependencies
+//
+// Return the version-catalog accessors of the runtime dependencies of the
+// application. They depend on the activity kind. The `AppCompat` stub needs
+// AppCompat, Material and ConstraintLayout for its layout. The `GameActivity`
+// stub needs the games-activity library, which itself builds on AppCompat.
+// The framework `NativeActivity` needs no dependencies at all.
+fn android_dependencies(
+    activity_kind: crate::manifest::RawPlatformAndroidActivityKind,
+) -> &'static [&'static str] {
+    match activity_kind {
+        crate::manifest::RawPlatformAndroidActivityKind::AppCompat => &[
+            "libs.androidx.appcompat",
+            "libs.material",
+            "libs.androidx.constraintlayout",
+        ],
+        crate::manifest::RawPlatformAndroidActivityKind::NativeActivity => &[],
+        crate::manifest::RawPlatformAndroidActivityKind::GameActivity => &[
+            "libs.androidx.appcompat",
+            "libs.material",
+            "libs.androidx.games.activity",
+        ],
+    }
+}
+
+// Emerge Android `libs.versions.toml`
+//
+// The Gradle version catalog defines the versions of all plugins and
+// libraries used by the build files. It is picked up automatically by Gradle
+// from `gradle/libs.versions.toml`. The versions are taken from the manifest,
+// which provides defaults for all of them.
+//
+// The catalog lists all libraries that might be used by the build files,
+// regardless of whether they are used with the current configuration.
+fn emerge_android_version_catalog(
+    path: &mut std::path::PathBuf,
+    state: &mut State,
+    root: &std::path::Path,
+    versions: &std::collections::BTreeMap<String, String>,
+) -> Result<(), Error> {
+    let mut content = String::new();
+
+    content.push_str("# Generated by osiris-platform\n");
+    content.push_str("[versions]\n");
+    for (k, v) in versions.iter() {
+        content.push_str(&format!("{} = \"{}\"\n", k, v));
+    }
+
+    content.push_str(concat!(
+        "\n",
+        "[libraries]\n",
+        "androidx-appcompat = { group = \"androidx.appcompat\", name = \"appcompat\", version.ref = \"appcompat\" }\n",
+        "androidx-constraintlayout = { group = \"androidx.constraintlayout\", name = \"constraintlayout\", version.ref = \"constraintlayout\" }\n",
+        "androidx-games-activity = { group = \"androidx.games\", name = \"games-activity\", version.ref = \"games-activity\" }\n",
+        "androidx-test-espresso = { group = \"androidx.test.espresso\", name = \"espresso-core\", version.ref = \"espresso\" }\n",
+        "androidx-test-junit = { group = \"androidx.test.ext\", name = \"junit\", version.ref = \"androidx-test-junit\" }\n",
+        "junit = { group = \"junit\", name = \"junit\", version.ref = \"junit\" }\n",
+        "material = { group = \"com.google.android.material\", name = \"material\", version.ref = \"material\" }\n",
+        "\n",
+        "[plugins]\n",
+        "android-application = { id = \"com.android.application\", version.ref = \"agp\" }\n",
+        "android-library = { id = \"com.android.library\", version.ref = \"agp\" }\n",
+        "kotlin-android = { id = \"org.jetbrains.kotlin.android\", version.ref = \"kotlin\" }\n",
+        "rust-android-gradle = { id = \"org.mozilla.rust-android-gradle.rust-android\", version.ref = \"rust-android-gradle\" }\n",
+    ));
+
+    path.push("libs.versions.toml");
+    emit_file(state, root, path.as_path(), content.as_str())?;
+    path.pop();
+    Ok(())
+}
+
+// Emerge Android `gradle-wrapper.properties`
+//
+// The Gradle wrapper pins the Gradle distribution used for a project. The
+// wrapper scripts and JAR are not generated here, but can be created via
+// `gradle wrapper`. However, the properties alone suffice for Android Studio
+// and other IDEs to pick the right distribution.
+//
+// If no Gradle version is pinned, nothing is written, so properties created
+// by `gradle wrapper` are retained. Leftover properties of a previously pinned
+// version are removed by the sweep of the emerge state, unless modified.
+fn emerge_android_gradle_wrapper_properties(
+    path: &mut std::path::PathBuf,
+    state: &mut State,
+    root: &std::path::Path,
+    gradle_version: Option<&str>,
+) -> Result<(), Error> {
+    path.push("gradle-wrapper.properties");
+    if let Some(version) = gradle_version {
+        let content = format!(
+            concat!(
+                "# Generated by osiris-platform\n",
+                "distributionBase=GRADLE_USER_HOME\n",
+                "distributionPath=wrapper/dists\n",
+                "distributionUrl=https\\://services.gradle.org/distributions/gradle-{0}-bin.zip\n",
+                "zipStoreBase=GRADLE_USER_HOME\n",
+                "zipStorePath=wrapper/dists\n",
+            ),
+            version,
+        );
+        emit_file(state, root, path.as_path(), content.as_str())?;
+    }
     path.pop();
     Ok(())
 }
@@ -216,114 +919,722 @@ fn emerge_android_settings_gradle(
 //
 // `build.gradle` is the root build file for Gradle. It defines the artifacts
 // to build, using the Groovy configuration language.
+//
+// With the Kotlin language selected, `build.gradle.kts` is written instead,
+// with the same semantics but using the Gradle Kotlin DSL (see `GradleDsl`).
+// This also applies the Kotlin Android plugin, so Kotlin sources can be
+// compiled. The file of the other language is left to the sweep of the
+// emerge state.
+//
+// The Rust library is either built via rust-android-gradle, which adds a
+// `cargoBuild` task to Gradle, or via the CMake integration of the Android
+// Gradle Plugin, which uses Corrosion to import the Cargo package (see
+// `emerge_android_cmake_lists()`).
+//
+// With the library artifact selected, a `com.android.library` module is
+// built instead of an application, producing an AAR per variant.
 fn emerge_android_build_gradle(
     path: &mut std::path::PathBuf,
+    state: &mut State,
+    root: &std::path::Path,
+    view_android: &crate::manifest::ViewPlatformAndroid,
 ) -> Result<(), Error> {
+    let d = GradleDsl::new(view_android.language);
+    let file = match d {
+        GradleDsl::Groovy => "build.gradle",
+        GradleDsl::Kotlin => "build.gradle.kts",
+    };
+
+    // Libraries are embedded into applications, which own versioning,
+    // packaging and signing. Hence, all application-only configuration is
+    // skipped for them, and the Rust library of all ABIs is packaged into
+    // a single AAR.
+    let application = view_android.artifact == crate::manifest::RawPlatformAndroidArtifact::Application;
+    let variants = match application {
+        true => "applicationVariants",
+        false => "libraryVariants",
+    };
+    let activity_dependencies = match application {
+        true => android_dependencies(view_android.activity_kind),
+        false => &[],
+    };
+
+    let mut dependencies = String::new();
+    for v in activity_dependencies {
+        dependencies.push_str(&format!("    {}\n", d.call("implementation", v)));
+    }
+    for v in view_android.dependencies.iter() {
+        dependencies.push_str(&format!("    {}\n", d.call("implementation", &d.quote(v))));
+    }
+
+    // Properties are passed on the command-line by the build operation, but
+    // are also recorded in `osiris.properties` for IDEs opening the project
+    // directly.
+    let properties = match d {
+        GradleDsl::Groovy => concat!(
+            "var osirisProperties = new Properties()\n",
+            "var osirisPropertiesFile = file('osiris.properties')\n",
+            "if (osirisPropertiesFile.exists()) {\n",
+            "    osirisPropertiesFile.withInputStream { osirisProperties.load(it) }\n",
+            "}\n",
+            "var osirisProperty = { String key ->\n",
+            "    project.findProperty(key) ?: osirisProperties.getProperty(key)\n",
+            "}\n",
+            "\n",
+            "var osirisAndroidAbis = osirisProperty('osiris.android.abis').split(';').collect{ it as String }\n",
+        ),
+        GradleDsl::Kotlin => concat!(
+            "val osirisProperties = java.util.Properties().apply {\n",
+            "    val f = file(\"osiris.properties\")\n",
+            "    if (f.exists()) {\n",
+            "        f.inputStream().use { load(it) }\n",
+            "    }\n",
+            "}\n",
+            "\n",
+            "fun osirisProperty(key: String): String =\n",
+            "    (project.findProperty(key) as String?) ?: osirisProperties.getProperty(key)\n",
+            "\n",
+            "val osirisAndroidAbis = osirisProperty(\"osiris.android.abis\").split(\";\")\n",
+        ),
+    };
+
+    let (plugins, native_config, native, cargo) = match view_android.integration {
+        crate::manifest::RawPlatformAndroidIntegration::RustAndroidGradle => {
+            // Convert the Android ABIs to the strings expected by
+            // rust-android-gradle. For some reason, they do not support the
+            // same namespace.
+            let targets = match d {
+                GradleDsl::Groovy => concat!(
+                    "osirisAndroidAbis.collect{\n",
+                    "        if (it == 'armeabi-v7a') {\n",
+                    "            'arm'\n",
+                    "        } else if (it == 'arm64-v8a') {\n",
+                    "            'arm64'\n",
+                    "        } else {\n",
+                    "            it\n",
+                    "        }\n",
+                    "    }",
+                ),
+                GradleDsl::Kotlin => concat!(
+                    "osirisAndroidAbis.map {\n",
+                    "        when (it) {\n",
+                    "            \"armeabi-v7a\" -> \"arm\"\n",
+                    "            \"arm64-v8a\" -> \"arm64\"\n",
+                    "            else -> it\n",
+                    "        }\n",
+                    "    }",
+                ),
+            };
+
+            // Once Gradle parsing is done, ensure that the asset targets of
+            // all application or library variants pull in the Cargo build,
+            // unless prebuilt libraries are packaged instead.
+            let hook = match d {
+                GradleDsl::Groovy => format!(concat!(
+                    "        android.{0}.all {{ variant ->\n",
+                    "            def flavor = ''\n",
+                    "            variant.productFlavors.each {{\n",
+                    "                flavor += \"${{it.name.capitalize()}}\"\n",
+                    "            }}\n",
+                    "            def type = \"${{variant.buildType.name.capitalize()}}\"\n",
+                    "            tasks[\"generate${{flavor}}${{type}}Assets\"].dependsOn(tasks['cargoBuild'])\n",
+                    "        }}\n",
+                ), variants),
+                GradleDsl::Kotlin => format!(concat!(
+                    "        android.{0}.all {{\n",
+                    "            val flavor = productFlavors.joinToString(\"\") {{\n",
+                    "                it.name.replaceFirstChar {{ c -> c.uppercase() }}\n",
+                    "            }}\n",
+                    "            val type = buildType.name.replaceFirstChar {{ c -> c.uppercase() }}\n",
+                    "            tasks.named(\"generate${{flavor}}${{type}}Assets\") {{\n",
+                    "                dependsOn(tasks.named(\"cargoBuild\"))\n",
+                    "            }}\n",
+                    "        }}\n",
+                ), variants),
+            };
+
+            (
+                "    alias(libs.plugins.rust.android.gradle)\n".to_string(),
+                String::new(),
+                String::new(),
+                format!(
+                    concat!(
+                        "cargo {{\n",
+                        "    apiLevel = {api_level}\n",
+                        "    extraCargoBuildArguments = {arguments}\n",
+                        "    libname = {libname}\n",
+                        "    module = {module}\n",
+                        "    targetDirectory = {target_directory}\n",
+                        "\n",
+                        "    profile = {profile}\n",
+                        "\n",
+                        "    targets = {targets}\n",
+                        "}}\n",
+                        "\n",
+                        "afterEvaluate {{\n",
+                        "    if (!{prebuilt}) {{\n",
+                        "{hook}",
+                        "    }}\n",
+                        "}}\n",
+                        "\n",
+                    ),
+                    api_level = d.property_int("osiris.android.minSdk"),
+                    // Always build the release profile with debug
+                    // information, so the unstripped libraries kept by the
+                    // Android Gradle Plugin can be symbolicated.
+                    arguments = d.list(&["--lib", "--config", "profile.release.debug=true"]),
+                    libname = d.property("osiris.application.packageSymbol"),
+                    module = d.property("osiris.application.path"),
+                    target_directory = d.property("osiris.metadata.targetDirectory"),
+                    // Unfortunately, rust-android-gradle only supports a
+                    // single build-target. We have to settle on a profile to
+                    // use, so we pick the release profile. Preferably, we
+                    // would create a build-target per profile, but that is
+                    // not yet supported.
+                    profile = d.quote("release"),
+                    targets = targets,
+                    prebuilt = d.property_bool("osiris.android.prebuilt"),
+                    hook = hook,
+                ),
+            )
+        },
+        crate::manifest::RawPlatformAndroidIntegration::Cmake => (
+            String::new(),
+            format!(
+                concat!(
+                    "\n",
+                    "        externalNativeBuild {{\n",
+                    "            cmake {{\n",
+                    "                {0}\n",
+                    "            }}\n",
+                    "        }}\n",
+                ),
+                d.call(
+                    "arguments",
+                    &format!(
+                        concat!(
+                            "\"-DOSIRIS_APPLICATION_PATH=${{{}}}\", ",
+                            "\"-DOSIRIS_CORROSION_PATH=${{{}}}\", ",
+                            "\"-DOSIRIS_OFFLINE=${{gradle.startParameter.offline}}\"",
+                        ),
+                        d.property("osiris.application.path"),
+                        d.property("osiris.android.corrosionPath"),
+                    ),
+                ),
+            ),
+            // Prebuilt libraries replace the CMake build entirely.
+            format!(
+                concat!(
+                    "\n",
+                    "    if (!{0}) {{\n",
+                    "        externalNativeBuild {{\n",
+                    "            cmake {{\n",
+                    "                {1}\n",
+                    "                {2}\n",
+                    "            }}\n",
+                    "        }}\n",
+                    "    }}\n",
+                ),
+                d.property_bool("osiris.android.prebuilt"),
+                d.assign("path", &format!("file({})", d.quote("src/main/native/CMakeLists.txt"))),
+                d.assign("version", &d.quote("3.22.1")),
+            ),
+            String::new(),
+        ),
+    };
+
+    // Per-ABI outputs (e.g., from ABI splits) get their offset added to the
+    // version code, so each ABI has a distinct version code.
+    let abi_offsets = if !application || view_android.version_code_abi_offsets.is_empty() {
+        ""
+    } else {
+        match d {
+            GradleDsl::Groovy => concat!(
+                "androidComponents {\n",
+                "    onVariants(selector().all()) { variant ->\n",
+                "        def offsets = osirisProperty('osiris.android.versionCodeAbiOffsets').tokenize(';').collectEntries { it.tokenize('=') }\n",
+                "        variant.outputs.each { output ->\n",
+                "            def abi = output.filters.find { it.filterType.name() == 'ABI' }?.identifier\n",
+                "            if (abi != null && offsets.containsKey(abi)) {\n",
+                "                output.versionCode.set(output.versionCode.get() + Integer.parseInt(offsets[abi]))\n",
+                "            }\n",
+                "        }\n",
+                "    }\n",
+                "}\n",
+                "\n",
+            ),
+            GradleDsl::Kotlin => concat!(
+                "androidComponents {\n",
+                "    onVariants(selector().all()) { variant ->\n",
+                "        val offsets = osirisProperty(\"osiris.android.versionCodeAbiOffsets\")\n",
+                "            .split(\";\")\n",
+                "            .filter { it.isNotEmpty() }\n",
+                "            .associate { it.substringBefore(\"=\") to it.substringAfter(\"=\").toInt() }\n",
+                "        variant.outputs.forEach { output ->\n",
+                "            val abi = output.filters.find {\n",
+                "                it.filterType == com.android.build.api.variant.FilterConfiguration.FilterType.ABI\n",
+                "            }?.identifier\n",
+                "            val offset = offsets[abi]\n",
+                "            if (offset != null) {\n",
+                "                output.versionCode.set(output.versionCode.get()!! + offset)\n",
+                "            }\n",
+                "        }\n",
+                "    }\n",
+                "}\n",
+                "\n",
+            ),
+        }
+    };
+
+    // ABI splits and NDK ABI filters are mutually exclusive. With splits, an
+    // APK is produced for each ABI, plus an optional universal APK.
+    // Otherwise, a single APK contains all ABIs.
+    let (abi_filters, abi_splits) = if application && view_android.abi_splits {
+        (
+            String::new(),
+            format!(
+                concat!(
+                    "\n",
+                    "    splits {{\n",
+                    "        abi {{\n",
+                    "            {0}\n",
+                    "            reset()\n",
+                    "            include({1})\n",
+                    "            {2}\n",
+                    "        }}\n",
+                    "    }}\n",
+                ),
+                d.flag("enable", "true"),
+                match d {
+                    GradleDsl::Groovy => "*osirisAndroidAbis",
+                    GradleDsl::Kotlin => "*osirisAndroidAbis.toTypedArray()",
+                },
+                d.flag("universalApk", &d.property_bool("osiris.android.universalApk")),
+            ),
+        )
+    } else {
+        (
+            concat!(
+                "            ndk {\n",
+                "                abiFilters.clear()\n",
+                "                abiFilters.addAll(osirisAndroidAbis)\n",
+                "            }\n",
+            ).to_string(),
+            String::new(),
+        )
+    };
+
+    // Release builds are signed with the configured key, if any. The
+    // passwords are passed by the build operation as project properties via
+    // the environment. Builds not driven by the build operation (e.g., IDEs)
+    // read the configured environment variables directly.
+    let (signing_configs, signing_release) = if application && view_android.signing.is_some() {
+        let password = |property: &str, env: &str| format!(
+            "{} ?: System.getenv({})",
+            d.project_property(property),
+            d.property(env),
+        );
+        (
+            format!(
+                concat!(
+                    "    signingConfigs {{\n",
+                    "        {0} {{\n",
+                    "            {1}\n",
+                    "            {2}\n",
+                    "            {3}\n",
+                    "            {4}\n",
+                    "        }}\n",
+                    "    }}\n",
+                    "\n",
+                ),
+                d.create("release"),
+                d.assign("storeFile", &format!("file({})", d.property("osiris.android.signing.storeFile"))),
+                d.assign(
+                    "storePassword",
+                    &password("osirisSigningStorePassword", "osiris.android.signing.storePasswordEnv"),
+                ),
+                d.assign("keyAlias", &d.property("osiris.android.signing.keyAlias")),
+                d.assign(
+                    "keyPassword",
+                    &password("osirisSigningKeyPassword", "osiris.android.signing.keyPasswordEnv"),
+                ),
+            ),
+            format!(
+                "            {}\n",
+                d.assign("signingConfig", &format!("signingConfigs.{}", d.named("release"))),
+            ),
+        )
+    } else {
+        (String::new(), String::new())
+    };
+
+    // Minified release builds use R8 with the optimizing default rules plus
+    // the rules of `proguard-rules.pro`. Libraries are minified by the
+    // consuming application instead, which applies the rules of
+    // `consumer-rules.pro`.
+    let minify = if application && view_android.minify {
+        format!(
+            "            {}\n            {}\n            {}\n",
+            d.flag("minifyEnabled", "true"),
+            d.flag("shrinkResources", &d.property_bool("osiris.android.shrinkResources")),
+            d.call(
+                "proguardFiles",
+                &format!(
+                    "getDefaultProguardFile({}), {}",
+                    d.quote("proguard-android-optimize.txt"),
+                    d.quote("proguard-rules.pro"),
+                ),
+            ),
+        )
+    } else {
+        String::new()
+    };
+
+    let (plugin, default_config) = match application {
+        true => (
+            "    alias(libs.plugins.android.application)\n",
+            format!(
+                "        {}\n        {}\n        {}\n        {}\n",
+                d.assign("applicationId", &d.property("osiris.android.applicationId")),
+                d.assign("targetSdk", &d.property_int("osiris.android.targetSdk")),
+                d.assign("versionCode", &d.property_int("osiris.android.versionCode")),
+                d.assign("versionName", &d.property("osiris.android.versionName")),
+            ),
+        ),
+        false => (
+            "    alias(libs.plugins.android.library)\n",
+            format!("        {}\n", d.call("consumerProguardFiles", &d.quote("consumer-rules.pro"))),
+        ),
+    };
+
+    let (plugin_kotlin, kotlin_options) = match d {
+        GradleDsl::Groovy => ("", ""),
+        GradleDsl::Kotlin => (
+            "    alias(libs.plugins.kotlin.android)\n",
+            concat!(
+                "\n",
+                "    kotlinOptions {\n",
+                "        jvmTarget = \"1.8\"\n",
+                "    }\n",
+            ),
+        ),
+    };
+
     let content = format!(
         concat!(
             "// Generated by osiris-platform\n",
             "plugins {{\n",
-            "    id 'com.android.application' version '8.0.2'\n",
-            "    id 'org.mozilla.rust-android-gradle.rust-android' version '0.9.3'\n",
+            "{plugin}",
+            "{plugin_kotlin}",
+            "{plugins}",
             "}}\n",
             "\n",
-            "var osirisAndroidAbis = project.property('osiris.android.abis').split(';').collect{{ it as String }}\n",
+            "{properties}",
             "\n",
             "android {{\n",
-            "    compileSdk Integer.parseInt(project.property('osiris.android.compileSdk'))\n",
-            "    namespace project.property('osiris.android.namespace')\n",
+            "    {compile_sdk}\n",
+            "    {namespace}\n",
             "\n",
             "    defaultConfig {{\n",
-            "        applicationId project.property('osiris.android.applicationId')\n",
-            "        minSdk Integer.parseInt(project.property('osiris.android.minSdk'))\n",
-            "        targetSdk Integer.parseInt(project.property('osiris.android.targetSdk'))\n",
-            "        versionCode Integer.parseInt(project.property('osiris.android.versionCode'))\n",
-            "        versionName project.property('osiris.android.versionName')\n",
+            "        {min_sdk}\n",
+            "{default_config}",
             "\n",
-            "        testInstrumentationRunner 'androidx.test.runner.AndroidJUnitRunner'\n",
+            "        {test_runner}\n",
+            "{native_config}",
             "    }}\n",
             "\n",
+            "{signing_configs}",
             "    buildTypes {{\n",
-            "        debug {{\n",
-            "            ndk {{\n",
-            "                abiFilters = osirisAndroidAbis\n",
-            "            }}\n",
+            "        {debug} {{\n",
+            "{abi_filters}",
             "        }}\n",
-            "        release {{\n",
-            "            initWith debug\n",
+            "        {release} {{\n",
+            "            {init_with}\n",
+            "{signing_release}",
+            "{minify}",
             "        }}\n",
             "    }}\n",
             "\n",
             "    compileOptions {{\n",
-            "        sourceCompatibility JavaVersion.VERSION_1_8\n",
-            "        targetCompatibility JavaVersion.VERSION_1_8\n",
-            "    }}\n",
-            "}}\n",
-            "\n",
-            "cargo {{\n",
-            "    apiLevel = Integer.parseInt(project.property('osiris.android.minSdk'))\n",
-            "    extraCargoBuildArguments = ['--lib']\n",
-            "    libname = project.property('osiris.application.packageSymbol')\n",
-            "    module  = project.property('osiris.application.path')\n",
-            "    targetDirectory = project.property('osiris.metadata.targetDirectory')\n",
-            "\n",
-            // Unfortunately, rust-android-gradle only supports a single
-            // build-target. We have to settle on a profile to use, so we pick
-            // the release profile. Preferably, we would create a build-target
-            // per profile, but that is not yet supported.
-            "    profile = 'release'\n",
-            "\n",
-            // Convert the Android ABIs to the strings expected by
-            // rust-android-gradle. For some reason, they do not support the
-            // same namespace.
-            "    targets = osirisAndroidAbis.collect{{\n",
-            "        if (it == 'armeabi-v7a') {{\n",
-            "            'arm'\n",
-            "        }} else if (it == 'arm64-v8a') {{\n",
-            "            'arm64'\n",
-            "        }} else {{\n",
-            "            it\n",
-            "        }}\n",
+            "        {source_compatibility}\n",
+            "        {target_compatibility}\n",
             "    }}\n",
-            "}}\n",
+            "{kotlin_options}",
+            "{abi_splits}",
+            // Additional native libraries and prebuilt Rust libraries are
+            // staged by the build operation and picked up as further
+            // source of `jniLibs`.
             "\n",
-            // Once Gradle parsing is done, ensure that all Android asset
-            // targets pull in the Cargo build.
-            "afterEvaluate {{\n",
-            "    android.applicationVariants.all {{ variant ->\n",
-            "        def flavor = ''\n",
-            "        variant.productFlavors.each {{\n",
-            "            flavor += \"${{it.name.capitalize()}}\"\n",
+            "    sourceSets {{\n",
+            "        {main} {{\n",
+            "            jniLibs.srcDir({jni_libs})\n",
             "        }}\n",
-            "        def type = \"${{variant.buildType.name.capitalize()}}\"\n",
-            "        tasks[\"generate${{flavor}}${{type}}Assets\"].dependsOn(tasks['cargoBuild'])\n",
             "    }}\n",
+            "{native}",
             "}}\n",
             "\n",
+            "{abi_offsets}",
+            "{cargo}",
             "dependencies {{\n",
-            "    implementation 'androidx.appcompat:appcompat:1.6.1'\n",
-            "    implementation 'com.google.android.material:material:1.9.0'\n",
-            "    implementation 'androidx.constraintlayout:constraintlayout:2.1.4'\n",
-            "    testImplementation 'junit:junit:4.13.2'\n",
-            "    androidTestImplementation 'androidx.test.ext:junit:1.1.5'\n",
-            "    androidTestImplementation 'androidx.test.espresso:espresso-core:3.5.1'\n",
+            "{dependencies}",
+            "    {test_junit}\n",
+            "    {android_test_junit}\n",
+            "    {android_test_espresso}\n",
             "}}\n",
         ),
+        plugin = plugin,
+        plugin_kotlin = plugin_kotlin,
+        plugins = plugins,
+        properties = properties,
+        compile_sdk = d.assign("compileSdk", &d.property_int("osiris.android.compileSdk")),
+        namespace = d.assign("namespace", &d.property("osiris.android.namespace")),
+        min_sdk = d.assign("minSdk", &d.property_int("osiris.android.minSdk")),
+        default_config = default_config,
+        test_runner = d.assign("testInstrumentationRunner", &d.quote("androidx.test.runner.AndroidJUnitRunner")),
+        native_config = native_config,
+        signing_configs = signing_configs,
+        debug = d.named("debug"),
+        abi_filters = abi_filters,
+        release = d.named("release"),
+        init_with = d.call("initWith", &d.named("debug")),
+        signing_release = signing_release,
+        minify = minify,
+        source_compatibility = d.assign("sourceCompatibility", "JavaVersion.VERSION_1_8"),
+        target_compatibility = d.assign("targetCompatibility", "JavaVersion.VERSION_1_8"),
+        kotlin_options = kotlin_options,
+        abi_splits = abi_splits,
+        main = d.named("main"),
+        jni_libs = d.property("osiris.android.jniLibs"),
+        native = native,
+        abi_offsets = abi_offsets,
+        cargo = cargo,
+        dependencies = dependencies,
+        test_junit = d.call("testImplementation", "libs.junit"),
+        android_test_junit = d.call("androidTestImplementation", "libs.androidx.test.junit"),
+        android_test_espresso = d.call("androidTestImplementation", "libs.androidx.test.espresso"),
     );
-    path.push("build.gradle");
-    update_file(path.as_path(), content.as_str())?;
+
+    path.push(file);
+    emit_file(state, root, path.as_path(), content.as_str())?;
     path.pop();
     Ok(())
 }
 
-// Emerge Android `AndroidManifest.xml`
+// Emerge Android `proguard-rules.pro`
 //
-// Write the main application manifest according to the Android Application
+// With minification enabled, R8 removes and renames all code it deems
+// unreachable. Code only used via JNI is invisible to it, so keep all native
+// methods (which are bound by name), the main activity, and all classes the
+// Rust library uses via JNI. `GameActivity` calls into its Java side via JNI
+// as well, so it is kept entirely.
+//
+// Without minification, no rules are needed. Rules emerged before are swept
+// with the emerge state, unless they were edited.
+//
+// Libraries ship their rules as `consumer-rules.pro` in the AAR instead,
+// which are applied by the consuming application when it is minified. They
+// keep all native methods, the facade, and all classes the Rust library
+// uses via JNI. The rules file of the other artifact is left to the sweep of
+// the emerge state.
+fn emerge_android_proguard_rules(
+    path: &mut std::path::PathBuf,
+    state: &mut State,
+    root: &std::path::Path,
+    view_android: &crate::manifest::ViewPlatformAndroid,
+) -> Result<(), Error> {
+    let mut classes = String::new();
+    for v in view_android.jni_classes.iter() {
+        classes.push_str(&format!("\n-keep class {} {{ *; }}\n", v));
+    }
+
+    if view_android.artifact == crate::manifest::RawPlatformAndroidArtifact::Library {
+        let facade = if view_android.library_facade {
+            format!("\n-keep class {}.NativeLibrary {{ *; }}\n", view_android.namespace)
+        } else {
+            String::new()
+        };
+
+        let content = format!(
+            concat!(
+                "# Generated by osiris-platform\n",
+                "\n",
+                "-keepclasseswithmembernames,includedescriptorclasses class * {{\n",
+                "    native <methods>;\n",
+                "}}\n",
+                "{0}",
+                "{1}",
+            ),
+            facade,
+            classes,
+        );
+
+        path.push("consumer-rules.pro");
+        emit_file(state, root, path.as_path(), content.as_str())?;
+        path.pop();
+        return Ok(());
+    }
+
+    path.push("proguard-rules.pro");
+
+    if view_android.minify {
+        let activity = match view_android.activity_kind {
+            crate::manifest::RawPlatformAndroidActivityKind::NativeActivity => String::new(),
+            crate::manifest::RawPlatformAndroidActivityKind::GameActivity => format!(
+                concat!(
+                    "\n",
+                    "-keep class {0}.MainActivity {{ *; }}\n",
+                    "-keep class com.google.androidgamesdk.** {{ *; }}\n",
+                ),
+                view_android.namespace,
+            ),
+            crate::manifest::RawPlatformAndroidActivityKind::AppCompat => format!(
+                concat!(
+                    "\n",
+                    "-keep class {0}.MainActivity {{ *; }}\n",
+                ),
+                view_android.namespace,
+            ),
+        };
+
+        let content = format!(
+            concat!(
+                "# Generated by osiris-platform\n",
+                "\n",
+                "-keepclasseswithmembernames,includedescriptorclasses class * {{\n",
+                "    native <methods>;\n",
+                "}}\n",
+                "{0}",
+                "{1}",
+            ),
+            activity,
+            classes,
+        );
+        emit_file(state, root, path.as_path(), content.as_str())?;
+    }
+
+    path.pop();
+    Ok(())
+}
+
+// Emerge Android `CMakeLists.txt`
+//
+// With the CMake integration, the Android Gradle Plugin builds all native code
+// via CMake. We use Corrosion to import the Cargo package into CMake. This
+// way, the Android native tooling (e.g., debugging and per-ABI builds) works
+// as designed. Corrosion derives the Rust target from the Android ABI and the
+// Cargo profile from the CMake build type. The release profile is built with
+// debug information, so the unstripped libraries can be symbolicated.
+//
+// An installed Corrosion package is preferred. If none is found, the
+// Corrosion sources configured via `corrosion-path` are used, or Corrosion is
+// fetched at configuration time. The latter is refused in offline mode, so
+// offline builds fail with a clear message rather than a network error.
+//
+// With rust-android-gradle, no CMake is used. A leftover `CMakeLists.txt` is
+// removed by the sweep of the emerge state, unless it was modified.
+fn emerge_android_cmake_lists(
+    path: &mut std::path::PathBuf,
+    state: &mut State,
+    root: &std::path::Path,
+    integration: crate::manifest::RawPlatformAndroidIntegration,
+    package: &str,
+) -> Result<(), Error> {
+    if integration != crate::manifest::RawPlatformAndroidIntegration::Cmake {
+        return Ok(());
+    }
+
+    let content = format!(
+        concat!(
+            "# Generated by osiris-platform\n",
+            "cmake_minimum_required(VERSION 3.22.1)\n",
+            "project(osiris LANGUAGES C)\n",
+            "\n",
+            "find_package(Corrosion QUIET)\n",
+            "if(NOT Corrosion_FOUND)\n",
+            "    if(OSIRIS_CORROSION_PATH)\n",
+            "        set(FETCHCONTENT_SOURCE_DIR_CORROSION \"${{OSIRIS_CORROSION_PATH}}\")\n",
+            "    elseif(OSIRIS_OFFLINE)\n",
+            "        message(FATAL_ERROR \"Corrosion is not installed and cannot be fetched in offline mode. Set `corrosion-path` in the manifest to a local copy of its sources.\")\n",
+            "    endif()\n",
+            "    include(FetchContent)\n",
+            "    FetchContent_Declare(\n",
+            "        Corrosion\n",
+            "        GIT_REPOSITORY https://github.com/corrosion-rs/corrosion.git\n",
+            "        GIT_TAG v0.4.4\n",
+            "    )\n",
+            "    FetchContent_MakeAvailable(Corrosion)\n",
+            "endif()\n",
+            "\n",
+            "corrosion_import_crate(\n",
+            "    MANIFEST_PATH \"${{OSIRIS_APPLICATION_PATH}}/Cargo.toml\"\n",
+            "    CRATES \"{0}\"\n",
+            "    CRATE_TYPES cdylib\n",
+            "    FLAGS --config profile.release.debug=true\n",
+            ")\n",
+        ),
+        package,
+    );
+    path.push("CMakeLists.txt");
+    emit_file(state, root, path.as_path(), content.as_str())?;
+    path.pop();
+    Ok(())
+}
+
+// Emerge Android `AndroidManifest.xml`
+//
+// Write the main application manifest according to the Android Application
 // documentation. This manifest is the root application configuration and
 // refers to all the embedded resources, including the entry-point activity.
+//
+// `NativeActivity` and `GameActivity` load the Rust library themselves, based
+// on the `android.app.lib_name` meta-data of the activity. For
+// `NativeActivity`, the framework class is used directly, since there is no
+// Java code in the application.
+//
+// If `icon` is set, the launcher icons of `emerge_android_icons()` are
+// referenced as application icon. If `locale_config` is set, the locales
+// configuration of `emerge_android_locales()` is referenced.
+//
+// Libraries have neither an application nor activities. Their manifest is
+// merged into the manifest of the consuming application.
 fn emerge_android_manifest(
     path: &mut std::path::PathBuf,
+    state: &mut State,
+    root: &std::path::Path,
+    view_android: &crate::manifest::ViewPlatformAndroid,
+    package_symbol: &str,
+    icon: bool,
+    locale_config: bool,
 ) -> Result<(), Error> {
+    if view_android.artifact == crate::manifest::RawPlatformAndroidArtifact::Library {
+        let content = concat!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n",
+            "<!-- Generated by osiris-platform -->\n",
+            "<manifest xmlns:android=\"http://schemas.android.com/apk/res/android\" />\n",
+        );
+        path.push("AndroidManifest.xml");
+        emit_file(state, root, path.as_path(), content)?;
+        path.pop();
+        return Ok(());
+    }
+
+    let (activity_name, activity_meta) = match view_android.activity_kind {
+        crate::manifest::RawPlatformAndroidActivityKind::AppCompat => {
+            (".MainActivity", String::new())
+        },
+        crate::manifest::RawPlatformAndroidActivityKind::NativeActivity => {
+            (
+                "android.app.NativeActivity",
+                emerge_android_manifest_lib_name(package_symbol),
+            )
+        },
+        crate::manifest::RawPlatformAndroidActivityKind::GameActivity => {
+            (
+                ".MainActivity",
+                emerge_android_manifest_lib_name(package_symbol),
+            )
+        },
+    };
     let content = format!(
         concat!(
             "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n",
@@ -334,12 +1645,15 @@ fn emerge_android_manifest(
             "\n",
             "    <application\n",
             "        android:allowBackup=\"true\"\n",
+            "{2}",
             "        android:label=\"@string/app_name\"\n",
+            "{3}",
             "        android:supportsRtl=\"true\"\n",
             "        android:theme=\"@style/Theme.Main\">\n",
             "        <activity\n",
-            "            android:name=\".MainActivity\"\n",
+            "            android:name=\"{0}\"\n",
             "            android:exported=\"true\">\n",
+            "{1}",
             "            <intent-filter>\n",
             "                <action android:name=\"android.intent.action.MAIN\" />\n",
             "                <category android:name=\"android.intent.category.LAUNCHER\" />\n",
@@ -348,13 +1662,42 @@ fn emerge_android_manifest(
             "    </application>\n",
             "</manifest>\n",
         ),
+        activity_name,
+        activity_meta,
+        if icon {
+            concat!(
+                "        android:icon=\"@mipmap/ic_launcher\"\n",
+                "        android:roundIcon=\"@mipmap/ic_launcher_round\"\n",
+            )
+        } else {
+            ""
+        },
+        if locale_config {
+            "        android:localeConfig=\"@xml/locales_config\"\n"
+        } else {
+            ""
+        },
     );
     path.push("AndroidManifest.xml");
-    update_file(path.as_path(), content.as_str())?;
+    emit_file(state, root, path.as_path(), content.as_str())?;
     path.pop();
     Ok(())
 }
 
+// Create the `android.app.lib_name` meta-data element of an activity. This
+// names the native library to be loaded by `NativeActivity` and
+// `GameActivity`.
+fn emerge_android_manifest_lib_name(package_symbol: &str) -> String {
+    format!(
+        concat!(
+            "            <meta-data\n",
+            "                android:name=\"android.app.lib_name\"\n",
+            "                android:value=\"{0}\" />\n",
+        ),
+        package_symbol,
+    )
+}
+
 // Emerge Android `activity_main.xml`
 //
 // This is the layout used by the main activity, defining the UI elements and
@@ -362,9 +1705,20 @@ fn emerge_android_manifest(
 // layout.
 //
 // This is a simple full-widget layout with a text-box showing "Hello World!".
+// Only the `AppCompat` stub uses a layout, so nothing is written for the
+// other activity kinds, nor for libraries.
 fn emerge_android_activity_main(
     path: &mut std::path::PathBuf,
+    state: &mut State,
+    root: &std::path::Path,
+    view_android: &crate::manifest::ViewPlatformAndroid,
 ) -> Result<(), Error> {
+    if view_android.artifact != crate::manifest::RawPlatformAndroidArtifact::Application
+        || view_android.activity_kind != crate::manifest::RawPlatformAndroidActivityKind::AppCompat
+    {
+        return Ok(());
+    }
+
     let content = format!(
         concat!(
             "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n",
@@ -389,55 +1743,542 @@ fn emerge_android_activity_main(
         ),
     );
     path.push("activity_main.xml");
-    update_file(path.as_path(), content.as_str())?;
+    emit_file(state, root, path.as_path(), content.as_str())?;
     path.pop();
     Ok(())
 }
 
+// Create the content of a `strings.xml` with the given application name, if
+// any, and string resources.
+fn android_strings_xml<'a>(
+    name: Option<&str>,
+    strings: impl Iterator<Item = (&'a String, &'a String)>,
+) -> String {
+    let mut v = String::new();
+
+    v.push_str("<!-- Generated by osiris-platform -->\n");
+    v.push_str("<resources>\n");
+    if let Some(name) = name {
+        v.push_str(&format!(
+            "    <string name=\"app_name\">{}</string>\n",
+            escape_android_string(name),
+        ));
+    }
+    for (k, s) in strings {
+        v.push_str(&format!(
+            "    <string name=\"{}\">{}</string>\n",
+            k,
+            escape_android_string(s),
+        ));
+    }
+    v.push_str("</resources>\n");
+
+    v
+}
+
+// Android resource qualifier of a language tag
+//
+// Language tags with at most a language and a 2-letter region use the
+// traditional `<language>-r<REGION>` form, which is supported by all
+// versions of Android. All other tags use the BCP 47 form `b+<subtags>`,
+// with `+` as separator.
+fn android_locale_qualifier(tag: &str) -> String {
+    let subtags: Vec<&str> = tag.split('-').collect();
+
+    match subtags.as_slice() {
+        [language] if language.len() <= 3 => {
+            language.to_ascii_lowercase()
+        },
+        [language, region]
+            if language.len() <= 3
+                && region.len() == 2
+                && region.chars().all(|v| v.is_ascii_alphabetic()) =>
+        {
+            format!("{}-r{}", language.to_ascii_lowercase(), region.to_ascii_uppercase())
+        },
+        _ => {
+            format!("b+{}", subtags.join("+"))
+        },
+    }
+}
+
 // Emerge Android `strings.xml`
 //
 // The default string registry of the Android application is usually located in
 // a `strings.xml`. This allows simple localization, as well as decoupling from
-// the code-base.
+// the code-base. It contains the application name as `app_name`, as well as
+// all string resources of the default locale.
+//
+// Libraries use the resources of the consuming application, so nothing is
+// written for them.
 fn emerge_android_strings(
     path: &mut std::path::PathBuf,
+    state: &mut State,
+    root: &std::path::Path,
+    view_android: &crate::manifest::ViewPlatformAndroid,
     name: &str,
+    catalog: &crate::manifest::ViewCatalog,
 ) -> Result<(), Error> {
-    let content = format!(
-        concat!(
-            "<!-- Generated by osiris-platform -->\n",
-            "<resources>\n",
-            "    <string name=\"app_name\">{0}</string>\n",
-            "</resources>\n",
-        ),
-        escape_xml_pcdata(name),
-    );
+    if view_android.artifact != crate::manifest::RawPlatformAndroidArtifact::Application {
+        return Ok(());
+    }
+
+    let content = android_strings_xml(Some(name), catalog.strings.iter());
     path.push("strings.xml");
-    update_file(path.as_path(), content.as_str())?;
+    emit_file(state, root, path.as_path(), content.as_str())?;
     path.pop();
     Ok(())
 }
 
+// Emerge Android localized resources
+//
+// Write the translations of each locale of the catalog into
+// `values-<qualifier>/strings.xml` in the resource directory `path`. Strings
+// without translation fall back to the default `strings.xml`.
+//
+// If `locale_config` is set, `xml/locales_config.xml` lists all supported
+// locales, including the default locale, for the per-app language settings
+// of Android 13. Translations of removed locales, as well as the locale
+// configuration once disabled, are swept as leftovers of the emerge state.
+//
+// Libraries use the resources of the consuming application, so nothing is
+// written for them.
+fn emerge_android_locales(
+    path: &mut std::path::PathBuf,
+    state: &mut State,
+    root: &std::path::Path,
+    view_android: &crate::manifest::ViewPlatformAndroid,
+    catalog: &crate::manifest::ViewCatalog,
+    locale_config: bool,
+) -> Result<(), Error> {
+    let application = view_android.artifact == crate::manifest::RawPlatformAndroidArtifact::Application;
+
+    if application {
+        for (tag, locale) in catalog.locales.iter() {
+            path.push(format!("values-{}", android_locale_qualifier(tag)));
+            {
+                ensure_dir(path.as_path())?;
+                let content = android_strings_xml(locale.name.as_deref(), locale.strings.iter());
+                path.push("strings.xml");
+                emit_file(state, root, path.as_path(), content.as_str())?;
+                path.pop();
+            }
+            path.pop();
+        }
+    }
+
+    if application && locale_config {
+        let mut content = String::new();
+        content.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+        content.push_str("<!-- Generated by osiris-platform -->\n");
+        content.push_str("<locale-config xmlns:android=\"http://schemas.android.com/apk/res/android\">\n");
+        for tag in std::iter::once(&catalog.locale).chain(catalog.locales.keys()) {
+            content.push_str(&format!("    <locale android:name=\"{}\" />\n", tag));
+        }
+        content.push_str("</locale-config>\n");
+
+        path.push("xml");
+        {
+            ensure_dir(path.as_path())?;
+            path.push("locales_config.xml");
+            emit_file(state, root, path.as_path(), content.as_str())?;
+            path.pop();
+        }
+        path.pop();
+    }
+
+    Ok(())
+}
+
+// Create the content of a `themes.xml` defining `Theme.Main` with the given
+// parent theme and items. Each item is given as the API level it requires,
+// its name and its value. Items of API levels above the compile SDK are
+// omitted, since the build tools would not know them.
+fn android_themes_xml(
+    view_android: &crate::manifest::ViewPlatformAndroid,
+    parent: &str,
+    items: &[(u32, &str, &str)],
+) -> String {
+    let mut v = String::new();
+
+    v.push_str("<!-- Generated by osiris-platform -->\n");
+    v.push_str("<resources xmlns:tools=\"http://schemas.android.com/tools\">\n");
+    v.push_str(&format!("    <style name=\"Theme.Main\" parent=\"{}\">\n", parent));
+    for (api, name, value) in items.iter() {
+        if *api > view_android.compile_sdk {
+            continue;
+        }
+        let target = if *api > view_android.min_sdk {
+            format!(" tools:targetApi=\"{}\"", api)
+        } else {
+            String::new()
+        };
+        v.push_str(&format!("        <item name=\"{}\"{}>{}</item>\n", name, target, value));
+    }
+    v.push_str("    </style>\n");
+    v.push_str("</resources>\n");
+
+    v
+}
+
 // Emerge Android `themes.xml`
 //
 // Define the base theme for the application. This is the theme referenced from
-// the application manifest. No custom styles are added, just the default
-// theme is inherited.
+// the application manifest. Without branding, no custom styles are added, just
+// the default theme is inherited.
+//
+// Without the Material dependencies (i.e., for `NativeActivity`), the
+// framework Material theme is inherited instead.
+//
+// With branding, the brand colors are written into `values/colors.xml` and
+// `values-night/colors.xml`, and the theme uses them for its color
+// attributes, the system bars and the splash screen of Android 12. The
+// system bars either use the background color, or are transparent if the
+// application draws edge-to-edge, as requested by the main activity stub.
+// Their icons are dark on light backgrounds, which differ in dark mode, so
+// the theme is repeated in `values-night/themes.xml`. The splash icon is
+// rendered by `emerge_android_splash_icon()`.
+//
+// Libraries have no application to apply a theme to, so nothing is written
+// for them. Themes and colors no longer written, like those of a removed
+// branding, are swept with the emerge state, unless they were edited.
 fn emerge_android_themes(
     path: &mut std::path::PathBuf,
+    state: &mut State,
+    root: &std::path::Path,
+    view_android: &crate::manifest::ViewPlatformAndroid,
+    branding: Option<&crate::manifest::ViewApplicationBranding>,
 ) -> Result<(), Error> {
+    let application = view_android.artifact == crate::manifest::RawPlatformAndroidArtifact::Application;
+
+    let material = view_android.activity_kind != crate::manifest::RawPlatformAndroidActivityKind::NativeActivity;
+    let parent = match material {
+        false => "android:Theme.Material.NoActionBar",
+        true => "Theme.Material3.DayNight.NoActionBar",
+    };
+
+    for (dir, night) in [("values", false), ("values-night", true)] {
+        path.push(dir);
+
+        let branding = branding.filter(|_| application);
+        let (themes, colors) = match branding {
+            None if application && !night => {
+                (Some(android_themes_xml(view_android, parent, &[])), None)
+            },
+            None => (None, None),
+            Some(branding) => {
+                let palette = if night { &branding.dark } else { &branding.light };
+                let light = if crate::util::color_is_light(palette.background) { "true" } else { "false" };
+                // `NativeActivity` has no stub to let the content draw
+                // behind the system bars, so it never draws edge-to-edge.
+                let edge_to_edge = branding.edge_to_edge && material;
+                let (bars, cutout) = if edge_to_edge {
+                    ("@android:color/transparent", "shortEdges")
+                } else {
+                    ("@color/brand_background", "default")
+                };
+                let (primary, accent) = match material {
+                    true => ("colorPrimary", "colorSecondary"),
+                    false => ("android:colorPrimary", "android:colorAccent"),
+                };
+
+                let mut items = vec![
+                    (21, primary, "@color/brand_primary"),
+                    (21, accent, "@color/brand_accent"),
+                    (21, "android:colorBackground", "@color/brand_background"),
+                    (21, "android:windowBackground", "@color/brand_background"),
+                    (21, "android:statusBarColor", bars),
+                    (21, "android:navigationBarColor", bars),
+                    (23, "android:windowLightStatusBar", light),
+                    (27, "android:windowLightNavigationBar", light),
+                    (28, "android:windowLayoutInDisplayCutoutMode", cutout),
+                    (29, "android:enforceNavigationBarContrast", if edge_to_edge { "false" } else { "true" }),
+                    (31, "android:windowSplashScreenBackground", "@color/brand_background"),
+                ];
+                if branding.splash_icon.is_some() {
+                    items.push((31, "android:windowSplashScreenAnimatedIcon", "@drawable/splash_icon"));
+                }
+                if !edge_to_edge {
+                    items.push((35, "android:windowOptOutEdgeToEdgeEnforcement", "true"));
+                }
+
+                let colors = format!(
+                    concat!(
+                        "<!-- Generated by osiris-platform -->\n",
+                        "<resources>\n",
+                        "    <color name=\"brand_primary\">{0}</color>\n",
+                        "    <color name=\"brand_accent\">{1}</color>\n",
+                        "    <color name=\"brand_background\">{2}</color>\n",
+                        "</resources>\n",
+                    ),
+                    crate::util::format_color(palette.primary),
+                    crate::util::format_color(palette.accent),
+                    crate::util::format_color(palette.background),
+                );
+
+                (Some(android_themes_xml(view_android, parent, &items)), Some(colors))
+            },
+        };
+
+        for (file, content) in [("themes.xml", themes), ("colors.xml", colors)] {
+            if let Some(content) = content {
+                ensure_dir(path.as_path())?;
+                path.push(file);
+                emit_file(state, root, path.as_path(), content.as_str())?;
+                path.pop();
+            }
+        }
+
+        path.pop();
+    }
+
+    Ok(())
+}
+
+// Densities of Android launcher icons, with their scale relative to `mdpi`.
+const ANDROID_ICON_DENSITIES: &[(&str, f32)] = &[
+    ("mdpi", 1.0),
+    ("hdpi", 1.5),
+    ("xhdpi", 2.0),
+    ("xxhdpi", 3.0),
+    ("xxxhdpi", 4.0),
+];
+
+// Render an icon image at the given size and encode it as PNG. The image is
+// fitted into `content` pixels and passed through `f` before encoding.
+fn android_icon_png(
+    source: &crate::image::Source,
+    path_source: &std::path::Path,
+    size: u32,
+    content: f32,
+    f: impl FnOnce(tiny_skia::Pixmap) -> Option<tiny_skia::Pixmap>,
+) -> Result<Vec<u8>, Error> {
+    source.render(size, content)
+        .and_then(f)
+        .and_then(|v| crate::image::encode(&v))
+        .ok_or_else(|| Error::Icon(path_source.as_os_str().to_os_string()))
+}
+
+// Emerge Android launcher icons
+//
+// Render the application icon into all launcher icon variants, placed in the
+// resource directory `path`:
+//
+// - `mipmap-<density>/ic_launcher.png`: Legacy icon with the source image on
+//   top of the background color, used before Android 8.
+// - `mipmap-<density>/ic_launcher_round.png`: Same as the legacy icon, but
+//   clipped to a circle.
+// - `mipmap-<density>/ic_launcher_foreground.png`: Foreground layer of the
+//   adaptive icon.
+// - `mipmap-<density>/ic_launcher_monochrome.png`: Monochrome layer of the
+//   adaptive icon, used for themed icons. Requires SDK 33 to compile, so it
+//   is omitted for older compile SDKs.
+// - `mipmap-anydpi-v26/ic_launcher{,_round}.xml`: Adaptive icon combining
+//   the background color with the foreground and monochrome layers.
+// - `values/ic_launcher_background.xml`: Background color of the icon.
+//
+// Adaptive icon layers are 108dp in size, of which the platform masks all
+// but the inner 72dp. The source image is fitted into the inner 66dp, which
+// are guaranteed to be visible with all masks. The legacy icons show the
+// same 72dp viewport at 48dp.
+//
+// Without an icon, or for libraries, nothing is written and the default icon
+// of the platform is used. Icons emerged before are swept with the emerge
+// state, while hand-made icons are retained.
+fn emerge_android_icons(
+    path: &mut std::path::PathBuf,
+    state: &mut State,
+    root: &std::path::Path,
+    manifest: &crate::manifest::Manifest,
+    view_android: &crate::manifest::ViewPlatformAndroid,
+    icon: Option<&crate::manifest::ViewApplicationIcon>,
+) -> Result<(), Error> {
+    let icon = match icon {
+        Some(v) if view_android.artifact == crate::manifest::RawPlatformAndroidArtifact::Application => v,
+        _ => return Ok(()),
+    };
+
+    let path_source = manifest.absolute_path(&icon.path);
+    let source = crate::image::Source::load(&path_source)
+        .ok_or_else(|| Error::Icon(path_source.as_os_str().to_os_string()))?;
+
+    // Themed icons are only available with SDK 33 and later. Without a
+    // dedicated monochrome image, the silhouette of the source is used.
+    let monochrome = view_android.compile_sdk >= 33;
+    let path_monochrome = icon.monochrome
+        .as_ref()
+        .map(|v| manifest.absolute_path(v))
+        .unwrap_or_else(|| path_source.clone());
+    let source_monochrome = match (monochrome, &icon.monochrome) {
+        (true, Some(_)) => Some(
+            crate::image::Source::load(&path_monochrome)
+                .ok_or_else(|| Error::Icon(path_monochrome.as_os_str().to_os_string()))?,
+        ),
+        _ => None,
+    };
+
+    for (density, scale) in ANDROID_ICON_DENSITIES.iter() {
+        let size_layer = (108.0 * scale).round() as u32;
+        let size_legacy = (48.0 * scale).round() as u32;
+        let content_layer = 66.0 * scale;
+        let content_legacy = 44.0 * scale;
+
+        path.push(format!("mipmap-{}", density));
+        ensure_dir(path.as_path())?;
+
+        let content = android_icon_png(
+            &source,
+            &path_source,
+            size_legacy,
+            content_legacy,
+            |v| crate::image::compose(&v, icon.background),
+        )?;
+        path.push("ic_launcher.png");
+        emit_file_bytes(state, root, path.as_path(), &content)?;
+        path.pop();
+
+        let content = android_icon_png(
+            &source,
+            &path_source,
+            size_legacy,
+            content_legacy,
+            |v| {
+                let mut v = crate::image::compose(&v, icon.background)?;
+                crate::image::clip_circle(&mut v)?;
+                Some(v)
+            },
+        )?;
+        path.push("ic_launcher_round.png");
+        emit_file_bytes(state, root, path.as_path(), &content)?;
+        path.pop();
+
+        let content = android_icon_png(
+            &source,
+            &path_source,
+            size_layer,
+            content_layer,
+            Some,
+        )?;
+        path.push("ic_launcher_foreground.png");
+        emit_file_bytes(state, root, path.as_path(), &content)?;
+        path.pop();
+
+        if monochrome {
+            let content = android_icon_png(
+                source_monochrome.as_ref().unwrap_or(&source),
+                &path_monochrome,
+                size_layer,
+                content_layer,
+                |mut v| {
+                    crate::image::monochrome(&mut v);
+                    Some(v)
+                },
+            )?;
+            path.push("ic_launcher_monochrome.png");
+            emit_file_bytes(state, root, path.as_path(), &content)?;
+            path.pop();
+        }
+
+        path.pop();
+    }
+
     let content = format!(
         concat!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n",
             "<!-- Generated by osiris-platform -->\n",
-            "<resources xmlns:tools=\"http://schemas.android.com/tools\">\n",
-            "    <style name=\"Theme.Main\" parent=\"Theme.Material3.DayNight.NoActionBar\">\n",
-            "    </style>\n",
+            "<adaptive-icon xmlns:android=\"http://schemas.android.com/apk/res/android\">\n",
+            "    <background android:drawable=\"@color/ic_launcher_background\" />\n",
+            "    <foreground android:drawable=\"@mipmap/ic_launcher_foreground\" />\n",
+            "{0}",
+            "</adaptive-icon>\n",
+        ),
+        if monochrome {
+            "    <monochrome android:drawable=\"@mipmap/ic_launcher_monochrome\" />\n"
+        } else {
+            ""
+        },
+    );
+    path.push("mipmap-anydpi-v26");
+    {
+        ensure_dir(path.as_path())?;
+        for file in ["ic_launcher.xml", "ic_launcher_round.xml"] {
+            path.push(file);
+            emit_file(state, root, path.as_path(), content.as_str())?;
+            path.pop();
+        }
+    }
+    path.pop();
+
+    let content = format!(
+        concat!(
+            "<!-- Generated by osiris-platform -->\n",
+            "<resources>\n",
+            "    <color name=\"ic_launcher_background\">{0}</color>\n",
             "</resources>\n",
         ),
+        crate::util::format_color(icon.background),
     );
-    path.push("themes.xml");
-    update_file(path.as_path(), content.as_str())?;
+    path.push("values");
+    {
+        ensure_dir(path.as_path())?;
+        path.push("ic_launcher_background.xml");
+        emit_file(state, root, path.as_path(), content.as_str())?;
+        path.pop();
+    }
     path.pop();
+
+    Ok(())
+}
+
+// Emerge Android splash icon
+//
+// Render the splash icon of the branding into
+// `drawable-<density>/splash_icon.png` in the resource directory `path`, as
+// referenced by `emerge_android_themes()`.
+//
+// Splash icons are 288dp in size and masked to a circle of 192dp. Like for
+// adaptive icons, the source image is fitted into the inner 176dp, which
+// keeps the same ratio as the safe zone of launcher icons.
+//
+// Without splash icon, or for libraries, nothing is written and the launcher
+// icon is shown on the splash screen. Splash icons emerged before are swept
+// with the emerge state.
+fn emerge_android_splash_icon(
+    path: &mut std::path::PathBuf,
+    state: &mut State,
+    root: &std::path::Path,
+    manifest: &crate::manifest::Manifest,
+    view_android: &crate::manifest::ViewPlatformAndroid,
+    branding: Option<&crate::manifest::ViewApplicationBranding>,
+) -> Result<(), Error> {
+    let splash_icon = branding
+        .and_then(|v| v.splash_icon.as_ref())
+        .filter(|_| view_android.artifact == crate::manifest::RawPlatformAndroidArtifact::Application);
+
+    let Some(splash_icon) = splash_icon else {
+        return Ok(());
+    };
+
+    let path_source = manifest.absolute_path(splash_icon);
+    let source = crate::image::Source::load(&path_source)
+        .ok_or_else(|| Error::Icon(path_source.as_os_str().to_os_string()))?;
+
+    for (density, scale) in ANDROID_ICON_DENSITIES.iter() {
+        path.push(format!("drawable-{}", density));
+        ensure_dir(path.as_path())?;
+        let content = android_icon_png(
+            &source,
+            &path_source,
+            (288.0 * scale).round() as u32,
+            176.0 * scale,
+            Some,
+        )?;
+        path.push("splash_icon.png");
+        emit_file_bytes(state, root, path.as_path(), &content)?;
+        path.pop();
+        path.pop();
+    }
+
     Ok(())
 }
 
@@ -446,45 +2287,677 @@ fn emerge_android_themes(
 // Write the main activity code, which is the entrypoint into the application.
 // It sets `activity_main` as the content-view and recreates the base class
 // from the saved state, if any.
+//
+// For `GameActivity`, the stub merely derives from `GameActivity`, which loads
+// the Rust library and manages the content-view itself. For `NativeActivity`,
+// no Java code is used, so no stub is written.
+//
+// Additional native libraries are loaded before the Rust library, in the
+// order of `android_preload_libraries()`.
+// `NativeActivity` has no stub to load them, so it relies on the dynamic
+// linker to find them.
+//
+// With the Kotlin language selected, `MainActivity.kt` is written instead,
+// with the same semantics. The stub of the other language is left to the
+// sweep of the emerge state.
+//
+// If `edge_to_edge` is set, the activity lets its content draw behind the
+// system bars, which the theme makes transparent.
+//
+// Libraries have no activity, so no stub is written for them.
 fn emerge_android_main_activity(
     path: &mut std::path::PathBuf,
-    namespace: &str,
+    state: &mut State,
+    root: &std::path::Path,
+    view_android: &crate::manifest::ViewPlatformAndroid,
     package_symbol: &str,
+    edge_to_edge: bool,
 ) -> Result<(), Error> {
-    let content = format!(
+    let language = view_android.language;
+    let activity_kind = view_android.activity_kind;
+    let namespace = view_android.namespace.as_str();
+    let preload = android_preload_libraries(view_android);
+
+    let (mut load_java, mut load_kotlin) = (String::new(), String::new());
+    for v in preload.iter().map(|v| v.as_str()).chain([package_symbol]) {
+        load_java.push_str(&format!("        System.loadLibrary(\"{}\");\n", v));
+        load_kotlin.push_str(&format!("            System.loadLibrary(\"{}\")\n", v));
+    }
+
+    // Drawing behind the system bars must be requested from the window, the
+    // theme merely makes the bars transparent.
+    let (edge_java, edge_kotlin) = match edge_to_edge {
+        true => (
+            "        WindowCompat.setDecorFitsSystemWindows(getWindow(), false);\n",
+            "        WindowCompat.setDecorFitsSystemWindows(window, false)\n",
+        ),
+        false => ("", ""),
+    };
+    let (import_java, import_kotlin) = match edge_to_edge {
+        true => (
+            "import androidx.core.view.WindowCompat;\n",
+            "import androidx.core.view.WindowCompat\n",
+        ),
+        false => ("", ""),
+    };
+
+    let content = match (language, activity_kind) {
+        _ if view_android.artifact != crate::manifest::RawPlatformAndroidArtifact::Application => {
+            None
+        },
+        (
+            crate::manifest::RawPlatformAndroidLanguage::Java,
+            crate::manifest::RawPlatformAndroidActivityKind::AppCompat,
+        ) => {
+            Some(format!(
+                concat!(
+                    "// Generated by osiris-platform\n",
+                    "package {0};\n",
+                    "\n",
+                    "import androidx.appcompat.app.AppCompatActivity;\n",
+                    "{2}",
+                    "\n",
+                    "import android.os.Bundle;\n",
+                    "\n",
+                    "public class MainActivity extends AppCompatActivity {{\n",
+                    "    static {{\n",
+                    "{1}",
+                    "    }}\n",
+                    "\n",
+                    "    @Override\n",
+                    "    protected void onCreate(Bundle savedInstanceState) {{\n",
+                    "        super.onCreate(savedInstanceState);\n",
+                    "{3}",
+                    "        setContentView(R.layout.activity_main);\n",
+                    "    }}\n",
+                    "}}\n",
+                ),
+                namespace,
+                load_java,
+                import_java,
+                edge_java,
+            ))
+        },
+        (
+            crate::manifest::RawPlatformAndroidLanguage::Java,
+            crate::manifest::RawPlatformAndroidActivityKind::GameActivity,
+        ) => {
+            let mut members = Vec::new();
+            if !preload.is_empty() {
+                members.push(format!("    static {{\n{}    }}\n", load_java));
+            }
+            if edge_to_edge {
+                members.push(format!(
+                    concat!(
+                        "    @Override\n",
+                        "    protected void onCreate(Bundle savedInstanceState) {{\n",
+                        "        super.onCreate(savedInstanceState);\n",
+                        "{0}",
+                        "    }}\n",
+                    ),
+                    edge_java,
+                ));
+            }
+
+            Some(format!(
+                concat!(
+                    "// Generated by osiris-platform\n",
+                    "package {0};\n",
+                    "\n",
+                    "import com.google.androidgamesdk.GameActivity;\n",
+                    "{2}",
+                    "\n",
+                    "public class MainActivity extends GameActivity {{\n",
+                    "{1}",
+                    "}}\n",
+                ),
+                namespace,
+                members.join("\n"),
+                if edge_to_edge {
+                    format!("{}\nimport android.os.Bundle;\n", import_java)
+                } else {
+                    String::new()
+                },
+            ))
+        },
+        (
+            crate::manifest::RawPlatformAndroidLanguage::Kotlin,
+            crate::manifest::RawPlatformAndroidActivityKind::AppCompat,
+        ) => {
+            Some(format!(
+                concat!(
+                    "// Generated by osiris-platform\n",
+                    "package {0}\n",
+                    "\n",
+                    "import androidx.appcompat.app.AppCompatActivity\n",
+                    "{2}",
+                    "\n",
+                    "import android.os.Bundle\n",
+                    "\n",
+                    "class MainActivity : AppCompatActivity() {{\n",
+                    "    companion object {{\n",
+                    "        init {{\n",
+                    "{1}",
+                    "        }}\n",
+                    "    }}\n",
+                    "\n",
+                    "    override fun onCreate(savedInstanceState: Bundle?) {{\n",
+                    "        super.onCreate(savedInstanceState)\n",
+                    "{3}",
+                    "        setContentView(R.layout.activity_main)\n",
+                    "    }}\n",
+                    "}}\n",
+                ),
+                namespace,
+                load_kotlin,
+                import_kotlin,
+                edge_kotlin,
+            ))
+        },
+        (
+            crate::manifest::RawPlatformAndroidLanguage::Kotlin,
+            crate::manifest::RawPlatformAndroidActivityKind::GameActivity,
+        ) => {
+            let mut members = Vec::new();
+            if !preload.is_empty() {
+                members.push(format!(
+                    "    companion object {{\n        init {{\n{}        }}\n    }}\n",
+                    load_kotlin,
+                ));
+            }
+            if edge_to_edge {
+                members.push(format!(
+                    concat!(
+                        "    override fun onCreate(savedInstanceState: Bundle?) {{\n",
+                        "        super.onCreate(savedInstanceState)\n",
+                        "{0}",
+                        "    }}\n",
+                    ),
+                    edge_kotlin,
+                ));
+            }
+
+            Some(format!(
+                concat!(
+                    "// Generated by osiris-platform\n",
+                    "package {0}\n",
+                    "\n",
+                    "import com.google.androidgamesdk.GameActivity\n",
+                    "{2}",
+                    "\n",
+                    "class MainActivity : GameActivity(){1}\n",
+                ),
+                namespace,
+                if members.is_empty() {
+                    String::new()
+                } else {
+                    format!(" {{\n{}}}", members.join("\n"))
+                },
+                if edge_to_edge {
+                    format!("{}\nimport android.os.Bundle\n", import_kotlin)
+                } else {
+                    String::new()
+                },
+            ))
+        },
+        (_, crate::manifest::RawPlatformAndroidActivityKind::NativeActivity) => {
+            None
+        },
+    };
+
+    let file = match language {
+        crate::manifest::RawPlatformAndroidLanguage::Java => "MainActivity.java",
+        crate::manifest::RawPlatformAndroidLanguage::Kotlin => "MainActivity.kt",
+    };
+
+    if let Some(content) = content {
+        path.push(file);
+        emit_file(state, root, path.as_path(), content.as_str())?;
+        path.pop();
+    }
+
+    Ok(())
+}
+
+// Emerge Android `NativeLibrary.java`
+//
+// Write the facade of a library, which loads the Rust library, as well as
+// all libraries it depends on in the order of `android_preload_libraries()`.
+// Loading happens once the class is initialized, so consumers merely call
+// `NativeLibrary.load()` before using any native methods.
+//
+// With the Kotlin language selected, `NativeLibrary.kt` is written instead,
+// using a Kotlin object. Facades that are no longer written, like the one of
+// the other language, are swept with the emerge state.
+fn emerge_android_native_library(
+    path: &mut std::path::PathBuf,
+    state: &mut State,
+    root: &std::path::Path,
+    view_android: &crate::manifest::ViewPlatformAndroid,
+    package_symbol: &str,
+) -> Result<(), Error> {
+    let preload = android_preload_libraries(view_android);
+    let libraries = preload.iter().map(|v| v.as_str()).chain([package_symbol]);
+
+    let (file, content) = match view_android.language {
+        crate::manifest::RawPlatformAndroidLanguage::Java => {
+            let mut load = String::new();
+            for v in libraries {
+                load.push_str(&format!("        System.loadLibrary(\"{}\");\n", v));
+            }
+
+            (
+                "NativeLibrary.java",
+                format!(
+                    concat!(
+                        "// Generated by osiris-platform\n",
+                        "package {0};\n",
+                        "\n",
+                        "public final class NativeLibrary {{\n",
+                        "    static {{\n",
+                        "{1}",
+                        "    }}\n",
+                        "\n",
+                        "    private NativeLibrary() {{\n",
+                        "    }}\n",
+                        "\n",
+                        "    public static void load() {{\n",
+                        "    }}\n",
+                        "}}\n",
+                    ),
+                    view_android.namespace,
+                    load,
+                ),
+            )
+        },
+        crate::manifest::RawPlatformAndroidLanguage::Kotlin => {
+            let mut load = String::new();
+            for v in libraries {
+                load.push_str(&format!("        System.loadLibrary(\"{}\")\n", v));
+            }
+
+            (
+                "NativeLibrary.kt",
+                format!(
+                    concat!(
+                        "// Generated by osiris-platform\n",
+                        "package {0}\n",
+                        "\n",
+                        "object NativeLibrary {{\n",
+                        "    init {{\n",
+                        "{1}",
+                        "    }}\n",
+                        "\n",
+                        "    @JvmStatic\n",
+                        "    fun load() {{\n",
+                        "    }}\n",
+                        "}}\n",
+                    ),
+                    view_android.namespace,
+                    load,
+                ),
+            )
+        },
+    };
+
+    let enabled = view_android.artifact == crate::manifest::RawPlatformAndroidArtifact::Library
+        && view_android.library_facade;
+
+    if enabled {
+        path.push(file);
+        emit_file(state, root, path.as_path(), content.as_str())?;
+        path.pop();
+    }
+
+    Ok(())
+}
+
+// Android bridge types
+//
+// Return the Java, Kotlin and Rust types of a bridge type, as well as the
+// Rust type used for it in JNI signatures. The Kotlin type of `void` is
+// empty, since the return type is omitted instead.
+fn android_bridge_types(
+    v: crate::manifest::RawPlatformAndroidBridgeType,
+) -> (&'static str, &'static str, &'static str, &'static str) {
+    match v {
+        crate::manifest::RawPlatformAndroidBridgeType::Boolean => ("boolean", "Boolean", "bool", "u8"),
+        crate::manifest::RawPlatformAndroidBridgeType::Byte => ("byte", "Byte", "i8", "i8"),
+        crate::manifest::RawPlatformAndroidBridgeType::Char => ("char", "Char", "u16", "u16"),
+        crate::manifest::RawPlatformAndroidBridgeType::Short => ("short", "Short", "i16", "i16"),
+        crate::manifest::RawPlatformAndroidBridgeType::Int => ("int", "Int", "i32", "i32"),
+        crate::manifest::RawPlatformAndroidBridgeType::Long => ("long", "Long", "i64", "i64"),
+        crate::manifest::RawPlatformAndroidBridgeType::Float => ("float", "Float", "f32", "f32"),
+        crate::manifest::RawPlatformAndroidBridgeType::Double => ("double", "Double", "f64", "f64"),
+        crate::manifest::RawPlatformAndroidBridgeType::String => ("String", "String", "String", "Object"),
+        crate::manifest::RawPlatformAndroidBridgeType::ByteArray => ("byte[]", "ByteArray", "Vec<u8>", "Object"),
+        crate::manifest::RawPlatformAndroidBridgeType::Void => ("void", "", "()", "()"),
+    }
+}
+
+// Create the Rust side of an Android bridge
+//
+// Every native method of the bridge class gets an `extern "system"` stub
+// exported under its mangled JNI name. The stub converts the arguments to
+// Rust types, calls the function of the same name in the implementing
+// module, and converts the return value back. Hence, the Rust compiler
+// verifies that the implementation matches the signatures described in the
+// manifest.
+//
+// Strings and byte arrays are converted via the JNI function table, so no
+// JNI crate is required. The conversion helpers are only emitted if used.
+fn android_bridge_rust(bridge: &crate::manifest::ViewPlatformAndroidBridge) -> String {
+    use crate::manifest::RawPlatformAndroidBridgeType as T;
+
+    let uses = |t: T| bridge.methods.values().any(|(args, ret)| *ret == t || args.contains(&t));
+    let (strings, bytes) = (uses(T::String), uses(T::ByteArray));
+
+    let mut content = format!(
         concat!(
             "// Generated by osiris-platform\n",
-            "package {0};\n",
-            "\n",
-            "import androidx.appcompat.app.AppCompatActivity;\n",
+            "//\n",
+            "// JNI bridge of `{0}`.\n",
+            "//\n",
+            "// Every native method forwards to the function of the same name in\n",
+            "// `{1}`. Declare this file as module of the crate, and update the\n",
+            "// bridge description in the manifest rather than this file.\n",
             "\n",
-            "import android.os.Bundle;\n",
+            "#![allow(non_snake_case)]\n",
             "\n",
-            "public class MainActivity extends AppCompatActivity {{\n",
-            "    static {{\n",
-            "        System.loadLibrary(\"{1}\");\n",
-            "    }}\n",
-            "\n",
-            "    @Override\n",
-            "    protected void onCreate(Bundle savedInstanceState) {{\n",
-            "        super.onCreate(savedInstanceState);\n",
-            "        setContentView(R.layout.activity_main);\n",
-            "    }}\n",
-            "}}\n",
+            "type Env = *mut *const *const std::ffi::c_void;\n",
+            "type Object = *mut std::ffi::c_void;\n",
         ),
-        namespace,
-        package_symbol,
+        bridge.class,
+        bridge.rust_module,
     );
-    path.push("MainActivity.java");
-    update_file(path.as_path(), content.as_str())?;
-    path.pop();
+
+    if strings || bytes {
+        content.push_str(concat!(
+            "\n",
+            "// Look up the function at the given index of the JNI function table.\n",
+            "unsafe fn env_fn(env: Env, index: usize) -> *const std::ffi::c_void {\n",
+            "    unsafe { *(*env).add(index) }\n",
+            "}\n",
+        ));
+    }
+    if strings {
+        content.push_str(concat!(
+            "\n",
+            "// Copy a Java string into a Rust string.\n",
+            "unsafe fn string_from_java(env: Env, v: Object) -> String {\n",
+            "    unsafe {\n",
+            "        let length: extern \"system\" fn(Env, Object) -> i32 =\n",
+            "            std::mem::transmute(env_fn(env, 164));\n",
+            "        let region: extern \"system\" fn(Env, Object, i32, i32, *mut u16) =\n",
+            "            std::mem::transmute(env_fn(env, 220));\n",
+            "        let n = length(env, v);\n",
+            "        let mut buf = vec![0u16; n as usize];\n",
+            "        region(env, v, 0, n, buf.as_mut_ptr());\n",
+            "        String::from_utf16_lossy(&buf)\n",
+            "    }\n",
+            "}\n",
+            "\n",
+            "// Copy a Rust string into a new Java string.\n",
+            "unsafe fn string_to_java(env: Env, v: String) -> Object {\n",
+            "    unsafe {\n",
+            "        let new: extern \"system\" fn(Env, *const u16, i32) -> Object =\n",
+            "            std::mem::transmute(env_fn(env, 163));\n",
+            "        let buf: Vec<u16> = v.encode_utf16().collect();\n",
+            "        new(env, buf.as_ptr(), buf.len() as i32)\n",
+            "    }\n",
+            "}\n",
+        ));
+    }
+    if bytes {
+        content.push_str(concat!(
+            "\n",
+            "// Copy a Java byte array into a Rust vector.\n",
+            "unsafe fn bytes_from_java(env: Env, v: Object) -> Vec<u8> {\n",
+            "    unsafe {\n",
+            "        let length: extern \"system\" fn(Env, Object) -> i32 =\n",
+            "            std::mem::transmute(env_fn(env, 171));\n",
+            "        let region: extern \"system\" fn(Env, Object, i32, i32, *mut i8) =\n",
+            "            std::mem::transmute(env_fn(env, 200));\n",
+            "        let n = length(env, v);\n",
+            "        let mut buf = vec![0u8; n as usize];\n",
+            "        region(env, v, 0, n, buf.as_mut_ptr().cast());\n",
+            "        buf\n",
+            "    }\n",
+            "}\n",
+            "\n",
+            "// Copy a Rust vector into a new Java byte array.\n",
+            "unsafe fn bytes_to_java(env: Env, v: Vec<u8>) -> Object {\n",
+            "    unsafe {\n",
+            "        let new: extern \"system\" fn(Env, i32) -> Object =\n",
+            "            std::mem::transmute(env_fn(env, 176));\n",
+            "        let region: extern \"system\" fn(Env, Object, i32, i32, *const i8) =\n",
+            "            std::mem::transmute(env_fn(env, 208));\n",
+            "        let array = new(env, v.len() as i32);\n",
+            "        region(env, array, 0, v.len() as i32, v.as_ptr().cast());\n",
+            "        array\n",
+            "    }\n",
+            "}\n",
+        ));
+    }
+
+    let class = crate::util::jni_mangle(&bridge.class);
+
+    for (name, (args, ret)) in bridge.methods.iter() {
+        let mut params = String::new();
+        let mut body = String::new();
+        let mut call = Vec::new();
+        let mut env = false;
+
+        for (i, t) in args.iter().enumerate() {
+            params.push_str(&format!("    a{}: {},\n", i, android_bridge_types(*t).3));
+            match t {
+                T::Boolean => call.push(format!("a{} != 0", i)),
+                T::String | T::ByteArray => {
+                    let f = if *t == T::String { "string_from_java" } else { "bytes_from_java" };
+                    body.push_str(&format!("    let a{0} = unsafe {{ {1}(env, a{0}) }};\n", i, f));
+                    call.push(format!("a{}", i));
+                    env = true;
+                },
+                _ => call.push(format!("a{}", i)),
+            }
+        }
+
+        let call = format!("{}::{}({})", bridge.rust_module, name, call.join(", "));
+        match ret {
+            T::Void => body.push_str(&format!("    {};\n", call)),
+            T::Boolean => body.push_str(&format!("    {} as u8\n", call)),
+            T::String | T::ByteArray => {
+                let f = if *ret == T::String { "string_to_java" } else { "bytes_to_java" };
+                body.push_str(&format!("    let v = {};\n    unsafe {{ {}(env, v) }}\n", call, f));
+                env = true;
+            },
+            _ => body.push_str(&format!("    {}\n", call)),
+        }
+
+        content.push_str(&format!(
+            concat!(
+                "\n",
+                "#[no_mangle]\n",
+                "pub extern \"system\" fn Java_{0}_{1}(\n",
+                "    {2}: Env,\n",
+                "    _class: Object,\n",
+                "{3}",
+                "){4} {{\n",
+                "{5}",
+                "}}\n",
+            ),
+            class,
+            crate::util::jni_mangle(name),
+            if env { "env" } else { "_env" },
+            params,
+            match ret {
+                T::Void => String::new(),
+                v => format!(" -> {}", android_bridge_types(*v).3),
+            },
+            body,
+        ));
+    }
+
+    content
+}
+
+// Emerge Android bridge class
+//
+// Write the Java class declaring the native methods of the bridge as static
+// methods, at the location given by its fully qualified name below the
+// given source directory. The class loads the Rust library, as well as all
+// libraries it depends on, so it can be used on its own.
+//
+// With the Kotlin language selected, a Kotlin object with `@JvmStatic`
+// external functions is written instead, which yields the same JNI symbols.
+// The class of the other language is left to the sweep of the emerge state.
+fn emerge_android_bridge(
+    path: &std::path::Path,
+    state: &mut State,
+    root: &std::path::Path,
+    view_android: &crate::manifest::ViewPlatformAndroid,
+    bridge: &crate::manifest::ViewPlatformAndroidBridge,
+    package_symbol: &str,
+) -> Result<(), Error> {
+    let (package, class) = bridge.class.rsplit_once('.').unwrap_or(("", &bridge.class));
+    let preload = android_preload_libraries(view_android);
+    let libraries = preload.iter().map(|v| v.as_str()).chain([package_symbol]);
+
+    let (file, content) = match view_android.language {
+        crate::manifest::RawPlatformAndroidLanguage::Java => {
+            let mut load = String::new();
+            for v in libraries {
+                load.push_str(&format!("        System.loadLibrary(\"{}\");\n", v));
+            }
+
+            let mut methods = String::new();
+            for (name, (args, ret)) in bridge.methods.iter() {
+                let args = args.iter()
+                    .enumerate()
+                    .map(|(i, v)| format!("{} a{}", android_bridge_types(*v).0, i))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                methods.push_str(&format!(
+                    "\n    public static native {} {}({});\n",
+                    android_bridge_types(*ret).0,
+                    name,
+                    args,
+                ));
+            }
+
+            (
+                format!("{}.java", class),
+                format!(
+                    concat!(
+                        "// Generated by osiris-platform\n",
+                        "package {0};\n",
+                        "\n",
+                        "public final class {1} {{\n",
+                        "    static {{\n",
+                        "{2}",
+                        "    }}\n",
+                        "\n",
+                        "    private {1}() {{\n",
+                        "    }}\n",
+                        "{3}",
+                        "}}\n",
+                    ),
+                    package,
+                    class,
+                    load,
+                    methods,
+                ),
+            )
+        },
+        crate::manifest::RawPlatformAndroidLanguage::Kotlin => {
+            let mut load = String::new();
+            for v in libraries {
+                load.push_str(&format!("        System.loadLibrary(\"{}\")\n", v));
+            }
+
+            let mut methods = String::new();
+            for (name, (args, ret)) in bridge.methods.iter() {
+                let args = args.iter()
+                    .enumerate()
+                    .map(|(i, v)| format!("a{}: {}", i, android_bridge_types(*v).1))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let ret = match android_bridge_types(*ret).1 {
+                    "" => String::new(),
+                    v => format!(": {}", v),
+                };
+                methods.push_str(&format!(
+                    "\n    @JvmStatic\n    external fun {}({}){}\n",
+                    name,
+                    args,
+                    ret,
+                ));
+            }
+
+            (
+                format!("{}.kt", class),
+                format!(
+                    concat!(
+                        "// Generated by osiris-platform\n",
+                        "package {0}\n",
+                        "\n",
+                        "object {1} {{\n",
+                        "    init {{\n",
+                        "{2}",
+                        "    }}\n",
+                        "{3}",
+                        "}}\n",
+                    ),
+                    package,
+                    class,
+                    load,
+                    methods,
+                ),
+            )
+        },
+    };
+
+    let mut ns_path = path.join(package.replace('.', "/"));
+    ensure_dir(ns_path.as_path())?;
+    ns_path.push(&file);
+    emit_file(state, root, ns_path.as_path(), content.as_str())?;
+
     Ok(())
 }
 
+// Emerge Rust bridge file
+//
+// Write the Rust side of the bridge (see `android_bridge_rust()`) into the
+// application sources. The file is not part of the platform integration, so
+// it is not recorded in the emerge state. Files not generated by us are
+// never overwritten, since they likely contain code of the application.
+fn emerge_android_bridge_rust(
+    manifest: &crate::manifest::Manifest,
+    bridge: &crate::manifest::ViewPlatformAndroidBridge,
+) -> Result<(), Error> {
+    let path = manifest.absolute_path(&bridge.rust_file);
+
+    match std::fs::read_to_string(&path) {
+        Ok(v) if !v.starts_with("// Generated by osiris-platform\n") => {
+            return Err(Error::BridgeFile(path.into_os_string()));
+        },
+        Err(v) if v.kind() != std::io::ErrorKind::NotFound => {
+            return Err(Error::FileUpdate(path.into_os_string(), v));
+        },
+        _ => {},
+    }
+
+    if let Some(v) = path.parent() {
+        ensure_dir(v)?;
+    }
+    update_file(&path, &android_bridge_rust(bridge))
+}
+
 // Android-specific backend to `emerge()`.
 fn emerge_android(
     manifest: &crate::manifest::Manifest,
-    _platform: &crate::manifest::RawPlatform,
+    metadata: Option<&crate::cargo::Metadata>,
+    platform: &crate::manifest::RawPlatform,
     android: &crate::manifest::RawPlatformAndroid,
     mut path: std::path::PathBuf,
 ) -> Result<(), Error> {
@@ -492,13 +2965,80 @@ fn emerge_android(
         .map_err(Error::from_manifest_error_view)?;
     let view_android = android.view(&manifest.raw)
         .map_err(Error::from_manifest_error_view)?;
+    let catalog = manifest.catalog()
+        .map_err(Error::Catalog)?;
+
+    // The locales configuration requires SDK 33 to compile, so it is
+    // omitted for older compile SDKs.
+    let locale_config = !catalog.locales.is_empty() && view_android.compile_sdk >= 33;
 
-    // Create the persistent files.
+    // Create the persistent files. Record all of them in the emerge state,
+    // so modifications can be detected later on. Unmodified files of the
+    // previous emerge state that are no longer created are swept.
 
-    emerge_android_gradle_properties(&mut path)?;
+    let root = path.clone();
+    let mut state = State::default();
+    let previous = std::fs::read_to_string(root.join(State::FILE))
+        .map(|v| State::parse(&v))
+        .unwrap_or_default();
+
+    if let Some(metadata) = metadata {
+        let version = android_version(manifest, metadata, &view_application, &view_android)
+            .map_err(Error::Version)?;
+        emerge_android_osiris_properties(
+            path.as_path(),
+            &android_properties(manifest, metadata, platform, &view_application, &view_android, &version),
+        )?;
+    }
+
+    emerge_android_gitignore(&mut path, &mut state, &root)?;
+    emerge_android_gradle_properties(&mut path, &mut state, &root)?;
     emerge_android_local_properties(&mut path)?;
-    emerge_android_settings_gradle(&mut path)?;
-    emerge_android_build_gradle(&mut path)?;
+    // Maven repositories are either URLs, or local paths relative to the
+    // manifest. The latter are turned into absolute paths, since the
+    // platform integration might be emerged elsewhere.
+    let maven_repositories: Option<Vec<String>> = view_android.maven_repositories
+        .as_ref()
+        .map(|list| {
+            list.iter().map(|v| {
+                if v.contains("://") {
+                    v.clone()
+                } else {
+                    manifest.absolute_path(v).to_string_lossy().into_owned()
+                }
+            }).collect()
+        });
+
+    emerge_android_settings_gradle(
+        &mut path,
+        &mut state,
+        &root,
+        view_android.language,
+        maven_repositories.as_deref(),
+    )?;
+    emerge_android_build_gradle(&mut path, &mut state, &root, &view_android)?;
+    emerge_android_proguard_rules(&mut path, &mut state, &root, &view_android)?;
+
+    path.push("gradle");
+    {
+        ensure_dir(path.as_path())?;
+        emerge_android_version_catalog(&mut path, &mut state, &root, &view_android.versions)?;
+
+        path.push("wrapper");
+        {
+            if view_android.gradle_version.is_some() {
+                ensure_dir(path.as_path())?;
+            }
+            emerge_android_gradle_wrapper_properties(
+                &mut path,
+                &mut state,
+                &root,
+                view_android.gradle_version.as_deref(),
+            )?;
+        }
+        path.pop();
+    }
+    path.pop();
 
     path.push("src");
     {
@@ -507,7 +3047,30 @@ fn emerge_android(
         path.push("main");
         {
             ensure_dir(path.as_path())?;
-            emerge_android_manifest(&mut path)?;
+            emerge_android_manifest(
+                &mut path,
+                &mut state,
+                &root,
+                &view_android,
+                &view_application.package_symbol,
+                view_application.icon.is_some(),
+                locale_config,
+            )?;
+
+            path.push("native");
+            {
+                if view_android.integration == crate::manifest::RawPlatformAndroidIntegration::Cmake {
+                    ensure_dir(path.as_path())?;
+                }
+                emerge_android_cmake_lists(
+                    &mut path,
+                    &mut state,
+                    &root,
+                    view_android.integration,
+                    &view_application.package,
+                )?;
+            }
+            path.pop();
 
             path.push("res");
             {
@@ -516,17 +3079,61 @@ fn emerge_android(
                 path.push("layout");
                 {
                     ensure_dir(path.as_path())?;
-                    emerge_android_activity_main(&mut path)?;
+                    emerge_android_activity_main(
+                        &mut path,
+                        &mut state,
+                        &root,
+                        &view_android,
+                    )?;
                 }
                 path.pop();
 
                 path.push("values");
                 {
                     ensure_dir(path.as_path())?;
-                    emerge_android_strings(&mut path, &view_application.name)?;
-                    emerge_android_themes(&mut path)?;
+                    emerge_android_strings(
+                        &mut path,
+                        &mut state,
+                        &root,
+                        &view_android,
+                        &view_application.name,
+                        &catalog,
+                    )?;
                 }
                 path.pop();
+
+                emerge_android_themes(
+                    &mut path,
+                    &mut state,
+                    &root,
+                    &view_android,
+                    view_application.branding.as_ref(),
+                )?;
+
+                emerge_android_icons(
+                    &mut path,
+                    &mut state,
+                    &root,
+                    manifest,
+                    &view_android,
+                    view_application.icon.as_ref(),
+                )?;
+                emerge_android_splash_icon(
+                    &mut path,
+                    &mut state,
+                    &root,
+                    manifest,
+                    &view_android,
+                    view_application.branding.as_ref(),
+                )?;
+                emerge_android_locales(
+                    &mut path,
+                    &mut state,
+                    &root,
+                    &view_android,
+                    &catalog,
+                    locale_config,
+                )?;
             }
             path.pop();
 
@@ -539,9 +3146,30 @@ fn emerge_android(
                 ensure_dir(ns_path.as_path())?;
                 emerge_android_main_activity(
                     &mut ns_path,
-                    &view_android.namespace,
+                    &mut state,
+                    &root,
+                    &view_android,
                     &view_application.package_symbol,
+                    view_application.branding.as_ref().map(|v| v.edge_to_edge).unwrap_or(false),
                 )?;
+                emerge_android_native_library(
+                    &mut ns_path,
+                    &mut state,
+                    &root,
+                    &view_android,
+                    &view_application.package_symbol,
+                )?;
+
+                if let Some(bridge) = view_android.bridge.as_ref() {
+                    emerge_android_bridge(
+                        &path,
+                        &mut state,
+                        &root,
+                        &view_android,
+                        bridge,
+                        &view_application.package_symbol,
+                    )?;
+                }
             }
             path.pop();
         }
@@ -549,6 +3177,16 @@ fn emerge_android(
     }
     path.pop();
 
+    sweep_files(&previous, &state, &root)?;
+
+    path.push(State::FILE);
+    update_file(path.as_path(), state.format().as_str())?;
+    path.pop();
+
+    if let Some(bridge) = view_android.bridge.as_ref() {
+        emerge_android_bridge_rust(manifest, bridge)?;
+    }
+
     Ok(())
 }
 
@@ -564,8 +3202,13 @@ fn emerge_android(
 /// platform already exists, unless `update` is `true`. In this case old files
 /// are updated to match the new platform integration, and old leftovers are
 /// deleted.
+///
+/// If cargo metadata is provided, the resolved platform properties are
+/// recorded in the platform integration as well, allowing IDEs to open it
+/// directly. The build operation refreshes them on every build.
 pub fn emerge(
     manifest: &crate::manifest::Manifest,
+    metadata: Option<&crate::cargo::Metadata>,
     platform: &crate::manifest::RawPlatform,
     path_override: Option<&std::path::Path>,
     update: bool,
@@ -611,8 +3254,226 @@ pub fn emerge(
     // can reuse it for further operations.
     match platform.configuration {
         Some(crate::manifest::RawPlatformConfiguration::Android(ref v)) => {
-            emerge_android(manifest, platform, v, path)
+            emerge_android(manifest, metadata, platform, v, path)
         },
         None => Ok(()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Verify that the emerge state survives a format/parse round-trip, and
+    // that paths escaping the platform directory are rejected.
+    #[test]
+    fn state_basic() {
+        let mut state = State::default();
+        state.files.insert("build.gradle".into(), 0x1);
+        state.files.insert("app/src/main/AndroidManifest.xml".into(), u64::MAX);
+
+        let v = State::parse(&state.format());
+        assert_eq!(v.files, state.files);
+
+        let v = State::parse(concat!(
+            "# comment\n",
+            "0000000000000001 ../outside\n",
+            "0000000000000002 /etc/passwd\n",
+            "0000000000000003 a/../../b\n",
+            "0000000000000004 a//b\n",
+            "0000000000000005 ./a\n",
+            "0000000000000006 \n",
+            "zz a\n",
+            "0000000000000007 a/b\n",
+        ));
+        assert_eq!(v.files.len(), 1);
+        assert_eq!(v.files.get("a/b"), Some(&7));
+    }
+
+    // Verify that sweeping removes leftovers of the previous emerge state,
+    // but retains files emerged again, modified files, and files that were
+    // never emerged.
+    #[test]
+    fn sweep_basic() {
+        let dir = std::env::temp_dir().join(format!("osiris-sweep-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("res")).unwrap();
+
+        let mut previous = State::default();
+        for file in ["kept", "res/unused", "res/modified", "res/missing"] {
+            emit_file(&mut previous, &dir, &dir.join(file), file).ok().unwrap();
+        }
+        std::fs::remove_file(dir.join("res/missing")).unwrap();
+        std::fs::write(dir.join("res/modified"), "local change").unwrap();
+        std::fs::write(dir.join("res/local"), "local file").unwrap();
+
+        let mut state = State::default();
+        emit_file(&mut state, &dir, &dir.join("kept"), "kept").ok().unwrap();
+        sweep_files(&previous, &state, &dir).ok().unwrap();
+
+        assert!(dir.join("kept").exists());
+        assert!(!dir.join("res/unused").exists());
+        assert_eq!(std::fs::read_to_string(dir.join("res/modified")).unwrap(), "local change");
+        assert_eq!(std::fs::read_to_string(dir.join("res/local")).unwrap(), "local file");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // Verify escaping of Java properties values, including separators,
+    // white-space and characters outside of printable ASCII.
+    #[test]
+    fn escape_properties_value_basic() {
+        assert_eq!(escape_properties_value("foo"), "foo");
+        assert_eq!(escape_properties_value(" a b"), "\\ a b");
+        assert_eq!(escape_properties_value("a=b:c#d!e"), "a\\=b\\:c\\#d\\!e");
+        assert_eq!(escape_properties_value("C:\\x\n"), "C\\:\\\\x\\n");
+        assert_eq!(escape_properties_value("D\u{e9}mo\u{7f}"), "D\\u00E9mo\\u007F");
+        assert_eq!(escape_properties_value("\u{1f600}\u{1}"), "\\uD83D\\uDE00\\u0001");
+    }
+
+    // Verify the syntax rendered for the Groovy and Kotlin DSLs of Gradle.
+    #[test]
+    fn gradle_dsl_basic() {
+        let (g, k) = (GradleDsl::Groovy, GradleDsl::Kotlin);
+
+        assert_eq!(g.assign("minSdk", &g.property_int("k")), "minSdk Integer.parseInt(osirisProperty('k'))");
+        assert_eq!(k.assign("minSdk", &k.property_int("k")), "minSdk = osirisProperty(\"k\").toInt()");
+        assert_eq!(g.flag("minifyEnabled", "true"), "minifyEnabled true");
+        assert_eq!(k.flag("minifyEnabled", "true"), "isMinifyEnabled = true");
+        assert_eq!(g.call("initWith", &g.named("debug")), "initWith debug");
+        assert_eq!(k.call("initWith", &k.named("debug")), "initWith(getByName(\"debug\"))");
+        assert_eq!(k.create("release"), "create(\"release\")");
+        assert_eq!(g.list(&["a", "b"]), "['a', 'b']");
+        assert_eq!(k.list(&["a", "b"]), "listOf(\"a\", \"b\")");
+    }
+
+    // Verify the content of `osiris.properties`, as written to a temporary
+    // platform directory.
+    #[test]
+    fn osiris_properties_basic() {
+        let dir = std::env::temp_dir().join(format!("osiris-properties-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        emerge_android_osiris_properties(
+            &dir,
+            &[
+                ("osiris.system.name", "D\u{e9}mo App".into()),
+                ("osiris.application.path", "/home/j\u{f6}rg/app".into()),
+                ("osiris.android.versionCodeAbiOffsets", "x86=1;arm64-v8a=2".into()),
+            ],
+        ).ok().unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(dir.join("osiris.properties")).unwrap(),
+            concat!(
+                "# Generated by osiris-platform\n",
+                "osiris.system.name=D\\u00E9mo App\n",
+                "osiris.application.path=/home/j\\u00F6rg/app\n",
+                "osiris.android.versionCodeAbiOffsets=x86\\=1;arm64-v8a\\=2\n",
+            ),
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // Verify that `gradle-wrapper.properties` pins the distribution of the
+    // given Gradle version and is recorded in the emerge state. Once no
+    // version is pinned, the emerged properties are swept, but properties
+    // created by `gradle wrapper` survive an update, whether or not they
+    // replaced emerged ones.
+    #[test]
+    fn android_gradle_wrapper_basic() {
+        let dir = std::env::temp_dir().join(format!("osiris-wrapper-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("gradle-wrapper.properties");
+
+        let mut previous = State::default();
+        let mut path = dir.clone();
+        emerge_android_gradle_wrapper_properties(
+            &mut path, &mut previous, &dir, Some("8.14.2"),
+        ).ok().unwrap();
+        assert_eq!(path, dir);
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            concat!(
+                "# Generated by osiris-platform\n",
+                "distributionBase=GRADLE_USER_HOME\n",
+                "distributionPath=wrapper/dists\n",
+                "distributionUrl=https\\://services.gradle.org/distributions/gradle-8.14.2-bin.zip\n",
+                "zipStoreBase=GRADLE_USER_HOME\n",
+                "zipStorePath=wrapper/dists\n",
+            ),
+        );
+        assert!(previous.files.contains_key("gradle-wrapper.properties"));
+
+        let mut state = State::default();
+        emerge_android_gradle_wrapper_properties(
+            &mut path, &mut state, &dir, None,
+        ).ok().unwrap();
+        assert!(state.files.is_empty());
+        sweep_files(&previous, &state, &dir).ok().unwrap();
+        assert!(!file.exists());
+
+        let wrapper = "distributionUrl=https\\://example.com/gradle-8.0-bin.zip\n";
+        std::fs::write(&file, wrapper).unwrap();
+        sweep_files(&state, &State::default(), &dir).ok().unwrap();
+        sweep_files(&previous, &State::default(), &dir).ok().unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), wrapper);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // Verify the Rust side of a bridge, including the mangled symbol names
+    // and the conversion of strings and byte arrays.
+    #[test]
+    fn android_bridge_rust_basic() {
+        use crate::manifest::RawPlatformAndroidBridgeType as T;
+
+        let bridge = crate::manifest::ViewPlatformAndroidBridge {
+            class: "foo.my_app.Bridge".into(),
+            rust_file: "src/bridge.rs".into(),
+            rust_module: "crate::bridge".into(),
+            methods: [
+                ("hash".to_string(), (vec![T::ByteArray], T::String)),
+                ("run".to_string(), (vec![T::Boolean, T::Int], T::Void)),
+            ].into_iter().collect(),
+        };
+        let v = android_bridge_rust(&bridge);
+
+        assert!(v.contains(concat!(
+            "#[no_mangle]\n",
+            "pub extern \"system\" fn Java_foo_my_1app_Bridge_hash(\n",
+            "    env: Env,\n",
+            "    _class: Object,\n",
+            "    a0: Object,\n",
+            ") -> Object {\n",
+            "    let a0 = unsafe { bytes_from_java(env, a0) };\n",
+            "    let v = crate::bridge::hash(a0);\n",
+            "    unsafe { string_to_java(env, v) }\n",
+            "}\n",
+        )));
+        assert!(v.contains(concat!(
+            "#[no_mangle]\n",
+            "pub extern \"system\" fn Java_foo_my_1app_Bridge_run(\n",
+            "    _env: Env,\n",
+            "    _class: Object,\n",
+            "    a0: u8,\n",
+            "    a1: i32,\n",
+            ") {\n",
+            "    crate::bridge::run(a0 != 0, a1);\n",
+            "}\n",
+        )));
+        assert!(v.contains("unsafe fn bytes_from_java(env: Env, v: Object) -> Vec<u8> {\n"));
+        assert!(v.contains("unsafe fn string_to_java(env: Env, v: String) -> Object {\n"));
+        assert!(v.contains("std::mem::transmute(env_fn(env, 163));\n"));
+        assert!(!v.contains("unsafe(no_mangle)"));
+
+        // Conversion helpers are only emitted if used.
+        let bridge = crate::manifest::ViewPlatformAndroidBridge {
+            methods: [("run".to_string(), (vec![], T::Void))].into_iter().collect(),
+            ..bridge
+        };
+        let v = android_bridge_rust(&bridge);
+        assert!(v.contains("pub extern \"system\" fn Java_foo_my_1app_Bridge_run(\n"));
+        assert!(!v.contains("env_fn"));
+    }
+}
@@ -0,0 +1,489 @@
+//! Initialize Platform Manifest
+//!
+//! Create a new `osiris-platform.toml` manifest for an existing Cargo
+//! package. The application parameters are proposed from the Cargo metadata,
+//! and each requested platform gets a platform table with sensible defaults.
+
+/// Init Errors
+///
+/// This is the exhaustive list of possible errors raised by the init
+/// operation. See each error for details.
+pub enum Error {
+    /// A manifest already exists at the specified path.
+    Already(std::ffi::OsString),
+    /// No suitable package was found in the Cargo workspace.
+    PackageMissing,
+    /// Multiple suitable packages were found in the Cargo workspace, but none
+    /// was selected. Contains the names of all candidates.
+    PackageAmbiguous(Vec<String>),
+    /// The selected package is not part of the Cargo workspace.
+    PackageUnknown(String),
+    /// The specified platform is not supported.
+    PlatformUnknown(String),
+    /// Specified option required but missing.
+    OptionMissing(&'static str),
+    /// The specified domain is not a valid domain name.
+    Domain(String),
+    /// The generated manifest is not valid (e.g., due to invalid values
+    /// provided by the user).
+    Manifest,
+    /// Writing the manifest to the specified path failed with the given
+    /// error.
+    FileUpdate(std::ffi::OsString, std::io::Error),
+}
+
+/// Init Options
+///
+/// This contains all options of the init operation. Any value left unset is
+/// proposed by the operation, and confirmed via the prompt, if any.
+#[derive(Default)]
+pub struct Options {
+    /// Name of the Cargo package implementing the application. Only needed if
+    /// the workspace contains multiple `cdylib` packages.
+    pub package: Option<String>,
+    /// Application ID. Defaults to the package name.
+    pub id: Option<String>,
+    /// Human-readable application name. Defaults to the package name in
+    /// title-case.
+    pub name: Option<String>,
+    /// IDs of the platforms to add platform tables for.
+    pub platforms: Vec<String>,
+    /// Domain owned by the publisher of the application, used to derive the
+    /// Android namespace (e.g., `example.com` yields `com.example.<id>`).
+    pub domain: Option<String>,
+    /// Path to the Android SDK. Detected from the environment if unset.
+    pub sdk_path: Option<String>,
+}
+
+/// Init Prompt
+///
+/// Callback used to query values from the user. It is invoked with a
+/// question and the proposed answer, and returns the answer of the user. If
+/// no prompt is used, all proposed answers are taken as is.
+pub type Prompt<'a> = dyn FnMut(&str, &str) -> String + 'a;
+
+/// Default Android SDK levels used for new platform tables. `min-sdk` is also
+/// used as NDK level, since lower levels are never used at runtime.
+const ANDROID_MIN_SDK: u32 = 29;
+const ANDROID_TARGET_SDK: u32 = 36;
+
+// Ask the prompt, if any
+//
+// Query the prompt with the given question and proposal. If there is no
+// prompt, or the user provides no answer, the proposal is returned.
+fn ask(prompt: &mut Option<&mut Prompt>, question: &str, proposal: &str) -> String {
+    match prompt {
+        Some(p) => {
+            let v = p(question, proposal);
+            let v = v.trim();
+            if v.is_empty() {
+                proposal.to_string()
+            } else {
+                v.to_string()
+            }
+        },
+        None => proposal.to_string(),
+    }
+}
+
+// Quote a TOML string
+//
+// Turn the given string into a TOML basic string, including the quotes.
+fn quote(data: &str) -> String {
+    toml::Value::String(data.to_string()).to_string()
+}
+
+// Turn a package name into a human-readable name
+//
+// Split the name at `-` and `_` and capitalize each word.
+fn title_case(name: &str) -> String {
+    name.split(['-', '_'])
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            let mut c = v.chars();
+            match c.next() {
+                Some(first) => first.to_uppercase().chain(c).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Derive a Java namespace
+//
+// Reverse the labels of the domain and append the application ID, turning
+// each into a valid Java package segment.
+fn android_namespace(domain: &str, id: &str) -> Result<String, Error> {
+    let domain = domain.trim().trim_end_matches('.');
+    let valid = !domain.is_empty() && domain.split('.').all(
+        |v| !v.is_empty() && v.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'),
+    );
+    if !valid {
+        return Err(Error::Domain(domain.to_string()));
+    }
+
+    Ok(
+        domain.split('.')
+            .rev()
+            .chain(std::iter::once(id))
+            .map(|v| crate::util::symbolize(&v.to_ascii_lowercase()))
+            .collect::<Vec<_>>()
+            .join(".")
+    )
+}
+
+// Detect the Android SDK
+//
+// Look for the Android SDK via the environment variables used by the Android
+// tooling, and then in the default installation paths of Android Studio.
+fn android_sdk_path() -> Option<String> {
+    for key in ["ANDROID_HOME", "ANDROID_SDK_ROOT"] {
+        if let Some(v) = std::env::var_os(key) {
+            if !v.is_empty() && std::path::Path::new(&v).is_dir() {
+                return Some(v.to_string_lossy().into_owned());
+            }
+        }
+    }
+
+    let home = std::env::var_os("HOME")?;
+    for sub in ["Android/Sdk", "Library/Android/sdk"] {
+        let v = std::path::Path::new(&home).join(sub);
+        if v.is_dir() {
+            return Some(v.to_string_lossy().into_owned());
+        }
+    }
+
+    None
+}
+
+// Select the application package
+//
+// Pick the requested package, or the only package of the workspace that
+// builds a `cdylib`.
+fn select_package<'metadata>(
+    metadata: &'metadata crate::cargo::Metadata,
+    package: Option<&str>,
+) -> Result<&'metadata crate::cargo::MetadataPackage, Error> {
+    if let Some(name) = package {
+        return metadata.packages.iter()
+            .find(|v| v.name == name)
+            .ok_or_else(|| Error::PackageUnknown(name.to_string()));
+    }
+
+    let candidates: Vec<&crate::cargo::MetadataPackage> = metadata.packages.iter()
+        .filter(|v| v.crate_types.iter().any(|t| t == "cdylib"))
+        .collect();
+
+    match candidates.len() {
+        0 => Err(Error::PackageMissing),
+        1 => Ok(candidates[0]),
+        _ => Err(Error::PackageAmbiguous(
+            candidates.iter().map(|v| v.name.clone()).collect(),
+        )),
+    }
+}
+
+// Generate the Android platform table
+//
+// Generate a `[[platform]]` entry with the given platform ID and an Android
+// configuration table for the application with the given ID.
+pub(crate) fn generate_android(
+    options: &Options,
+    prompt: &mut Option<&mut Prompt>,
+    id: &str,
+    platform_id: &str,
+) -> Result<String, Error> {
+    let domain = match options.domain.as_ref() {
+        Some(v) => v.clone(),
+        None if prompt.is_some() => ask(prompt, "Domain of the publisher", "example.com"),
+        None => return Err(Error::OptionMissing("domain")),
+    };
+    let namespace = android_namespace(&domain, id)?;
+    let namespace = ask(prompt, "Android namespace", &namespace);
+
+    let sdk_path = match options.sdk_path.as_ref() {
+        Some(v) => Some(v.clone()),
+        None => {
+            let v = android_sdk_path().unwrap_or_default();
+            let v = ask(prompt, "Path to the Android SDK", &v);
+            if v.is_empty() { None } else { Some(v) }
+        },
+    };
+
+    // Without a known SDK path, leave the key as a comment for the user to
+    // fill in, rather than writing a bogus path.
+    let sdk_path = match sdk_path {
+        Some(v) => format!("sdk-path = {}\n", quote(&v)),
+        None => "#sdk-path = \"/path/to/Android/Sdk\"\n".to_string(),
+    };
+
+    // The version name is left unset, so it follows the version of the
+    // Cargo package rather than going stale.
+    Ok(format!(
+        concat!(
+            "\n",
+            "[[platform]]\n",
+            "id = {4}\n",
+            "\n",
+            "[platform.android]\n",
+            "application-id = {0}\n",
+            "namespace = {0}\n",
+            "\n",
+            "compile-sdk = {2}\n",
+            "min-sdk = {1}\n",
+            "target-sdk = {2}\n",
+            "\n",
+            "ndk-level = {1}\n",
+            "\n",
+            "version-code = 1\n",
+            "\n",
+            "{3}",
+        ),
+        quote(&namespace),
+        ANDROID_MIN_SDK,
+        ANDROID_TARGET_SDK,
+        sdk_path,
+        quote(platform_id),
+    ))
+}
+
+/// Generate platform manifest
+///
+/// Generate the content of a new platform manifest located at
+/// `path_manifest` based on the Cargo metadata and the specified options.
+/// The generated manifest is verified to parse successfully.
+///
+/// If a prompt is given, all proposed values that were not specified in
+/// the options are confirmed via the prompt. Otherwise, proposed values are
+/// used as is, and options without suitable defaults are required.
+pub fn generate(
+    metadata: &crate::cargo::Metadata,
+    path_manifest: &std::path::Path,
+    options: &Options,
+    mut prompt: Option<&mut Prompt>,
+) -> Result<String, Error> {
+    for v in options.platforms.iter() {
+        if v != "android" {
+            return Err(Error::PlatformUnknown(v.clone()));
+        }
+    }
+
+    let package = select_package(metadata, options.package.as_deref())?;
+
+    let id = match options.id.as_ref() {
+        Some(v) => v.clone(),
+        None => ask(&mut prompt, "Application ID", &package.name),
+    };
+    let name = match options.name.as_ref() {
+        Some(v) => v.clone(),
+        None => ask(&mut prompt, "Application name", &title_case(&package.name)),
+    };
+
+    // Refer to the package relative to the manifest, if possible. The path
+    // can be omitted if both live in the same directory.
+    let dir_manifest = std::env::current_dir()
+        .expect("Cannot query current working directory")
+        .join(path_manifest)
+        .parent()
+        .map(|v| v.to_path_buf())
+        .unwrap_or_default();
+    let dir_package = std::path::Path::new(&package.manifest_path)
+        .parent()
+        .map(|v| v.to_path_buf())
+        .unwrap_or_default();
+    let path = match dir_package.strip_prefix(&dir_manifest) {
+        Ok(v) if v.as_os_str().is_empty() => None,
+        Ok(v) => Some(v.to_string_lossy().replace('\\', "/")),
+        Err(_) => Some(dir_package.to_string_lossy().into_owned()),
+    };
+
+    let mut content = format!(
+        concat!(
+            "version = 1\n",
+            "\n",
+            "[application]\n",
+            "id = {0}\n",
+            "name = {1}\n",
+            "package = {2}\n",
+        ),
+        quote(&id),
+        quote(&name),
+        quote(&package.name),
+    );
+    if let Some(v) = path {
+        content.push_str(&format!("path = {}\n", quote(&v)));
+    }
+
+    for v in options.platforms.iter() {
+        if v == "android" {
+            content.push_str(&generate_android(options, &mut prompt, &id, v)?);
+        }
+    }
+
+    crate::manifest::Manifest::parse_str(&path_manifest, &content)
+        .map_err(|_| Error::Manifest)?;
+
+    Ok(content)
+}
+
+/// Initialize platform manifest
+///
+/// Generate a new platform manifest via `generate()` and write it to
+/// `path_manifest`. An existing manifest is never overwritten.
+pub fn init(
+    metadata: &crate::cargo::Metadata,
+    path_manifest: &std::path::Path,
+    options: &Options,
+    prompt: Option<&mut Prompt>,
+) -> Result<(), Error> {
+    if path_manifest.exists() {
+        return Err(Error::Already(path_manifest.as_os_str().to_os_string()));
+    }
+
+    let content = generate(metadata, path_manifest, options, prompt)?;
+
+    // Create the file exclusively, so a manifest created concurrently is not
+    // overwritten either.
+    let mut f = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path_manifest)
+        .map_err(|v| {
+            if v.kind() == std::io::ErrorKind::AlreadyExists {
+                Error::Already(path_manifest.as_os_str().to_os_string())
+            } else {
+                Error::FileUpdate(path_manifest.as_os_str().to_os_string(), v)
+            }
+        })?;
+
+    <std::fs::File as std::io::Write>::write_all(&mut f, content.as_bytes())
+        .map_err(|v| Error::FileUpdate(path_manifest.as_os_str().to_os_string(), v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Verify namespace derivation from domains. Labels are reversed and
+    // turned into valid Java package segments.
+    #[test]
+    fn android_namespace_basic() {
+        assert_eq!(android_namespace("example.com", "app").ok().unwrap(), "com.example.app");
+        assert_eq!(android_namespace("Osiris.Foo.", "my-app").ok().unwrap(), "foo.osiris.my_app");
+        assert_eq!(android_namespace("3d.example.com", "app").ok().unwrap(), "com.example._3d.app");
+
+        assert!(android_namespace("", "app").is_err());
+        assert!(android_namespace("foo..bar", "app").is_err());
+        assert!(android_namespace("foo/bar", "app").is_err());
+    }
+
+    // Verify proposed application names from package names.
+    #[test]
+    fn title_case_basic() {
+        assert_eq!(title_case("app"), "App");
+        assert_eq!(title_case("example-makepad_counter"), "Example Makepad Counter");
+        assert_eq!(title_case("-foo--bar-"), "Foo Bar");
+    }
+
+    // Create fabricated Cargo metadata with a library, and two applications,
+    // one of which lives outside of the workspace directory `dir`.
+    fn metadata(dir: &std::path::Path) -> crate::cargo::Metadata {
+        let package = |name: &str, path: std::path::PathBuf, crate_type: &str| {
+            crate::cargo::MetadataPackage {
+                name: name.into(),
+                version: "0.1.0".into(),
+                manifest_path: path.join("Cargo.toml").to_string_lossy().into_owned(),
+                crate_types: vec![crate_type.into()],
+            }
+        };
+
+        crate::cargo::Metadata {
+            target_directory: dir.join("target").to_string_lossy().into_owned(),
+            packages: vec![
+                package("core", dir.join("core"), "lib"),
+                package("my-app", dir.join("app"), "cdylib"),
+                package("game", dir.with_extension("game"), "cdylib"),
+            ],
+        }
+    }
+
+    // Verify manifest generation from Cargo metadata
+    //
+    // Packages are selected explicitly or as the only `cdylib`, and their
+    // path is derived relative to the manifest. Verify the generated
+    // Android table, and that existing manifests are never overwritten.
+    #[test]
+    fn generate_basic() {
+        let dir = std::env::temp_dir().join(format!("osiris-init-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path_manifest = dir.join("osiris-platform.toml");
+        let mut metadata = metadata(&dir);
+
+        let options = Options {
+            platforms: vec!["android".into()],
+            domain: Some("example.com".into()),
+            sdk_path: Some("/sdk".into()),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            generate(&metadata, &path_manifest, &options, None),
+            Err(Error::PackageAmbiguous(v)) if v == ["my-app", "game"],
+        ));
+        let v = Options { package: Some("foo".into()), ..Default::default() };
+        assert!(matches!(
+            generate(&metadata, &path_manifest, &v, None),
+            Err(Error::PackageUnknown(v)) if v == "foo",
+        ));
+        let v = Options { platforms: vec!["ios".into()], ..Default::default() };
+        assert!(matches!(
+            generate(&metadata, &path_manifest, &v, None),
+            Err(Error::PlatformUnknown(v)) if v == "ios",
+        ));
+
+        let options = Options { package: Some("my-app".into()), ..options };
+        let content = generate(&metadata, &path_manifest, &options, None).ok().unwrap();
+        assert!(content.contains(concat!(
+            "id = \"my-app\"\n",
+            "name = \"My App\"\n",
+            "package = \"my-app\"\n",
+            "path = \"app\"\n",
+        )));
+        assert!(content.contains("namespace = \"com.example.my_app\"\n"));
+        assert!(content.contains("target-sdk = 36\n"));
+        assert!(content.contains("sdk-path = \"/sdk\"\n"));
+        assert!(!content.contains("version-name"));
+
+        // The package outside of the manifest directory is referred to by
+        // its absolute path, while a package next to the manifest needs no
+        // path at all.
+        let options = Options { package: None, domain: None, platforms: vec![], ..options };
+        metadata.packages.retain(|v| v.name == "game");
+        let content = generate(&metadata, &path_manifest, &options, None).ok().unwrap();
+        assert!(content.contains(&format!("path = \"{}\"\n", dir.with_extension("game").display())));
+        metadata.packages[0].manifest_path = dir.join("Cargo.toml").to_string_lossy().into_owned();
+        let content = generate(&metadata, &path_manifest, &options, None).ok().unwrap();
+        assert!(!content.contains("path ="));
+
+        // The Android table requires a domain, unless prompted for.
+        let options = Options { platforms: vec!["android".into()], ..options };
+        assert!(matches!(
+            generate(&metadata, &path_manifest, &options, None),
+            Err(Error::OptionMissing("domain")),
+        ));
+
+        let options = Options { domain: Some("example.com".into()), ..options };
+        init(&metadata, &path_manifest, &options, None).ok().unwrap();
+        let content = std::fs::read_to_string(&path_manifest).unwrap();
+        assert!(content.starts_with("version = 1\n"));
+        std::fs::write(&path_manifest, "foo").unwrap();
+        assert!(matches!(
+            init(&metadata, &path_manifest, &options, None),
+            Err(Error::Already(_)),
+        ));
+        assert_eq!(std::fs::read_to_string(&path_manifest).unwrap(), "foo");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
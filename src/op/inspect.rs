@@ -0,0 +1,359 @@
+//! Inspect Platform Artifacts
+//!
+//! Inspect the content of built artifacts. For Android, APKs and app bundles
+//! are supported. All archive entries and native libraries are listed, and
+//! the compiled `AndroidManifest.xml` is decoded. The relevant manifest
+//! values can be compared against the platform manifest, to verify that the
+//! artifact matches the configuration.
+
+/// Inspect Errors
+///
+/// This is the exhaustive list of possible errors raised by the inspect
+/// operation. See each error for details.
+pub enum Error {
+    /// Specified key required but missing in manifest.
+    ManifestKey(&'static str),
+    /// Reading the file at the specified path failed with the given error.
+    FileRead(std::ffi::OsString, std::io::Error),
+    /// The file at the specified path is not a valid archive.
+    Archive(std::ffi::OsString),
+    /// The archive at the specified path lacks an `AndroidManifest.xml`.
+    AndroidManifestMissing(std::ffi::OsString),
+    /// The `AndroidManifest.xml` of the archive at the specified path could
+    /// not be decoded.
+    AndroidManifestFormat(std::ffi::OsString),
+    /// Resolving the version of the platform integration failed.
+    Version(crate::manifest::ErrorVersion),
+}
+
+impl Error {
+    fn from_manifest_error_view(error: crate::manifest::ErrorView) -> Self {
+        match error {
+            crate::manifest::ErrorView::MissingKey(v) => Self::ManifestKey(v),
+        }
+    }
+}
+
+/// Artifact Formats
+///
+/// Sub-type of `Report` describing the format of the inspected artifact.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// Android application package.
+    Apk,
+    /// Android app bundle.
+    Aab,
+}
+
+/// Archive Entry
+///
+/// Sub-type of `Report` describing a single entry of the artifact archive.
+pub struct ReportEntry {
+    /// Path of the entry in the archive.
+    pub name: String,
+    /// Uncompressed size of the entry in bytes.
+    pub size: u64,
+    /// Compressed size of the entry in bytes.
+    pub compressed_size: u64,
+    /// Compression method of the entry (e.g., `stored` or `deflated`).
+    pub compression: String,
+}
+
+/// Native Library
+///
+/// Sub-type of `Report` describing a single native library of the artifact.
+pub struct ReportLibrary {
+    /// ABI of the library.
+    pub abi: String,
+    /// File name of the library.
+    pub name: String,
+    /// Uncompressed size of the library in bytes.
+    pub size: u64,
+}
+
+/// Inspect Report
+///
+/// This describes the content of an inspected artifact. Values of the
+/// Android manifest are `None` if not present in the artifact.
+pub struct Report {
+    /// Format of the artifact.
+    pub format: ReportFormat,
+    /// All entries of the artifact archive.
+    pub entries: Vec<ReportEntry>,
+    /// All native libraries of the artifact, ordered by ABI.
+    pub libraries: Vec<ReportLibrary>,
+    /// Decoded `AndroidManifest.xml` as text.
+    pub android_manifest: String,
+    /// Application ID (i.e., the manifest package).
+    pub application_id: Option<String>,
+    /// Version code.
+    pub version_code: Option<u32>,
+    /// Version name.
+    pub version_name: Option<String>,
+    /// Minimum SDK version.
+    pub min_sdk: Option<u32>,
+    /// Target SDK version.
+    pub target_sdk: Option<u32>,
+    /// All requested permissions.
+    pub permissions: Vec<String>,
+}
+
+/// Manifest Mismatch
+///
+/// A value of an inspected artifact that differs from the value expected
+/// by the platform manifest.
+pub struct Mismatch {
+    /// Name of the mismatching value.
+    pub key: &'static str,
+    /// Value expected by the platform manifest.
+    pub expected: String,
+    /// Value found in the artifact, or `None` if missing.
+    pub actual: Option<String>,
+}
+
+// Parse a native library path
+//
+// Native libraries reside in `lib/<abi>/<name>.so` of an APK, and in
+// `<module>/lib/<abi>/<name>.so` of an app bundle. Return the ABI and file
+// name of the library, if the path refers to one.
+fn parse_library(name: &str) -> Option<(&str, &str)> {
+    let mut iter = name.rsplit('/');
+    let file = iter.next()?;
+    let abi = iter.next()?;
+    if !file.ends_with(".so") || iter.next()? != "lib" {
+        return None;
+    }
+    Some((abi, file))
+}
+
+/// Inspect artifact
+///
+/// Open the APK or app bundle at the specified path and report its content.
+/// The format is detected from the location of `AndroidManifest.xml` in the
+/// archive.
+pub fn inspect(
+    path: &std::path::Path,
+) -> Result<Report, Error> {
+    let file = std::fs::File::open(path).map_err(
+        |v| Error::FileRead(path.as_os_str().to_os_string(), v),
+    )?;
+    let mut archive = zip::ZipArchive::new(std::io::BufReader::new(file)).map_err(
+        |_| Error::Archive(path.as_os_str().to_os_string()),
+    )?;
+
+    let mut entries = Vec::new();
+    let mut libraries = Vec::new();
+    for i in 0..archive.len() {
+        let v = archive.by_index_raw(i).map_err(
+            |_| Error::Archive(path.as_os_str().to_os_string()),
+        )?;
+        if v.is_dir() {
+            continue;
+        }
+
+        let compression = match v.compression() {
+            zip::CompressionMethod::Stored => "stored".to_string(),
+            zip::CompressionMethod::Deflated => "deflated".to_string(),
+            v => format!("{:?}", v).to_lowercase(),
+        };
+        if let Some((abi, name)) = parse_library(v.name()) {
+            libraries.push(ReportLibrary {
+                abi: abi.to_string(),
+                name: name.to_string(),
+                size: v.size(),
+            });
+        }
+        entries.push(ReportEntry {
+            name: v.name().to_string(),
+            size: v.size(),
+            compressed_size: v.compressed_size(),
+            compression,
+        });
+    }
+    libraries.sort_by(|a, b| (&a.abi, &a.name).cmp(&(&b.abi, &b.name)));
+
+    // App bundles keep the manifest of the base module in
+    // `base/manifest/`, APKs at the root.
+    let (format, name) = if entries.iter().any(|v| v.name == "base/manifest/AndroidManifest.xml") {
+        (ReportFormat::Aab, "base/manifest/AndroidManifest.xml")
+    } else {
+        (ReportFormat::Apk, "AndroidManifest.xml")
+    };
+
+    let mut data = Vec::new();
+    let mut v = archive.by_name(name).map_err(
+        |_| Error::AndroidManifestMissing(path.as_os_str().to_os_string()),
+    )?;
+    std::io::Read::read_to_end(&mut v, &mut data).map_err(
+        |v| Error::FileRead(path.as_os_str().to_os_string(), v),
+    )?;
+    let xml = crate::axml::decode(&data).map_err(
+        |_| Error::AndroidManifestFormat(path.as_os_str().to_os_string()),
+    )?;
+
+    let android = |e: &crate::axml::Element, name: &str| {
+        e.attribute(Some(crate::axml::ANDROID_NS), name).map(|v| v.to_string())
+    };
+    let uses_sdk = xml.children_by_name("uses-sdk").next();
+    let mut permissions: Vec<String> = xml.children.iter()
+        .filter(|v| v.name == "uses-permission" || v.name == "uses-permission-sdk-23")
+        .filter_map(|v| android(v, "name"))
+        .collect();
+    permissions.sort();
+    permissions.dedup();
+
+    Ok(Report {
+        format,
+        entries,
+        libraries,
+        android_manifest: xml.to_text(),
+        application_id: xml.attribute(None, "package").map(|v| v.to_string()),
+        version_code: android(&xml, "versionCode").and_then(|v| v.parse().ok()),
+        version_name: android(&xml, "versionName"),
+        min_sdk: uses_sdk.and_then(|v| android(v, "minSdkVersion")).and_then(|v| v.parse().ok()),
+        target_sdk: uses_sdk.and_then(|v| android(v, "targetSdkVersion")).and_then(|v| v.parse().ok()),
+        permissions,
+    })
+}
+
+/// Compare artifact against manifest
+///
+/// Compare the values of an inspected artifact against the values the
+/// platform manifest resolves to, and return all mismatches. The expected
+/// version code of split APKs includes the offset of their ABI. Version
+/// codes derived from timestamps are not compared, since they change with
+/// every build.
+pub fn compare(
+    manifest: &crate::manifest::Manifest,
+    metadata: &crate::cargo::Metadata,
+    platform: &crate::manifest::RawPlatform,
+    report: &Report,
+) -> Result<Vec<Mismatch>, Error> {
+    let mut mismatches = Vec::new();
+
+    let Some(android) = platform.android() else {
+        return Ok(mismatches);
+    };
+
+    let view_application = manifest.raw.view_application()
+        .map_err(Error::from_manifest_error_view)?;
+    let view_android = android.view(&manifest.raw)
+        .map_err(Error::from_manifest_error_view)?;
+    let version = crate::op::emerge::android_version(
+        manifest,
+        metadata,
+        &view_application,
+        &view_android,
+    ).map_err(Error::Version)?;
+
+    let mut check = |key: &'static str, expected: String, actual: Option<String>| {
+        if actual.as_ref() != Some(&expected) {
+            mismatches.push(Mismatch { key, expected, actual });
+        }
+    };
+
+    check("application-id", view_android.application_id.clone(), report.application_id.clone());
+    check("version-name", version.name.clone(), report.version_name.clone());
+    check("min-sdk", view_android.min_sdk.to_string(), report.min_sdk.map(|v| v.to_string()));
+    check("target-sdk", view_android.target_sdk.to_string(), report.target_sdk.map(|v| v.to_string()));
+
+    if view_android.version_code != crate::manifest::RawPlatformAndroidVersionCode::Strategy(
+        crate::manifest::RawPlatformAndroidVersionCodeStrategy::Timestamp,
+    ) {
+        let mut abis: Vec<&str> = report.libraries.iter().map(|v| v.abi.as_str()).collect();
+        abis.dedup();
+
+        let offset = match (report.format, view_android.abi_splits, abis.as_slice()) {
+            (ReportFormat::Apk, true, [abi]) => {
+                view_android.version_code_abi_offsets.get(*abi).copied().unwrap_or(0)
+            },
+            _ => 0,
+        };
+        check("version-code", (version.code + offset).to_string(), report.version_code.map(|v| v.to_string()));
+    }
+
+    Ok(mismatches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Verify native library detection
+    //
+    // Libraries are detected in APKs and app bundle modules, but not in
+    // other directories.
+    #[test]
+    fn library_parse() {
+        assert_eq!(parse_library("lib/arm64-v8a/libfoo.so"), Some(("arm64-v8a", "libfoo.so")));
+        assert_eq!(parse_library("base/lib/x86/libfoo.so"), Some(("x86", "libfoo.so")));
+        assert_eq!(parse_library("assets/arm64-v8a/libfoo.so"), None);
+        assert_eq!(parse_library("lib/arm64-v8a/foo.txt"), None);
+        assert_eq!(parse_library("libfoo.so"), None);
+    }
+
+    // Verify comparison against the platform manifest
+    //
+    // Split APKs with libraries of a single ABI expect the version code
+    // offset of that ABI, while multi-ABI APKs and app bundles expect the
+    // plain version code. Timestamp version codes are never compared.
+    #[test]
+    fn compare_basic() {
+        let metadata = crate::cargo::Metadata {
+            target_directory: "/target".into(),
+            packages: vec![crate::cargo::MetadataPackage {
+                name: "test".into(),
+                version: "1.2.3".into(),
+                manifest_path: "/app/Cargo.toml".into(),
+                crate_types: vec!["cdylib".into()],
+            }],
+        };
+        let report = |format: ReportFormat, abis: &[&str], version_code: u32| Report {
+            format,
+            entries: Vec::new(),
+            libraries: abis.iter()
+                .flat_map(|abi| ["libc++_shared.so", "libtest.so"].map(|name| ReportLibrary {
+                    abi: abi.to_string(),
+                    name: name.into(),
+                    size: 0,
+                }))
+                .collect(),
+            android_manifest: String::new(),
+            application_id: Some("test".into()),
+            version_code: Some(version_code),
+            version_name: Some("1.2.3".into()),
+            min_sdk: Some(29),
+            target_sdk: Some(29),
+            permissions: Vec::new(),
+        };
+        let keys = |extra: &str, report: &Report| -> Vec<&'static str> {
+            let m = crate::manifest::tests::android(&format!("target-sdk = 29\n{}", extra)).unwrap();
+            compare(&m, &metadata, &m.raw.platform[0], report).ok().unwrap()
+                .iter().map(|v| v.key).collect()
+        };
+
+        let splits = "abi-splits = true\nabis = [\"arm64-v8a\", \"x86_64\"]\nversion-code = 5";
+        assert!(keys(splits, &report(ReportFormat::Apk, &["arm64-v8a"], 300000005)).is_empty());
+        assert!(keys(splits, &report(ReportFormat::Apk, &["x86_64"], 400000005)).is_empty());
+        assert!(keys(splits, &report(ReportFormat::Apk, &["arm64-v8a", "x86_64"], 5)).is_empty());
+        assert!(keys(splits, &report(ReportFormat::Aab, &["arm64-v8a"], 5)).is_empty());
+        assert_eq!(keys(splits, &report(ReportFormat::Apk, &["arm64-v8a"], 5)), ["version-code"]);
+        assert!(keys("version-code = 5", &report(ReportFormat::Apk, &["arm64-v8a"], 5)).is_empty());
+
+        let m = crate::manifest::tests::android(&format!("target-sdk = 29\n{}", splits)).unwrap();
+        let r = report(ReportFormat::Apk, &["x86_64"], 5);
+        let v = compare(&m, &metadata, &m.raw.platform[0], &r).ok().unwrap();
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0].key, "version-code");
+        assert_eq!(v[0].expected, "400000005");
+        assert_eq!(v[0].actual.as_deref(), Some("5"));
+
+        assert!(keys("version-code = \"timestamp\"", &report(ReportFormat::Apk, &["arm64-v8a"], 1)).is_empty());
+
+        let mut r = report(ReportFormat::Apk, &[], 1);
+        r.application_id = Some("foo.baz".into());
+        r.version_name = Some("1.2.4".into());
+        r.min_sdk = None;
+        assert_eq!(keys("", &r), ["application-id", "version-name", "min-sdk"]);
+    }
+}
@@ -0,0 +1,473 @@
+//! Edit Platform Manifest
+//!
+//! Query and modify the platform manifest programmatically. All edits
+//! preserve comments and formatting of the manifest, and the result is
+//! verified with the same checks as used when parsing the manifest, before
+//! it is written back.
+//!
+//! Keys are dotted paths into the manifest (e.g., `application.name`). The
+//! `[[platform]]` entries are addressed by their ID, so `platform.<id>.path`
+//! refers to the `path` key of the platform with the given ID, and
+//! `platform.<id>.android.version-code` to its Android configuration.
+
+/// Manifest Errors
+///
+/// This is the exhaustive list of possible errors raised by the manifest
+/// operations. See each error for details.
+pub enum Error {
+    /// Reading the manifest at the specified path failed with the given
+    /// error.
+    FileRead(std::ffi::OsString, std::io::Error),
+    /// The manifest at the specified path is not valid TOML.
+    Parse(std::ffi::OsString),
+    /// The specified key is malformed.
+    Key(String),
+    /// The specified key does not exist in the manifest.
+    KeyMissing(String),
+    /// The specified key refers to a value that is not a table, but is used
+    /// as one.
+    KeyType(String),
+    /// A platform with the specified ID already exists.
+    PlatformExists(String),
+    /// No platform with the specified ID exists.
+    PlatformMissing(String),
+    /// Generating the platform table failed.
+    Init(crate::op::init::Error),
+    /// The edited manifest fails validation.
+    Invalid,
+    /// Writing the manifest to the specified path failed with the given
+    /// error.
+    FileUpdate(std::ffi::OsString, std::io::Error),
+}
+
+// Load the manifest as editable document.
+fn load(path: &std::path::Path) -> Result<toml_edit::Document, Error> {
+    let content = std::fs::read_to_string(path).map_err(
+        |v| Error::FileRead(path.as_os_str().to_os_string(), v),
+    )?;
+
+    content.parse::<toml_edit::Document>().map_err(
+        |_| Error::Parse(path.as_os_str().to_os_string()),
+    )
+}
+
+// Store the manifest
+//
+// Verify the edited manifest with the manifest parser and write it back to
+// the specified path. Invalid manifests are never written.
+fn store(path: &std::path::Path, document: &toml_edit::Document) -> Result<(), Error> {
+    let content = document.to_string();
+
+    let manifest = crate::manifest::Manifest::parse_str(&path, &content)
+        .map_err(|_| Error::Invalid)?;
+
+    // Platform configuration is flattened into the platform tables, so an
+    // invalid configuration table is silently ignored by the parser, rather
+    // than refused. Hence, verify every configuration table was picked up.
+    if let Some(platforms) = document.get("platform").and_then(|v| v.as_array_of_tables()) {
+        for (table, platform) in platforms.iter().zip(manifest.raw.platform.iter()) {
+            if table.contains_key("android") && platform.android().is_none() {
+                return Err(Error::Invalid);
+            }
+        }
+    }
+
+    std::fs::write(path, content).map_err(
+        |v| Error::FileUpdate(path.as_os_str().to_os_string(), v),
+    )
+}
+
+// Find the last table position
+//
+// Return the highest document position of all tables in the given table,
+// including itself. Tables are written in the order of their position.
+fn last_position(table: &toml_edit::Table) -> usize {
+    let mut v = table.position().unwrap_or(0);
+
+    for (_, item) in table.iter() {
+        match item {
+            toml_edit::Item::Table(t) => {
+                v = v.max(last_position(t));
+            },
+            toml_edit::Item::ArrayOfTables(a) => {
+                for t in a.iter() {
+                    v = v.max(last_position(t));
+                }
+            },
+            _ => {},
+        }
+    }
+
+    v
+}
+
+// Move a table to the end
+//
+// Assign increasing document positions starting at `next` to the given table
+// and all its sub-tables, so they are written after all existing tables.
+fn reposition(table: &mut toml_edit::Table, next: &mut usize) {
+    table.set_position(*next);
+    *next += 1;
+
+    for (_, item) in table.iter_mut() {
+        if let toml_edit::Item::Table(t) = item {
+            reposition(t, next);
+        }
+    }
+}
+
+// Split a key into its segments
+//
+// Split the dotted key at each dot, rejecting empty segments.
+fn split_key(key: &str) -> Result<Vec<&str>, Error> {
+    let v: Vec<&str> = key.split('.').collect();
+
+    if v.iter().any(|v| v.is_empty()) {
+        Err(Error::Key(key.to_string()))
+    } else {
+        Ok(v)
+    }
+}
+
+// Find a platform by its ID
+//
+// Return the index of the `[[platform]]` entry with the given ID, if any.
+fn find_platform(document: &toml_edit::Document, id: &str) -> Option<usize> {
+    document.get("platform")
+        .and_then(|v| v.as_array_of_tables())
+        .and_then(|v| {
+            v.iter().position(
+                |t| t.get("id").and_then(|v| v.as_str()) == Some(id),
+            )
+        })
+}
+
+// Resolve the parent table of a key
+//
+// Walk all segments of the key but the last, and return the table they
+// refer to. If `create` is set, missing tables are created on the way.
+// Platform entries are resolved by their ID.
+fn resolve<'doc>(
+    document: &'doc mut toml_edit::Document,
+    key: &str,
+    segments: &[&str],
+    create: bool,
+) -> Result<&'doc mut dyn toml_edit::TableLike, Error> {
+    let mut table: &mut dyn toml_edit::TableLike = document.as_table_mut();
+    let mut rest = &segments[..segments.len() - 1];
+
+    if segments.len() > 2 && segments[0] == "platform" {
+        let id = segments[1];
+        table = table.get_mut("platform")
+            .and_then(|v| v.as_array_of_tables_mut())
+            .and_then(|v| {
+                v.iter_mut().find(
+                    |t| t.get("id").and_then(|v| v.as_str()) == Some(id),
+                )
+            })
+            .ok_or_else(|| Error::PlatformMissing(id.to_string()))?;
+        rest = &rest[2..];
+    }
+
+    for segment in rest {
+        if create && !table.contains_key(segment) {
+            table.insert(segment, toml_edit::table());
+        }
+
+        table = table.get_mut(segment)
+            .ok_or_else(|| Error::KeyMissing(key.to_string()))?
+            .as_table_like_mut()
+            .ok_or_else(|| Error::KeyType(key.to_string()))?;
+    }
+
+    Ok(table)
+}
+
+/// Query manifest value
+///
+/// Return the value of the specified key in the manifest at the specified
+/// path. Strings are returned without quotes, all other values in their
+/// TOML representation.
+pub fn get(
+    path: &std::path::Path,
+    key: &str,
+) -> Result<String, Error> {
+    let mut document = load(path)?;
+    let segments = split_key(key)?;
+    let table = resolve(&mut document, key, &segments, false)?;
+
+    let item = table.get(segments[segments.len() - 1])
+        .ok_or_else(|| Error::KeyMissing(key.to_string()))?;
+
+    Ok(match item {
+        toml_edit::Item::Value(toml_edit::Value::String(v)) => v.value().clone(),
+        toml_edit::Item::Value(v) => {
+            let mut v = v.clone();
+            v.decor_mut().clear();
+            v.to_string()
+        },
+        v => v.to_string().trim().to_string(),
+    })
+}
+
+/// Modify manifest value
+///
+/// Set the specified key in the manifest at the specified path to the given
+/// value. Missing tables are created as needed. The value is parsed as TOML
+/// value, and used as plain string if it is not valid TOML. Comments attached
+/// to a previous value are retained.
+pub fn set(
+    path: &std::path::Path,
+    key: &str,
+    value: &str,
+) -> Result<(), Error> {
+    let mut document = load(path)?;
+    let segments = split_key(key)?;
+    let table = resolve(&mut document, key, &segments, true)?;
+    let last = segments[segments.len() - 1];
+
+    let mut value = value.parse::<toml_edit::Value>()
+        .unwrap_or_else(|_| toml_edit::Value::from(value));
+
+    if let Some(old) = table.get(last).and_then(|v| v.as_value()) {
+        *value.decor_mut() = old.decor().clone();
+    } else {
+        value.decor_mut().clear();
+    }
+    table.insert(last, toml_edit::Item::Value(value));
+
+    store(path, &document)
+}
+
+/// Add platform
+///
+/// Add a new `[[platform]]` entry with the specified ID and kind to the
+/// manifest at the specified path. The platform table is generated like the
+/// init operation does, using the given options and prompt.
+pub fn add_platform(
+    path: &std::path::Path,
+    id: &str,
+    kind: &str,
+    options: &crate::op::init::Options,
+    mut prompt: Option<&mut crate::op::init::Prompt>,
+) -> Result<(), Error> {
+    let mut document = load(path)?;
+
+    if find_platform(&document, id).is_some() {
+        return Err(Error::PlatformExists(id.to_string()));
+    }
+
+    // The application ID is needed to derive namespaces. Use the value of
+    // the existing manifest, including its defaults.
+    let manifest = crate::manifest::Manifest::parse_str(&path, &document.to_string())
+        .map_err(|_| Error::Invalid)?;
+    let view_application = manifest.raw.view_application()
+        .map_err(|_| Error::Invalid)?;
+
+    let content = match kind {
+        "android" => crate::op::init::generate_android(
+            options,
+            &mut prompt,
+            &view_application.id,
+            id,
+        ).map_err(Error::Init)?,
+        _ => return Err(Error::Init(crate::op::init::Error::PlatformUnknown(kind.to_string()))),
+    };
+
+    let generated = content.parse::<toml_edit::Document>()
+        .expect("Generated platform table must be valid TOML");
+    let mut table = generated.get("platform")
+        .and_then(|v| v.as_array_of_tables())
+        .and_then(|v| v.get(0))
+        .expect("Generated platform table must contain a platform")
+        .clone();
+
+    // Tables of the generated document carry their own positions, so move
+    // them behind all tables of the manifest.
+    let mut next = last_position(document.as_table()) + 1;
+    reposition(&mut table, &mut next);
+
+    if !document.contains_key("platform") {
+        document.insert("platform", toml_edit::Item::ArrayOfTables(toml_edit::ArrayOfTables::new()));
+    }
+    document.get_mut("platform")
+        .and_then(|v| v.as_array_of_tables_mut())
+        .ok_or_else(|| Error::KeyType("platform".to_string()))?
+        .push(table);
+
+    store(path, &document)
+}
+
+/// Remove platform
+///
+/// Remove the `[[platform]]` entry with the specified ID from the manifest
+/// at the specified path, including all its configuration.
+pub fn remove_platform(
+    path: &std::path::Path,
+    id: &str,
+) -> Result<(), Error> {
+    let mut document = load(path)?;
+
+    let index = find_platform(&document, id)
+        .ok_or_else(|| Error::PlatformMissing(id.to_string()))?;
+    let platforms = document.get_mut("platform")
+        .and_then(|v| v.as_array_of_tables_mut())
+        .expect("Platform array must exist if a platform was found");
+    platforms.remove(index);
+    if platforms.is_empty() {
+        document.remove("platform");
+    }
+
+    store(path, &document)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Verify editing preserves comments and formatting, and that invalid
+    // edits are refused without touching the manifest.
+    #[test]
+    fn manifest_edit_roundtrip() {
+        let mut path = std::env::temp_dir();
+        path.push(format!("osiris-platform-test-manifest-{}.toml", std::process::id()));
+
+        let content = concat!(
+            "version = 1 # format\n",
+            "\n",
+            "[application]\n",
+            "id = \"foo\"\n",
+            "\n",
+            "# The Android platform\n",
+            "[[platform]]\n",
+            "id = \"android\"\n",
+            "[platform.android]\n",
+            "version-code = 1 # bump on release\n",
+        );
+        std::fs::write(&path, content).unwrap();
+
+        assert_eq!(get(&path, "platform.android.android.version-code").ok().unwrap(), "1");
+        assert_eq!(get(&path, "application.id").ok().unwrap(), "foo");
+        assert!(matches!(get(&path, "application.name"), Err(Error::KeyMissing(_))));
+        assert!(matches!(get(&path, "platform.ios.path"), Err(Error::PlatformMissing(_))));
+        assert!(matches!(get(&path, "application..id"), Err(Error::Key(_))));
+
+        assert!(set(&path, "platform.android.android.version-code", "2").is_ok());
+        assert!(set(&path, "application.name", "Foo Bar").is_ok());
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            concat!(
+                "version = 1 # format\n",
+                "\n",
+                "[application]\n",
+                "id = \"foo\"\n",
+                "name = \"Foo Bar\"\n",
+                "\n",
+                "# The Android platform\n",
+                "[[platform]]\n",
+                "id = \"android\"\n",
+                "[platform.android]\n",
+                "version-code = 2 # bump on release\n",
+            ),
+        );
+
+        let old = std::fs::read_to_string(&path).unwrap();
+        assert!(matches!(set(&path, "platform.android.android.version-code", "x"), Err(Error::Invalid)));
+        assert!(matches!(set(&path, "application.id", "foo bar"), Err(Error::Invalid)));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), old);
+
+        assert!(matches!(remove_platform(&path, "ios"), Err(Error::PlatformMissing(_))));
+        assert!(remove_platform(&path, "android").is_ok());
+        assert!(matches!(get(&path, "platform.android.path"), Err(Error::PlatformMissing(_))));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    // Verify an added platform is generated like the init operation does,
+    // and placed after all existing tables, including tables of other
+    // entries following the platforms, without touching comments.
+    #[test]
+    fn manifest_add_platform() {
+        let mut path = std::env::temp_dir();
+        path.push(format!("osiris-platform-test-add-platform-{}.toml", std::process::id()));
+
+        std::fs::write(
+            &path,
+            concat!(
+                "version = 1 # format\n",
+                "\n",
+                "[application]\n",
+                "id = \"foo\"\n",
+                "package = \"foo\"\n",
+                "\n",
+                "# The Android platform\n",
+                "[[platform]]\n",
+                "id = \"android\"\n",
+                "[platform.android]\n",
+                "version-code = 1 # bump on release\n",
+                "\n",
+                "# Colors of the application\n",
+                "[application.branding]\n",
+                "primary = \"#336699\"\n",
+            ),
+        ).unwrap();
+
+        let options = crate::op::init::Options {
+            domain: Some("example.com".into()),
+            sdk_path: Some("/sdk".into()),
+            ..Default::default()
+        };
+        assert!(add_platform(&path, "tv", "android", &options, None).is_ok());
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            concat!(
+                "version = 1 # format\n",
+                "\n",
+                "[application]\n",
+                "id = \"foo\"\n",
+                "package = \"foo\"\n",
+                "\n",
+                "# The Android platform\n",
+                "[[platform]]\n",
+                "id = \"android\"\n",
+                "[platform.android]\n",
+                "version-code = 1 # bump on release\n",
+                "\n",
+                "# Colors of the application\n",
+                "[application.branding]\n",
+                "primary = \"#336699\"\n",
+                "\n",
+                "[[platform]]\n",
+                "id = \"tv\"\n",
+                "\n",
+                "[platform.android]\n",
+                "application-id = \"com.example.foo\"\n",
+                "namespace = \"com.example.foo\"\n",
+                "\n",
+                "compile-sdk = 36\n",
+                "min-sdk = 29\n",
+                "target-sdk = 36\n",
+                "\n",
+                "ndk-level = 29\n",
+                "\n",
+                "version-code = 1\n",
+                "\n",
+                "sdk-path = \"/sdk\"\n",
+            ),
+        );
+        assert_eq!(get(&path, "platform.tv.android.namespace").ok().unwrap(), "com.example.foo");
+
+        let old = std::fs::read_to_string(&path).unwrap();
+        assert!(matches!(
+            add_platform(&path, "tv", "android", &options, None),
+            Err(Error::PlatformExists(_)),
+        ));
+        assert!(matches!(
+            add_platform(&path, "ios", "ios", &options, None),
+            Err(Error::Init(crate::op::init::Error::PlatformUnknown(_))),
+        ));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), old);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,477 @@
+//! Run Platform Integration
+//!
+//! Build the platform integration, deploy it to a device, and launch it.
+//! Output of the application is streamed until the application exits or the
+//! operation is interrupted.
+
+/// Run Errors
+///
+/// This is the exhaustive list of possible errors raised by the run
+/// operation. See each error for details.
+pub enum Error {
+    /// Specified key required but missing in manifest.
+    ManifestKey(&'static str),
+    /// Building the platform integration failed.
+    Build(crate::op::build::Error),
+    /// No artifact was built for the specified build variant.
+    ArtifactMissing(String),
+    /// Multiple artifacts were built for the specified build variant, and
+    /// none can be picked based on its ABI.
+    ArtifactAmbiguous(String),
+    /// No artifact of the specified build variant supports any of the
+    /// specified device ABIs.
+    ArtifactAbi(String, Vec<String>),
+    /// No device is attached.
+    DeviceMissing,
+    /// Multiple devices are attached, but no device was selected.
+    DeviceAmbiguous(Vec<String>),
+    /// The selected device is not attached or not ready.
+    DeviceUnknown(String),
+    /// The specified tool could not be invoked.
+    Exec(std::ffi::OsString, std::io::Error),
+    /// The specified tool failed running the specified operation.
+    Tool(std::ffi::OsString, &'static str),
+    /// The application process could not be found after launch.
+    Process(String),
+}
+
+impl Error {
+    fn from_manifest_error_view(error: crate::manifest::ErrorView) -> Self {
+        match error {
+            crate::manifest::ErrorView::MissingKey(v) => Self::ManifestKey(v),
+        }
+    }
+}
+
+/// Run Options
+///
+/// This contains all options of the run operation that are not part of the
+/// manifest. Defaults are used for all options left unset.
+#[derive(Default)]
+pub struct Options {
+    /// Options passed to the build operation. Its variant is overridden by
+    /// the variant to deploy.
+    pub build: crate::op::build::Options,
+    /// Build variant to deploy. Defaults to `debug`.
+    pub variant: Option<String>,
+    /// Serial of the device to deploy to. If unset, the single attached
+    /// device is used.
+    pub device: Option<String>,
+    /// Path to the `adb` executable. If unset, the `adb` of the Android SDK
+    /// is used, if present, or `adb` is looked up in `$PATH`.
+    pub adb: Option<std::path::PathBuf>,
+    /// Whether to stream the application log after launch.
+    pub log: bool,
+}
+
+// Invoke `adb` with the given arguments
+//
+// Run `adb` with the given arguments, capturing its output. If a device is
+// given, the command is directed at it. `op` describes the operation for
+// error reporting.
+fn adb_output(
+    adb: &std::path::Path,
+    device: Option<&str>,
+    args: &[&dyn AsRef<std::ffi::OsStr>],
+    op: &'static str,
+) -> Result<String, Error> {
+    let mut cmd = std::process::Command::new(adb);
+
+    if let Some(v) = device {
+        cmd.arg("-s");
+        cmd.arg(v);
+    }
+    for v in args {
+        cmd.arg(v.as_ref());
+    }
+
+    cmd.stdin(std::process::Stdio::null());
+    cmd.stderr(std::process::Stdio::inherit());
+
+    let output = cmd.output().map_err(
+        |v| Error::Exec(adb.as_os_str().to_os_string(), v),
+    )?;
+    if !output.status.success() {
+        return Err(Error::Tool(adb.as_os_str().to_os_string(), op));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// Parse `adb devices` output
+//
+// The output of `adb devices` starts with a header line, followed by a line
+// per device with its serial and state separated by whitespace. Return all
+// devices that are ready (i.e., in state `device`).
+fn parse_adb_devices(output: &str) -> Vec<String> {
+    output.lines()
+        .skip_while(|v| !v.starts_with("List of devices"))
+        .skip(1)
+        .filter_map(|v| {
+            let mut iter = v.split_whitespace();
+            match (iter.next(), iter.next()) {
+                (Some(serial), Some("device")) => Some(serial.to_string()),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+// Select a device
+//
+// Pick the requested device from the list of ready devices, or the single
+// ready device if none was requested.
+fn select_device(
+    devices: Vec<String>,
+    requested: Option<&str>,
+) -> Result<String, Error> {
+    match requested {
+        Some(v) => {
+            if devices.iter().any(|d| d == v) {
+                Ok(v.to_string())
+            } else {
+                Err(Error::DeviceUnknown(v.to_string()))
+            }
+        },
+        None => {
+            match devices.len() {
+                0 => Err(Error::DeviceMissing),
+                1 => Ok(devices.into_iter().next().unwrap()),
+                _ => Err(Error::DeviceAmbiguous(devices)),
+            }
+        },
+    }
+}
+
+// Select the APK to deploy
+//
+// Pick the APK of the given variant to deploy. If there is only one, it is
+// used regardless of its ABI. Otherwise, the APK of the most preferred ABI
+// of the device is used, falling back to the universal APK, if any. The
+// device ABIs are only queried if needed.
+fn select_android_apk(
+    variant: &str,
+    apks: &[(Option<String>, std::path::PathBuf)],
+    device_abis: &mut dyn FnMut() -> Result<Vec<String>, Error>,
+) -> Result<std::path::PathBuf, Error> {
+    match apks.len() {
+        0 => return Err(Error::ArtifactMissing(variant.to_string())),
+        1 => return Ok(apks[0].1.clone()),
+        _ => {},
+    }
+
+    let abis = device_abis()?;
+    for abi in abis.iter() {
+        if let Some((_, v)) = apks.iter().find(|(a, _)| a.as_deref() == Some(abi.as_str())) {
+            return Ok(v.clone());
+        }
+    }
+
+    let mut universal = apks.iter().filter(|(a, _)| a.is_none());
+    match (universal.next(), universal.next()) {
+        (Some((_, v)), None) => Ok(v.clone()),
+        (Some(_), Some(_)) => Err(Error::ArtifactAmbiguous(variant.to_string())),
+        (None, _) => Err(Error::ArtifactAbi(variant.to_string(), abis)),
+    }
+}
+
+// Deploy and launch an Android APK
+//
+// Install the APK matching the selected device, start the given activity of the
+// application, and optionally stream the log of the application process.
+fn run_android_apk(
+    adb: &std::path::Path,
+    device: Option<&str>,
+    variant: &str,
+    apks: &[(Option<String>, std::path::PathBuf)],
+    application_id: &str,
+    activity: &str,
+    log: bool,
+) -> Result<(), Error> {
+    // Pick the device to operate on. All further commands are directed at
+    // this device, so they are not affected by devices attached later on.
+    let devices = adb_output(adb, None, &[&"devices"], "devices")?;
+    let device = select_device(parse_adb_devices(&devices), device)?;
+
+    // Pick the APK matching the device. The device lists its supported ABIs
+    // in order of preference.
+    let apk = select_android_apk(variant, apks, &mut || {
+        let v = adb_output(
+            adb,
+            Some(&device),
+            &[&"shell", &"getprop", &"ro.product.cpu.abilist"],
+            "getprop",
+        )?;
+        Ok(v.trim().split(',').filter(|v| !v.is_empty()).map(|v| v.to_string()).collect())
+    })?;
+
+    // Install the APK, replacing any previous installation but retaining
+    // its data.
+    adb_output(adb, Some(&device), &[&"install", &"-r", &apk], "install")?;
+
+    // Start the launcher activity of the application.
+    let component = format!("{}/{}", application_id, activity);
+    adb_output(
+        adb,
+        Some(&device),
+        &[
+            &"shell", &"am", &"start",
+            &"-a", &"android.intent.action.MAIN",
+            &"-c", &"android.intent.category.LAUNCHER",
+            &"-n", &component,
+        ],
+        "start",
+    )?;
+
+    if !log {
+        return Ok(());
+    }
+
+    // The application process is spawned asynchronously, so wait for it to
+    // show up. `pidof` fails if no process matches, so ignore its errors
+    // until we give up.
+    let mut pid = None;
+    for _ in 0..20 {
+        if let Ok(v) = adb_output(
+            adb,
+            Some(&device),
+            &[&"shell", &"pidof", &"-s", &application_id],
+            "pidof",
+        ) {
+            let v = v.trim();
+            if !v.is_empty() && v.chars().all(|c| c.is_ascii_digit()) {
+                pid = Some(v.to_string());
+                break;
+            }
+        }
+        std::thread::sleep(std::time::Duration::from_millis(250));
+    }
+    let pid = pid.ok_or_else(|| Error::Process(application_id.to_string()))?;
+
+    // Stream the log of the application process. This only returns once
+    // `adb` exits, or the operation is interrupted.
+    let mut cmd = std::process::Command::new(adb);
+    cmd.arg("-s");
+    cmd.arg(&device);
+    cmd.arg("logcat");
+    cmd.arg(format!("--pid={}", pid));
+    cmd.stdin(std::process::Stdio::null());
+    cmd.stdout(std::process::Stdio::inherit());
+    cmd.stderr(std::process::Stdio::inherit());
+
+    let status = cmd.status().map_err(
+        |v| Error::Exec(adb.as_os_str().to_os_string(), v),
+    )?;
+    if !status.success() {
+        return Err(Error::Tool(adb.as_os_str().to_os_string(), "logcat"));
+    }
+
+    Ok(())
+}
+
+// Android-specific backend to `run()`.
+fn run_android(
+    manifest: &crate::manifest::Manifest,
+    options: &Options,
+    variant: &str,
+    report: &crate::op::build::Report,
+    android: &crate::manifest::RawPlatformAndroid,
+) -> Result<(), Error> {
+    let view_android = android.view(&manifest.raw)
+        .map_err(Error::from_manifest_error_view)?;
+
+    // Use the `adb` of the configured SDK, unless overridden. Fall back to
+    // `$PATH` lookup if the SDK lacks the platform-tools.
+    let adb: std::path::PathBuf = if let Some(v) = options.adb.as_ref() {
+        v.clone()
+    } else {
+        let v = manifest.absolute_path(&view_android.sdk_path)
+            .join("platform-tools")
+            .join("adb");
+        if v.is_file() { v } else { "adb".into() }
+    };
+
+    // The launcher activity depends on the activity kind. Only the framework
+    // `NativeActivity` lives outside the application namespace.
+    let activity = match view_android.activity_kind {
+        crate::manifest::RawPlatformAndroidActivityKind::NativeActivity => {
+            "android.app.NativeActivity".to_string()
+        },
+        _ => format!("{}.MainActivity", view_android.namespace),
+    };
+
+    // Consider all APKs of the selected variant. With ABI splits, there is
+    // an APK per ABI.
+    let apks: Vec<(Option<String>, std::path::PathBuf)> = report.artifacts.iter()
+        .filter(|v| v.kind == crate::op::build::ReportArtifactKind::Apk && v.variant == variant)
+        .map(|v| (v.abi.clone(), v.path.clone()))
+        .collect();
+
+    run_android_apk(
+        &adb,
+        options.device.as_deref(),
+        variant,
+        &apks,
+        &view_android.application_id,
+        &activity,
+        options.log,
+    )
+}
+
+/// Run platform integration
+///
+/// Build the selected build variant of the specified platform, deploy its
+/// artifact to a device, and launch it. See `Options` for the available
+/// parameters.
+pub fn run(
+    manifest: &crate::manifest::Manifest,
+    metadata: &crate::cargo::Metadata,
+    platform: &crate::manifest::RawPlatform,
+    options: &Options,
+) -> Result<(), Error> {
+    let variant = options.variant.as_deref().unwrap_or("debug");
+    let options_build = crate::op::build::Options {
+        variant: Some(variant.to_string()),
+        ..options.build.clone()
+    };
+    let report = crate::op::build::build(manifest, metadata, platform, &options_build)
+        .map_err(Error::Build)?;
+
+    match platform.configuration {
+        Some(crate::manifest::RawPlatformConfiguration::Android(ref v)) => {
+            run_android(manifest, options, variant, &report, v)
+        },
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Verify `adb devices` parsing
+    //
+    // Only devices in state `device` are ready for use. Offline and
+    // unauthorized devices must be skipped, as well as daemon noise.
+    #[test]
+    fn adb_devices_parse() {
+        let s = concat!(
+            "* daemon not running; starting now at tcp:5037\n",
+            "* daemon started successfully\n",
+            "List of devices attached\n",
+            "emulator-5554\tdevice\n",
+            "0123456789ABCDEF\tunauthorized\n",
+            "192.168.0.2:5555\toffline\n",
+            "R58M123456\tdevice product:foo model:bar\n",
+            "\n",
+        );
+
+        assert_eq!(parse_adb_devices(s), ["emulator-5554", "R58M123456"]);
+        assert!(parse_adb_devices("List of devices attached\n\n").is_empty());
+    }
+
+    // Verify device selection
+    //
+    // A requested device must be ready. Without a request, exactly one
+    // device must be ready.
+    #[test]
+    fn device_select() {
+        let l = || vec!["a".to_string(), "b".to_string()];
+
+        assert_eq!(select_device(l(), Some("b")).ok().unwrap(), "b");
+        assert!(matches!(select_device(l(), Some("c")), Err(Error::DeviceUnknown(_))));
+        assert!(matches!(select_device(l(), None), Err(Error::DeviceAmbiguous(_))));
+        assert!(matches!(select_device(vec![], None), Err(Error::DeviceMissing)));
+        assert_eq!(select_device(vec!["a".to_string()], None).ok().unwrap(), "a");
+    }
+
+    // Verify APK selection
+    //
+    // A single APK is used as is. Otherwise, the most preferred device ABI
+    // wins, falling back to the universal APK.
+    #[test]
+    fn apk_select() {
+        let apks = |v: &[(Option<&str>, &str)]| -> Vec<(Option<String>, std::path::PathBuf)> {
+            v.iter().map(|(a, p)| (a.map(|v| v.to_string()), p.into())).collect()
+        };
+        let abis = |v: &'static [&'static str]| {
+            move || -> Result<Vec<String>, Error> { Ok(v.iter().map(|v| v.to_string()).collect()) }
+        };
+
+        let l = apks(&[(None, "a.apk")]);
+        assert_eq!(select_android_apk("debug", &l, &mut || unreachable!()).ok().unwrap(), std::path::Path::new("a.apk"));
+        assert!(matches!(select_android_apk("debug", &[], &mut abis(&[])), Err(Error::ArtifactMissing(_))));
+
+        let l = apks(&[(Some("x86"), "x86.apk"), (Some("armeabi-v7a"), "arm.apk"), (None, "all.apk")]);
+        assert_eq!(select_android_apk("debug", &l, &mut abis(&["arm64-v8a", "armeabi-v7a"])).ok().unwrap(), std::path::Path::new("arm.apk"));
+        assert_eq!(select_android_apk("debug", &l, &mut abis(&["arm64-v8a"])).ok().unwrap(), std::path::Path::new("all.apk"));
+
+        let l = apks(&[(Some("x86"), "x86.apk"), (Some("armeabi-v7a"), "arm.apk")]);
+        assert!(matches!(select_android_apk("debug", &l, &mut abis(&["arm64-v8a"])), Err(Error::ArtifactAbi(_, _))));
+
+        let l = apks(&[(None, "a.apk"), (None, "b.apk")]);
+        assert!(matches!(select_android_apk("debug", &l, &mut abis(&["x86"])), Err(Error::ArtifactAmbiguous(_))));
+    }
+
+    // Verify deployment via a scripted `adb`
+    //
+    // Substitute `adb` with a shell script that records its invocations, and
+    // verify the deployment runs the expected commands in order.
+    #[cfg(unix)]
+    #[test]
+    fn adb_scripted_run() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("osiris-platform-test-run-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let adb = dir.join("adb");
+        let log = dir.join("adb.log");
+        std::fs::write(
+            &adb,
+            format!(
+                concat!(
+                    "#!/bin/sh\n",
+                    "echo \"$*\" >> '{0}'\n",
+                    "case \"$*\" in\n",
+                    "    devices) printf 'List of devices attached\\nserial0\\tdevice\\n' ;;\n",
+                    "    *pidof*) echo 1234 ;;\n",
+                    "    *getprop*) echo 'arm64-v8a,armeabi-v7a,armeabi' ;;\n",
+                    "esac\n",
+                ),
+                log.display(),
+            ),
+        ).unwrap();
+        std::fs::set_permissions(&adb, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        assert!(run_android_apk(
+            &adb,
+            None,
+            "debug",
+            &[
+                (Some("x86_64".to_string()), "/foo/app-x86_64.apk".into()),
+                (Some("arm64-v8a".to_string()), "/foo/app.apk".into()),
+                (None, "/foo/app-universal.apk".into()),
+            ],
+            "foo.bar",
+            "foo.bar.MainActivity",
+            true,
+        ).is_ok());
+
+        let calls = std::fs::read_to_string(&log).unwrap();
+        assert_eq!(
+            calls.lines().collect::<Vec<_>>(),
+            [
+                "devices",
+                "-s serial0 shell getprop ro.product.cpu.abilist",
+                "-s serial0 install -r /foo/app.apk",
+                "-s serial0 shell am start -a android.intent.action.MAIN -c android.intent.category.LAUNCHER -n foo.bar/foo.bar.MainActivity",
+                "-s serial0 shell pidof -s foo.bar",
+                "-s serial0 logcat --pid=1234",
+            ],
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,353 @@
+//! Symbolicate Native Backtraces
+//!
+//! Resolve the program counters of native backtraces (e.g., from logcat or
+//! tombstones) to functions, source files, and lines. The unstripped native
+//! libraries archived by the build operation are used to look up the DWARF
+//! debug information.
+
+/// Symbolicate Errors
+///
+/// This is the exhaustive list of possible errors raised by the symbolicate
+/// operation. See each error for details.
+pub enum Error {
+    /// No native debug symbols were archived for the specified build variant.
+    SymbolsMissing(String),
+    /// Reading the file at the specified path failed with the given error.
+    FileRead(std::ffi::OsString, std::io::Error),
+    /// The native library at the specified path could not be parsed.
+    Library(std::ffi::OsString),
+    /// Reading the input failed with the given error.
+    Input(std::io::Error),
+    /// Writing the output failed with the given error.
+    Output(std::io::Error),
+}
+
+/// Symbolicate Options
+///
+/// This contains all options of the symbolicate operation that are not part
+/// of the manifest. Defaults are used for all options left unset.
+#[derive(Default)]
+pub struct Options {
+    /// Build variant whose symbols are used. Defaults to `release`.
+    pub variant: Option<String>,
+    /// ABI whose symbols are used. If unset, the ABI is taken from the
+    /// input, or derived from the library paths.
+    pub abi: Option<String>,
+}
+
+// Backtrace frame
+//
+// A single frame of a native backtrace, referring to a program counter
+// relative to the start of a library.
+#[derive(Debug, PartialEq, Eq)]
+struct Frame<'a> {
+    pc: u64,
+    library: &'a str,
+}
+
+// Parse a backtrace frame
+//
+// Android prints native frames as `#<n> pc <pc> <library> ...`, possibly
+// prefixed by logcat metadata. Return the program counter and library path
+// of the frame, if the line contains one.
+fn parse_frame(line: &str) -> Option<Frame<'_>> {
+    let mut tokens = line.split_whitespace()
+        .skip_while(|v| {
+            !(v.len() > 1 && v.starts_with('#') && v[1..].chars().all(|v| v.is_ascii_digit()))
+        })
+        .skip(1);
+
+    if tokens.next()? != "pc" {
+        return None;
+    }
+
+    let pc = tokens.next()?;
+    let pc = u64::from_str_radix(pc.strip_prefix("0x").unwrap_or(pc), 16).ok()?;
+    let library = tokens.next()?;
+
+    Some(Frame { pc, library })
+}
+
+// Map a runtime ABI name to an Android ABI
+//
+// Tombstones and library paths on the device use the runtime names (e.g.,
+// `arm64`), rather than the Android ABI names (e.g., `arm64-v8a`).
+fn android_abi(name: &str) -> Option<&'static str> {
+    match name {
+        "arm" | "armeabi-v7a" => Some("armeabi-v7a"),
+        "arm64" | "arm64-v8a" => Some("arm64-v8a"),
+        "x86" => Some("x86"),
+        "x86_64" => Some("x86_64"),
+        _ => None,
+    }
+}
+
+// Parse the ABI of a tombstone
+//
+// Tombstones contain a line `ABI: '<abi>'` describing the ABI of the crashed
+// process. Return the Android ABI, if the line is such a line.
+fn parse_abi(line: &str) -> Option<&'static str> {
+    let (_, v) = line.split_once("ABI: '")?;
+    let (v, _) = v.split_once('\'')?;
+    android_abi(v)
+}
+
+// Derive the ABI of a library path
+//
+// Libraries extracted from an APK reside in `lib/<abi>/` of the application
+// directory. Return the Android ABI, if the path follows this layout.
+fn library_abi(library: &str) -> Option<&'static str> {
+    let mut iter = library.rsplit('/').skip(1);
+    let abi = iter.next()?;
+    if iter.next()? != "lib" {
+        return None;
+    }
+    android_abi(abi)
+}
+
+// Native library with debug information
+type Library = addr2line::Context<addr2line::gimli::EndianRcSlice<addr2line::gimli::RunTimeEndian>>;
+
+// Load a native library
+//
+// Read the native library at the given path and parse its debug information.
+fn load_library(path: &std::path::Path) -> Result<Library, Error> {
+    let data = std::fs::read(path).map_err(
+        |v| Error::FileRead(path.as_os_str().to_os_string(), v),
+    )?;
+    let file = addr2line::object::File::parse(data.as_slice()).map_err(
+        |_| Error::Library(path.as_os_str().to_os_string()),
+    )?;
+    addr2line::Context::new(&file).map_err(
+        |_| Error::Library(path.as_os_str().to_os_string()),
+    )
+}
+
+// Resolve a program counter
+//
+// Return all frames at the given program counter, innermost first. Inlined
+// functions yield a frame each. Every frame is formatted as
+// `<function> at <file>:<line>`, omitting unknown parts.
+fn resolve(library: &Library, pc: u64) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    let Ok(mut frames) = library.find_frames(pc).skip_all_loads() else {
+        return lines;
+    };
+
+    while let Ok(Some(frame)) = frames.next() {
+        let mut v = frame.function.as_ref()
+            .and_then(|v| v.demangle().ok())
+            .map(|v| v.into_owned())
+            .unwrap_or_else(|| "??".to_string());
+
+        if let Some(location) = frame.location.as_ref() {
+            if let Some(file) = location.file {
+                v.push_str(" at ");
+                v.push_str(file);
+                if let Some(line) = location.line {
+                    v.push_str(&format!(":{}", line));
+                }
+            }
+        }
+
+        lines.push(v);
+    }
+
+    lines
+}
+
+// Symbolicate a backtrace
+//
+// Copy all lines of `input` to `output`. Every backtrace frame in a library
+// found via `lookup` is followed by the resolved frames at its program
+// counter. The ABI is taken from `abi`, if given, otherwise from the input
+// or the library paths. `lookup` is given the ABI (if known) and the file
+// name of the library.
+fn symbolicate_stream(
+    input: &mut dyn std::io::BufRead,
+    output: &mut dyn std::io::Write,
+    abi: Option<&str>,
+    lookup: &mut dyn FnMut(Option<&str>, &str) -> Option<std::path::PathBuf>,
+) -> Result<(), Error> {
+    let mut libraries: std::collections::BTreeMap<std::path::PathBuf, Library> = Default::default();
+    let mut abi_input: Option<&'static str> = None;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        if input.read_line(&mut line).map_err(Error::Input)? == 0 {
+            break;
+        }
+
+        output.write_all(line.as_bytes()).map_err(Error::Output)?;
+        if !line.ends_with('\n') {
+            output.write_all(b"\n").map_err(Error::Output)?;
+        }
+
+        if let Some(v) = parse_abi(&line) {
+            abi_input = Some(v);
+            continue;
+        }
+
+        let Some(frame) = parse_frame(&line) else {
+            continue;
+        };
+        let name = frame.library.rsplit('/').next().unwrap_or(frame.library);
+        let abi_frame = abi.or(abi_input).or_else(|| library_abi(frame.library));
+        let Some(path) = lookup(abi_frame, name) else {
+            continue;
+        };
+
+        if !libraries.contains_key(&path) {
+            let library = load_library(&path)?;
+            libraries.insert(path.clone(), library);
+        }
+
+        // Indent the resolved frames below the frame they belong to.
+        let indent = line.len() - line.trim_start().len();
+        for v in resolve(&libraries[&path], frame.pc) {
+            writeln!(output, "{:indent$}    {}", "", v, indent = indent)
+                .map_err(Error::Output)?;
+        }
+    }
+
+    output.flush().map_err(Error::Output)
+}
+
+/// Symbolicate native backtraces
+///
+/// Read native backtraces of the specified platform from `input`, and write
+/// them to `output` with all frames of the application libraries resolved.
+/// The native debug symbols archived by the build operation for the selected
+/// build variant are used. See `Options` for the available parameters.
+pub fn symbolicate(
+    metadata: &crate::cargo::Metadata,
+    platform: &crate::manifest::RawPlatform,
+    options: &Options,
+    input: &mut dyn std::io::BufRead,
+    output: &mut dyn std::io::Write,
+) -> Result<(), Error> {
+    let variant = options.variant.as_deref().unwrap_or("release");
+    let path = crate::op::build::symbols_directory(metadata, &platform.id).join(variant);
+    if !path.is_dir() {
+        return Err(Error::SymbolsMissing(variant.to_string()));
+    }
+
+    // Without a known ABI, only use a library if a single ABI provides it.
+    let mut lookup = |abi: Option<&str>, name: &str| -> Option<std::path::PathBuf> {
+        if let Some(abi) = abi {
+            let v = path.join(abi).join(name);
+            return v.is_file().then_some(v);
+        }
+
+        let mut matches = std::fs::read_dir(&path).ok()?
+            .filter_map(|v| v.ok())
+            .map(|v| v.path().join(name))
+            .filter(|v| v.is_file());
+        match (matches.next(), matches.next()) {
+            (Some(v), None) => Some(v),
+            _ => None,
+        }
+    };
+
+    symbolicate_stream(input, output, options.abi.as_deref(), &mut lookup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Verify frame parsing
+    //
+    // Frames are found in plain backtraces and logcat lines, and other lines
+    // are ignored.
+    #[test]
+    fn frame_parse() {
+        assert_eq!(
+            parse_frame("      #00 pc 000000000004f2d8  /data/app/foo/lib/arm64/libapp.so (foo+8)"),
+            Some(Frame { pc: 0x4f2d8, library: "/data/app/foo/lib/arm64/libapp.so" }),
+        );
+        assert_eq!(
+            parse_frame("10-18 12:00:00.000  1234  1234 F DEBUG   :       #12 pc 0x1a2b  /system/lib64/libc.so"),
+            Some(Frame { pc: 0x1a2b, library: "/system/lib64/libc.so" }),
+        );
+        assert_eq!(parse_frame("#01 sp 00007ffd  /foo.so"), None);
+        assert_eq!(parse_frame("backtrace:"), None);
+
+        assert_eq!(parse_abi("ABI: 'arm64'"), Some("arm64-v8a"));
+        assert_eq!(parse_abi("pid: 1, tid: 1, name: foo"), None);
+        assert_eq!(library_abi("/data/app/foo/lib/arm/libapp.so"), Some("armeabi-v7a"));
+        assert_eq!(library_abi("/system/lib64/libc.so"), None);
+    }
+
+    // Verify symbolication against a local ELF fixture
+    //
+    // Build a small shared library with debug information via the system C
+    // compiler, and resolve a frame at the address of one of its functions.
+    // The test is skipped if no C compiler is available.
+    #[cfg(unix)]
+    #[test]
+    fn symbolicate_fixture() {
+        use addr2line::object::{Object, ObjectSymbol};
+
+        let dir = std::env::temp_dir().join(format!("osiris-symbolicate-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("fixture.c"),
+            "int osiris_fixture(int v) {\n    return v * 3;\n}\n",
+        ).unwrap();
+
+        let status = std::process::Command::new("cc")
+            .current_dir(&dir)
+            .args(["-g", "-O0", "-shared", "-fPIC", "-o", "libfixture.so", "fixture.c"])
+            .status();
+        if !status.map(|v| v.success()).unwrap_or(false) {
+            std::fs::remove_dir_all(&dir).unwrap();
+            return;
+        }
+
+        let data = std::fs::read(dir.join("libfixture.so")).unwrap();
+        let file = addr2line::object::File::parse(data.as_slice()).unwrap();
+        let address = file.symbols()
+            .find(|v| v.name() == Ok("osiris_fixture"))
+            .unwrap()
+            .address();
+
+        let input = format!(
+            concat!(
+                "ABI: 'x86_64'\n",
+                "backtrace:\n",
+                "      #00 pc {:016x}  /data/app/foo/lib/x86_64/libfixture.so\n",
+                "      #01 pc 0000000000001234  /system/lib64/libc.so\n",
+            ),
+            address,
+        );
+        let mut output = Vec::new();
+        let mut lookups = Vec::new();
+        symbolicate_stream(
+            &mut input.as_bytes(),
+            &mut output,
+            None,
+            &mut |abi, name| {
+                lookups.push((abi.map(|v| v.to_string()), name.to_string()));
+                (name == "libfixture.so").then(|| dir.join(name))
+            },
+        ).ok().unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[3].trim_start().starts_with("osiris_fixture at "));
+        assert!(lines[3].ends_with("fixture.c:1"));
+        assert_eq!(
+            lookups,
+            [
+                (Some("x86_64".to_string()), "libfixture.so".to_string()),
+                (Some("x86_64".to_string()), "libc.so".to_string()),
+            ],
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
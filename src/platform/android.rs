@@ -33,7 +33,9 @@
 //! is [rust-android-gradle](https://github.com/mozilla/rust-android-gradle)
 //! by Mozilla to directly pull Cargo into Gradle. The latter does not
 //! automatically benefit from optimizations the Android project puts into
-//! the CMake integration for native code, though.
+//! the CMake integration for native code, though. The platform abstractions
+//! support both, selected via the `integration` key of the Android platform
+//! table in the manifest.
 //!
 //! The Osiris platform module places no restrictions on how to structure code
 //! for an application. The Gradle based code and the Cargo based code can be
@@ -34,6 +34,91 @@ pub fn symbolize(input: &str) -> String {
     v
 }
 
+/// ## Mangle names for JNI
+///
+/// Create the mangled form of a Java class or method name, as used in the
+/// symbol names of native methods (`Java_<class>_<method>`). Package
+/// separators (`.` or `/`) become `_`, while `_`, `;` and `[` are escaped as
+/// `_1`, `_2` and `_3`. All other characters besides ASCII alphanumerics are
+/// escaped as `_0xxxx` with their UTF-16 code units in lowercase hex.
+pub fn jni_mangle(input: &str) -> String {
+    let mut v = String::with_capacity(input.len());
+
+    for c in input.chars() {
+        match c {
+            '.' | '/' => v.push('_'),
+            '_' => v.push_str("_1"),
+            ';' => v.push_str("_2"),
+            '[' => v.push_str("_3"),
+            c if c.is_ascii_alphanumeric() => v.push(c),
+            c => {
+                let mut buf = [0u16; 2];
+                for u in c.encode_utf16(&mut buf) {
+                    v.push_str(&format!("_0{:04x}", u));
+                }
+            },
+        }
+    }
+
+    v
+}
+
+/// ## Parse hex colors
+///
+/// Parse a color in the hexadecimal notation `#RGB` or `#RRGGBB`, as used by
+/// web stylesheets and Android resources. The color is returned as opaque
+/// RGBA quadruple. Returns `None` if the input is not a valid color.
+pub fn parse_color(input: &str) -> Option<[u8; 4]> {
+    let hex = input.strip_prefix('#')?;
+    if !hex.chars().all(|v| v.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let digit = |i: usize, n: usize| u8::from_str_radix(&hex[i..i + n], 16).ok();
+    match hex.len() {
+        3 => Some([digit(0, 1)? * 0x11, digit(1, 1)? * 0x11, digit(2, 1)? * 0x11, 0xff]),
+        6 => Some([digit(0, 2)?, digit(2, 2)?, digit(4, 2)?, 0xff]),
+        _ => None,
+    }
+}
+
+/// ## Format hex colors
+///
+/// Format the color part of an RGBA quadruple in the hexadecimal notation
+/// `#RRGGBB`. The alpha channel is ignored.
+pub fn format_color(color: [u8; 4]) -> String {
+    format!("#{:02X}{:02X}{:02X}", color[0], color[1], color[2])
+}
+
+/// ## Check for light colors
+///
+/// Check whether the luminance of a color is above the middle of the range,
+/// so dark content should be placed on top of it. The alpha channel is
+/// ignored.
+pub fn color_is_light(color: [u8; 4]) -> bool {
+    let luminance = 0.2126 * color[0] as f32
+        + 0.7152 * color[1] as f32
+        + 0.0722 * color[2] as f32;
+
+    luminance > 127.5
+}
+
+/// ## Hash data with FNV-1a
+///
+/// Compute the 64-bit FNV-1a hash of the input. This is a simple and stable
+/// non-cryptographic hash, suitable to detect modifications of files, but
+/// not suitable to protect against malicious modifications.
+pub fn fnv1a64(input: &[u8]) -> u64 {
+    let mut v: u64 = 0xcbf29ce484222325;
+
+    for b in input {
+        v ^= *b as u64;
+        v = v.wrapping_mul(0x100000001b3);
+    }
+
+    v
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -50,4 +135,48 @@ mod tests {
 
         assert_eq!(symbolize("foo(bar)"), "foo_bar_");
     }
+
+    // Verify JNI name mangling, including all escape sequences and
+    // characters outside of the basic multilingual plane.
+    #[test]
+    fn jni_mangle_basic() {
+        assert_eq!(jni_mangle("foo.bar.Baz"), "foo_bar_Baz");
+        assert_eq!(jni_mangle("foo/bar_baz"), "foo_bar_1baz");
+        assert_eq!(jni_mangle("[Ljava/lang/String;"), "_3Ljava_lang_String_2");
+        assert_eq!(jni_mangle("f\u{e9}e"), "f_000e9e");
+        assert_eq!(jni_mangle("\u{1f600}"), "_0d83d_0de00");
+    }
+
+    // Verify parsing of hex colors in both notations, and refusal of
+    // malformed input.
+    #[test]
+    fn parse_color_basic() {
+        assert_eq!(parse_color("#000"), Some([0x00, 0x00, 0x00, 0xff]));
+        assert_eq!(parse_color("#f0A"), Some([0xff, 0x00, 0xaa, 0xff]));
+        assert_eq!(parse_color("#12aBeF"), Some([0x12, 0xab, 0xef, 0xff]));
+        assert_eq!(parse_color("123456"), None);
+        assert_eq!(parse_color("#1234"), None);
+        assert_eq!(parse_color("#12345g"), None);
+        assert_eq!(parse_color("#+1+2+3"), None);
+    }
+
+    // Verify formatting and classification of colors.
+    #[test]
+    fn format_color_basic() {
+        assert_eq!(format_color([0x12, 0xab, 0x00, 0x80]), "#12AB00");
+        assert_eq!(parse_color(&format_color([0x12, 0xab, 0x00, 0xff])), Some([0x12, 0xab, 0x00, 0xff]));
+
+        assert!(color_is_light([0xff, 0xff, 0xff, 0xff]));
+        assert!(color_is_light([0xff, 0xfb, 0xfe, 0xff]));
+        assert!(!color_is_light([0x1c, 0x1b, 0x1f, 0xff]));
+        assert!(!color_is_light([0x00, 0x00, 0xff, 0xff]));
+    }
+
+    // Verify the FNV-1a hash against the reference test vectors.
+    #[test]
+    fn fnv1a64_basic() {
+        assert_eq!(fnv1a64(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a64(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(fnv1a64(b"foobar"), 0x85944171f73967e8);
+    }
 }